@@ -0,0 +1,548 @@
+//! Estimates of how good a hand is, found by dealing out the rest of the cards
+//! many times, as opposed to the precomputed tables in `look_up`.
+
+use models::{Card, CardBitset, MoneyInner, MAX_DECK_SIZE};
+use probability::{Count, EvalCount, Probability};
+use xs::Xs;
+
+pub type OpponentCount = u8;
+
+/// The amount of runouts to sample. Larger values give more accurate estimates,
+/// but take longer to compute.
+pub type Iterations = Count;
+
+pub const DEFAULT_ITERATIONS: Iterations = 1024;
+
+/// More than this many iterations would overflow the counts.
+pub const MAX_ITERATIONS: Iterations = Count::MAX / SHARE;
+
+/// The amount one whole pot is worth when counting wins. When multiple hands
+/// tie, this is split among them, so it is chosen to be evenly divisible by
+/// every small number of tied hands.
+const SHARE: Count = 2520;
+
+/// The fraction of the resulting pot that the `call_remainder` would be. If the
+/// probability of winning is above this, then calling is profitable in the long
+/// run.
+pub fn pot_odds(call_remainder: MoneyInner, pot_total: MoneyInner) -> Probability {
+    if call_remainder == 0 {
+        return 0
+    }
+
+    EvalCount {
+        win_count: call_remainder,
+        total: call_remainder.saturating_add(pot_total),
+    }.probability()
+}
+
+/// The cards that could still be dealt, set up once and then sampled from
+/// every iteration. Only as many cards as are drawn get shuffled, and the
+/// cards are left in whatever order they end up in between samples, since
+/// that is as good as any other order to start shuffling from.
+struct Remaining {
+    cards: [Card; MAX_DECK_SIZE as usize],
+    len: u8,
+    drawn: u8,
+}
+
+impl Remaining {
+    fn new(cards: CardBitset) -> Self {
+        let mut output = Self {
+            cards: [0; MAX_DECK_SIZE as usize],
+            len: 0,
+            drawn: 0,
+        };
+
+        for card in cards.iter() {
+            output.cards[usize::from(output.len)] = card;
+            output.len += 1;
+        }
+
+        output
+    }
+
+    /// All of the usual cards, other than the `known` ones.
+    fn without(known: CardBitset) -> Self {
+        let mut cards = CardBitset::full();
+        for card in known.iter() {
+            cards.remove(card);
+        }
+
+        Self::new(cards)
+    }
+
+    /// Makes every card available to be drawn again.
+    fn start_sample(&mut self) {
+        self.drawn = 0;
+    }
+
+    /// Draws one of the cards not yet drawn in this sample, with one step of
+    /// a Fisher–Yates shuffle.
+    fn draw(&mut self, rng: &mut Xs) -> Card {
+        debug_assert!(self.drawn < self.len, "Ran out of cards!");
+        if self.drawn >= self.len {
+            return <_>::default()
+        }
+
+        let i = usize::from(self.drawn);
+        let j = xs::range(rng, u32::from(self.drawn)..u32::from(self.len)) as usize;
+        self.cards.swap(i, j);
+        self.drawn += 1;
+
+        self.cards[i]
+    }
+}
+
+pub mod holdem {
+    use super::*;
+    use evaluate::{Eval, HoleCards};
+    use models::{DeckComposition, holdem::CommunityCards};
+
+    /// Estimate the probability that `hand` wins against `opponent_count` live
+    /// opponents with unknown hands, by sampling `iterations` possible runouts.
+    /// Ties count as a fraction of a win, split evenly among the tied hands.
+    /// Runouts are drawn from, and hands are ranked according to, `deck`.
+    /// This works for any game played like hold'em, such as Omaha, with the
+    /// opponents being dealt the same kind of hand as `hand`.
+    pub fn equity<H: HoleCards>(
+        rng: &mut Xs,
+        hand: H,
+        community_opt: Option<CommunityCards>,
+        opponent_count: OpponentCount,
+        deck: DeckComposition,
+        iterations: Iterations,
+    ) -> Probability {
+        let iterations = iterations.clamp(1, MAX_ITERATIONS);
+
+        // Cards that are not in the deck at all can be handled just like
+        // cards that are known to be somewhere else.
+        let mut known = deck.excluded();
+        for &card in hand.as_ref() {
+            known.set(card);
+        }
+
+        let mut board = [0; 5];
+        let known_board_len = match community_opt {
+            None => 0,
+            Some(CommunityCards::Flop(flop)) => {
+                board[..3].copy_from_slice(&flop);
+                3
+            },
+            Some(CommunityCards::Turn(flop, turn)) => {
+                board[..3].copy_from_slice(&flop);
+                board[3] = turn;
+                4
+            },
+            Some(CommunityCards::River(flop, turn, river)) => {
+                board[..3].copy_from_slice(&flop);
+                board[3] = turn;
+                board[4] = river;
+                5
+            },
+        };
+        for &card in &board[..known_board_len] {
+            known.set(card);
+        }
+
+        let mut remaining = Remaining::without(known);
+
+        let mut eval_count = EvalCount {
+            win_count: 0,
+            total: 0,
+        };
+
+        for _ in 0..iterations {
+            remaining.start_sample();
+
+            for card in &mut board[known_board_len..] {
+                *card = remaining.draw(rng);
+            }
+
+            let community_cards = CommunityCards::from(board);
+
+            let own_eval = hand.eval_with(deck, community_cards);
+
+            let mut best_other = Eval::WORST;
+            let mut best_other_count: Count = 0;
+            for _ in 0..opponent_count {
+                let mut other = H::default();
+                for card in other.as_mut() {
+                    *card = remaining.draw(rng);
+                }
+
+                let other_eval = other.eval_with(deck, community_cards);
+
+                if other_eval > best_other {
+                    best_other = other_eval;
+                    best_other_count = 1;
+                } else if other_eval == best_other {
+                    best_other_count += 1;
+                }
+            }
+
+            eval_count.total += SHARE;
+
+            use core::cmp::Ordering::*;
+            match own_eval.cmp(&best_other) {
+                Greater => {
+                    eval_count.win_count += SHARE;
+                },
+                Equal => {
+                    eval_count.win_count += SHARE / (best_other_count + 1);
+                },
+                Less => {},
+            }
+        }
+
+        eval_count.probability()
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use models::holdem::Hand;
+        use probability::SEVENTY_FIVE_PERCENT;
+
+        // Aces of clubs and diamonds
+        const POCKET_ACES: Hand = [0, 13];
+
+        #[test]
+        fn pocket_aces_are_a_big_favourite_heads_up() {
+            let mut rng = xs::from_seed([1; 16]);
+
+            let equity = equity(&mut rng, POCKET_ACES, None, 1, <_>::default(), 512);
+
+            assert!(equity > SEVENTY_FIVE_PERCENT, "{equity}");
+        }
+
+        #[test]
+        fn more_opponents_means_less_equity() {
+            let mut rng = xs::from_seed([2; 16]);
+
+            let heads_up = equity(&mut rng, POCKET_ACES, None, 1, <_>::default(), 512);
+            let against_five = equity(&mut rng, POCKET_ACES, None, 5, <_>::default(), 512);
+
+            assert!(against_five < heads_up, "{against_five} >= {heads_up}");
+        }
+
+        #[test]
+        fn everyone_splits_when_the_board_is_unbeatable() {
+            let mut rng = xs::from_seed([3; 16]);
+
+            // Royal flush in clubs on the board.
+            let board = [0, 12, 11, 10, 9];
+
+            let equity = equity(
+                &mut rng,
+                [14, 15],
+                Some(CommunityCards::from(board)),
+                3,
+                <_>::default(),
+                64,
+            );
+
+            // Everyone plays the board, so it's a four way split.
+            assert_eq!(equity, EvalCount { win_count: 1, total: 4 }.probability());
+        }
+
+        #[test]
+        fn double_suited_aces_are_a_favourite_heads_up_in_omaha() {
+            let mut rng = xs::from_seed([4; 16]);
+
+            // Aces and kings, in clubs and diamonds.
+            let hand: models::omaha::Hand = [0, 12, 13, 25];
+
+            let equity = equity(&mut rng, hand, None, 1, <_>::default(), 512);
+
+            assert!(equity > probability::FIFTY_PERCENT, "{equity}");
+        }
+    }
+}
+
+pub mod stud {
+    use super::*;
+    use evaluate::{Eval, stud_hand};
+    use models::stud::{HAND_LEN, Hand, UpCards};
+
+    /// Estimate the probability that the first `dealt` cards of `hand` win
+    /// against opponents showing `opponent_up_cards`, with `up_len` of those
+    /// cards dealt each, by sampling `iterations` possible ways the rest of the
+    /// cards could be dealt. Every opponent is assumed to have the same amount
+    /// of cards dealt as `hand` does. Ties count as a fraction of a win, split
+    /// evenly among the tied hands.
+    pub fn equity(
+        rng: &mut Xs,
+        hand: Hand,
+        dealt: u8,
+        opponent_up_cards: &[UpCards],
+        up_len: u8,
+        iterations: Iterations,
+    ) -> Probability {
+        let iterations = iterations.clamp(1, MAX_ITERATIONS);
+
+        let dealt = usize::from(dealt.min(HAND_LEN));
+        let up_len = usize::from(up_len);
+
+        let mut known = CardBitset::default();
+        for &card in &hand[..dealt] {
+            known.set(card);
+        }
+        for up_cards in opponent_up_cards {
+            for &card in &up_cards[..up_len] {
+                known.set(card);
+            }
+        }
+
+        let mut remaining = Remaining::without(known);
+
+        let mut eval_count = EvalCount {
+            win_count: 0,
+            total: 0,
+        };
+
+        for _ in 0..iterations {
+            remaining.start_sample();
+
+            let mut own_hand = hand;
+            for card in &mut own_hand[dealt..] {
+                *card = remaining.draw(rng);
+            }
+
+            let own_eval = stud_hand(own_hand);
+
+            let mut best_other = Eval::WORST;
+            let mut best_other_count: Count = 0;
+            for up_cards in opponent_up_cards {
+                // Which cards are face up doesn't matter for the evaluation,
+                // so the known ones can just go first.
+                let mut other_hand = Hand::default();
+                other_hand[..up_len].copy_from_slice(&up_cards[..up_len]);
+                for card in &mut other_hand[up_len..] {
+                    *card = remaining.draw(rng);
+                }
+
+                let other_eval = stud_hand(other_hand);
+
+                if other_eval > best_other {
+                    best_other = other_eval;
+                    best_other_count = 1;
+                } else if other_eval == best_other {
+                    best_other_count += 1;
+                }
+            }
+
+            eval_count.total += SHARE;
+
+            use core::cmp::Ordering::*;
+            match own_eval.cmp(&best_other) {
+                Greater => {
+                    eval_count.win_count += SHARE;
+                },
+                Equal => {
+                    eval_count.win_count += SHARE / (best_other_count + 1);
+                },
+                Less => {},
+            }
+        }
+
+        eval_count.probability()
+    }
+
+    #[test]
+    fn rolled_up_aces_are_a_big_favourite_against_a_low_card() {
+        let mut rng = xs::from_seed([5; 16]);
+
+        // Three aces, against a showing deuce of spades.
+        let mut hand = Hand::default();
+        hand[..3].copy_from_slice(&[0, 13, 26]);
+
+        let equity = equity(&mut rng, hand, 3, &[[40, 0, 0, 0]], 1, 512);
+
+        assert!(equity > probability::SEVENTY_FIVE_PERCENT, "{equity}");
+    }
+}
+
+pub mod five_card {
+    use super::*;
+    use evaluate::{Category, five_card_category_wild};
+    use models::WildCards;
+
+    pub const HAND_LEN: usize = 5;
+
+    pub type Hand = [Card; HAND_LEN];
+
+    /// Which cards in a hand to discard, where bit `i` set means the card at
+    /// index `i` is discarded.
+    pub type DiscardMask = u8;
+
+    pub const DISCARD_MASK_COUNT: usize = 1 << HAND_LEN;
+
+    /// An amount that a hand pays out, in the style of a video poker pay table.
+    /// Higher is better.
+    pub type Payout = u32;
+
+    pub fn payout(category: Category) -> Payout {
+        use Category::*;
+        match category {
+            HighCard => 0,
+            OnePair => 1,
+            TwoPair => 2,
+            ThreeOfAKind => 3,
+            Straight => 4,
+            Flush => 6,
+            FullHouse => 9,
+            FourOfAKind => 25,
+            StraightFlush => 50,
+            FiveOfAKind => 100,
+        }
+    }
+
+    /// Estimate, for every possible set of discards, the average `payout` of
+    /// the hand after drawing replacements. The averages are scaled up by
+    /// `PAYOUT_SCALE` to keep some precision. Each sampled deck is shared across
+    /// all of the sets of discards, so the estimates can be meaningfully compared
+    /// to each other even with a small amount of iterations.
+    pub fn draw_expected_values(
+        rng: &mut Xs,
+        hand: Hand,
+        wilds: WildCards,
+        iterations: Iterations,
+    ) -> [Payout; DISCARD_MASK_COUNT] {
+        let iterations = iterations.clamp(1, MAX_ITERATIONS);
+
+        let mut unknown = wilds.cards();
+        for card in hand {
+            unknown.remove(card);
+        }
+
+        let mut remaining = Remaining::new(unknown);
+
+        let mut sums = [0 as Payout; DISCARD_MASK_COUNT];
+
+        for _ in 0..iterations {
+            remaining.start_sample();
+
+            let replacements = [
+                remaining.draw(rng),
+                remaining.draw(rng),
+                remaining.draw(rng),
+                remaining.draw(rng),
+                remaining.draw(rng),
+            ];
+
+            for (mask, sum) in sums.iter_mut().enumerate() {
+                let mut new_hand = hand;
+                let mut replacement_index = 0;
+                for (i, card) in new_hand.iter_mut().enumerate() {
+                    if mask & (1 << i) != 0 {
+                        *card = replacements[replacement_index];
+                        replacement_index += 1;
+                    }
+                }
+
+                *sum = sum.saturating_add(payout(five_card_category_wild(new_hand, wilds)));
+            }
+        }
+
+        let mut output = [0; DISCARD_MASK_COUNT];
+        for (expected_value, sum) in output.iter_mut().zip(sums) {
+            *expected_value = sum.saturating_mul(PAYOUT_SCALE) / iterations;
+        }
+        output
+    }
+
+    pub const PAYOUT_SCALE: Payout = 256;
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn best_mask(hand: Hand) -> DiscardMask {
+            best_mask_with(hand, WildCards::None)
+        }
+
+        fn best_mask_with(hand: Hand, wilds: WildCards) -> DiscardMask {
+            let mut rng = xs::from_seed([4; 16]);
+
+            let expected_values = draw_expected_values(&mut rng, hand, wilds, 256);
+
+            let mut best = 0;
+            for mask in 0..DISCARD_MASK_COUNT {
+                if expected_values[mask] > expected_values[best] {
+                    best = mask;
+                }
+            }
+            best as DiscardMask
+        }
+
+        // Ranks are 0 for ace up to 12 for king, and suits advance by 13.
+        const CLUBS: Card = 0;
+        const DIAMONDS: Card = 13;
+        const HEARTS: Card = 26;
+        const SPADES: Card = 39;
+
+        #[test]
+        fn keeps_a_pair_and_draws_to_it() {
+            assert_eq!(
+                best_mask([CLUBS, DIAMONDS, HEARTS + 4, SPADES + 7, CLUBS + 9]),
+                0b11100
+            );
+        }
+
+        #[test]
+        fn draws_to_a_flush() {
+            assert_eq!(
+                best_mask([HEARTS + 1, HEARTS + 5, SPADES + 7, HEARTS + 9, HEARTS + 11]),
+                0b00100
+            );
+        }
+
+        #[test]
+        fn keeps_the_joker_when_jokers_are_wild() {
+            let mask = best_mask_with(
+                [models::JOKER, HEARTS + 4, SPADES + 7, CLUBS + 9, DIAMONDS + 11],
+                WildCards::Jokers,
+            );
+
+            assert_eq!(mask & 1, 0, "{mask:05b}");
+        }
+
+        #[test]
+        fn stands_pat_on_a_straight() {
+            assert_eq!(
+                best_mask([HEARTS + 4, CLUBS + 5, SPADES + 6, DIAMONDS + 7, HEARTS + 8]),
+                0
+            );
+        }
+    }
+}
+
+#[test]
+fn remaining_cards_are_each_drawn_once_per_sample() {
+    let mut rng = xs::from_seed([6; 16]);
+
+    let mut known = CardBitset::default();
+    known.set(0);
+    known.set(13);
+
+    let mut remaining = Remaining::without(known);
+
+    for _ in 0..4 {
+        remaining.start_sample();
+
+        let mut drawn = CardBitset::default();
+        for _ in 0..models::DECK_SIZE - 2 {
+            let card = remaining.draw(&mut rng);
+            assert!(!known.contains(card), "{card}");
+            assert!(!drawn.contains(card), "{card}");
+            drawn.set(card);
+        }
+    }
+}
+
+#[test]
+fn pot_odds_works_on_these_examples() {
+    assert_eq!(pot_odds(0, 100), 0);
+    assert_eq!(pot_odds(100, 100), probability::FIFTY_PERCENT);
+    assert_eq!(pot_odds(100, 300), probability::TWENTY_FIVE_PERCENT);
+}
@@ -1,11 +1,58 @@
 
-use models::holdem;
+use models::{holdem, omaha, stud, DeckComposition, WildCards};
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Eval(poker::Eval);
+pub struct Eval(EvalInner);
+
+/// Evals from different deck compositions are never compared with each other,
+/// except with `Eval::WORST`, which sorts below all of them.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum EvalInner {
+    Poker(poker::Eval),
+    /// Only possible with wild cards. The rank is aces high.
+    FiveOfAKind(models::Rank),
+    ShortDeck(ShortDeckKey),
+}
+
+impl core::fmt::Display for EvalInner {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Poker(eval) => write!(f, "{eval}"),
+            Self::FiveOfAKind(_) => write!(f, "{}", Category::FiveOfAKind.text()),
+            Self::ShortDeck(key) => write!(
+                f,
+                "{}",
+                SHORT_DECK_CATEGORIES[(key >> HIGH_KEY_RANKS_WIDTH) as usize].text()
+            ),
+        }
+    }
+}
 
 impl Eval {
-    pub const WORST: Eval = Eval(poker::Eval::WORST);
+    pub const WORST: Eval = Eval(EvalInner::Poker(poker::Eval::WORST));
+
+    /// What kind of hand this is, as decided by the same evaluation that
+    /// ranks it against other hands.
+    pub fn category(self) -> Category {
+        match self.0 {
+            EvalInner::Poker(eval) => {
+                use poker::EvalClass;
+                match eval.class() {
+                    EvalClass::HighCard { .. } => Category::HighCard,
+                    EvalClass::Pair { .. } => Category::OnePair,
+                    EvalClass::TwoPair { .. } => Category::TwoPair,
+                    EvalClass::ThreeOfAKind { .. } => Category::ThreeOfAKind,
+                    EvalClass::Straight { .. } => Category::Straight,
+                    EvalClass::Flush { .. } => Category::Flush,
+                    EvalClass::FullHouse { .. } => Category::FullHouse,
+                    EvalClass::FourOfAKind { .. } => Category::FourOfAKind,
+                    EvalClass::StraightFlush { .. } => Category::StraightFlush,
+                }
+            },
+            EvalInner::FiveOfAKind(_) => Category::FiveOfAKind,
+            EvalInner::ShortDeck(key) => SHORT_DECK_CATEGORIES[(key >> HIGH_KEY_RANKS_WIDTH) as usize],
+        }
+    }
 }
 
 impl Default for Eval {
@@ -27,52 +74,277 @@ impl core::fmt::Debug for Eval {
 }
 
 pub fn holdem_hand(community_cards: holdem::CommunityCards, hand: holdem::Hand) -> Eval {
+    let (cards, len) = holdem_cards(community_cards, hand);
+
+    let cards = cards.map(to_poker_card);
+
+    match poker::evaluate::static_lookup::evaluate(&cards[..len]) {
+        Ok(eval) => Eval(EvalInner::Poker(eval)),
+        Err(err) => {
+            debug_assert!(false, "holdem_hand: evaluate Err: {err}");
+            Eval::default()
+        },
+    }
+}
+
+/// The cards in `hand` followed by the community cards, and how many of them
+/// there are, since there can be fewer than seven before the river.
+fn holdem_cards(
+    community_cards: holdem::CommunityCards,
+    hand: holdem::Hand,
+) -> ([models::Card; 7], usize) {
+    let (board, board_len) = board_cards(community_cards);
+
+    let mut cards = [0; 7];
+    cards[..2].copy_from_slice(&hand);
+    cards[2..2 + board_len].copy_from_slice(&board[..board_len]);
+
+    (cards, 2 + board_len)
+}
+
+/// The community cards, and how many of them there are, since there can be
+/// fewer than five before the river.
+fn board_cards(community_cards: holdem::CommunityCards) -> ([models::Card; 5], usize) {
     use holdem::CommunityCards::*;
 
-    let mut cards = [to_poker_card(0); 7];
+    let mut board = [0; 5];
 
     let len = match community_cards {
         Flop(flop) => {
-            cards[0] = to_poker_card(flop[0]);
-            cards[1] = to_poker_card(flop[1]);
-            cards[2] = to_poker_card(flop[2]);
-            cards[3] = to_poker_card(hand[0]);
-            cards[4] = to_poker_card(hand[1]);
-
-            5
+            board[..3].copy_from_slice(&flop);
+            3
         },
         Turn(flop, turn) => {
-            cards[0] = to_poker_card(flop[0]);
-            cards[1] = to_poker_card(flop[1]);
-            cards[2] = to_poker_card(flop[2]);
-            cards[3] = to_poker_card(turn);
-            cards[4] = to_poker_card(hand[0]);
-            cards[5] = to_poker_card(hand[1]);
-
-            6
+            board[..3].copy_from_slice(&flop);
+            board[3] = turn;
+            4
         },
         River(flop, turn, river) => {
-            cards[0] = to_poker_card(flop[0]);
-            cards[1] = to_poker_card(flop[1]);
-            cards[2] = to_poker_card(flop[2]);
-            cards[3] = to_poker_card(turn);
-            cards[4] = to_poker_card(river);
-            cards[5] = to_poker_card(hand[0]);
-            cards[6] = to_poker_card(hand[1]);
-
-            7
+            board[..3].copy_from_slice(&flop);
+            board[3] = turn;
+            board[4] = river;
+            5
         },
     };
 
-    match poker::evaluate::static_lookup::evaluate(&cards[..len]) {
-        Ok(eval) => Eval(eval),
+    (board, len)
+}
+
+/// The best hand that can be made from exactly two of the cards in `hand`, and
+/// exactly three of the community cards.
+pub fn omaha_hand(community_cards: omaha::CommunityCards, hand: omaha::Hand) -> Eval {
+    omaha_hand_with(DeckComposition::Full, community_cards, hand)
+}
+
+/// `omaha_hand`, but with hands ranked as in short deck hold'em when
+/// `composition` is short.
+pub fn omaha_hand_with(
+    composition: DeckComposition,
+    community_cards: omaha::CommunityCards,
+    hand: omaha::Hand,
+) -> Eval {
+    omaha_best(composition, community_cards, hand).0
+}
+
+/// The five cards that make up the hand that `omaha_hand_with` evaluates, and
+/// what kind of hand they are, for showing to the players.
+pub fn omaha_best_five_with(
+    composition: DeckComposition,
+    community_cards: omaha::CommunityCards,
+    hand: omaha::Hand,
+) -> ([models::Card; 5], Category) {
+    let (eval, five) = omaha_best(composition, community_cards, hand);
+
+    (five, eval.category())
+}
+
+fn omaha_best(
+    composition: DeckComposition,
+    community_cards: omaha::CommunityCards,
+    hand: omaha::Hand,
+) -> (Eval, [models::Card; 5]) {
+    let (board, board_len) = board_cards(community_cards);
+    let board = &board[..board_len];
+
+    let eval_five = five_card_hand_with(composition);
+
+    let mut best = (Eval::WORST, [hand[0], hand[1], board[0], board[1], board[2]]);
+
+    for [hole_1, hole_2] in omaha::hole_card_pairs(hand) {
+        for i in 0..board.len() {
+            for j in (i + 1)..board.len() {
+                for k in (j + 1)..board.len() {
+                    let five = [hole_1, hole_2, board[i], board[j], board[k]];
+
+                    let eval = eval_five(five);
+
+                    if eval > best.0 {
+                        best = (eval, five);
+                    }
+                }
+            }
+        }
+    }
+
+    best
+}
+
+/// The best five card hand that can be made from the seven cards in `hand`.
+pub fn stud_hand(hand: stud::Hand) -> Eval {
+    let mut cards = [to_poker_card(0); 7];
+
+    for (i, card) in hand.into_iter().enumerate() {
+        cards[i] = to_poker_card(card);
+    }
+
+    match poker::evaluate::static_lookup::evaluate(&cards[..]) {
+        Ok(eval) => Eval(EvalInner::Poker(eval)),
         Err(err) => {
-            debug_assert!(false, "holdem_hand: evaluate Err: {err}");
+            debug_assert!(false, "stud_hand: evaluate Err: {err}");
             Eval::default()
         },
     }
 }
 
+/// The five cards that make up the hand that `stud_hand` evaluates, and what
+/// kind of hand they are, for showing to the players.
+pub fn stud_best_five(hand: stud::Hand) -> ([models::Card; 5], Category) {
+    let (eval, five) = best_of_fives(&hand, five_card_hand)
+        .unwrap_or((Eval::WORST, [hand[0], hand[1], hand[2], hand[3], hand[4]]));
+
+    (five, eval.category())
+}
+
+/// The cards dealt to each player in hold'em, or in a game played the same way
+/// apart from them, along with the rules for which of them can be used.
+pub trait HoleCards: Copy + Default + AsRef<[models::Card]> + AsMut<[models::Card]> {
+    const LEN: u8;
+
+    /// The best hand that can be made with `community_cards`, ranked according
+    /// to `composition`.
+    fn eval_with(
+        self,
+        composition: DeckComposition,
+        community_cards: holdem::CommunityCards,
+    ) -> Eval;
+
+    /// The five cards that make up the hand that `eval_with` evaluates, and
+    /// what kind of hand they are, for showing to the players.
+    fn best_five_with(
+        self,
+        composition: DeckComposition,
+        community_cards: holdem::CommunityCards,
+    ) -> ([models::Card; 5], Category);
+}
+
+/// Any five of the hole cards and community cards can be used.
+impl HoleCards for holdem::Hand {
+    const LEN: u8 = holdem::HAND_LEN;
+
+    fn eval_with(
+        self,
+        composition: DeckComposition,
+        community_cards: holdem::CommunityCards,
+    ) -> Eval {
+        holdem_hand_with(composition, community_cards, self)
+    }
+
+    fn best_five_with(
+        self,
+        composition: DeckComposition,
+        community_cards: holdem::CommunityCards,
+    ) -> ([models::Card; 5], Category) {
+        holdem_best_five_with(composition, community_cards, self)
+    }
+}
+
+/// Exactly two of the hole cards must be used.
+impl HoleCards for omaha::Hand {
+    const LEN: u8 = omaha::HAND_LEN;
+
+    fn eval_with(
+        self,
+        composition: DeckComposition,
+        community_cards: holdem::CommunityCards,
+    ) -> Eval {
+        omaha_hand_with(composition, community_cards, self)
+    }
+
+    fn best_five_with(
+        self,
+        composition: DeckComposition,
+        community_cards: holdem::CommunityCards,
+    ) -> ([models::Card; 5], Category) {
+        omaha_best_five_with(composition, community_cards, self)
+    }
+}
+
+/// A ranking of the face up cards in a stud hand, for deciding who acts first.
+/// Only groups of the same rank count, so straights and flushes are ignored,
+/// and aces are high. Higher is better.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Showing(u32);
+
+pub fn showing(up_cards: &[models::Card]) -> Showing {
+    let Grouped { mut ranks_key, most, second_most } = grouped(&rank_counts(up_cards, true));
+
+    // Pad out to the same length, so fewer up cards are not penalized in
+    // comparisons, when all players always have the same amount anyway.
+    for _ in up_cards.len()..usize::from(stud::MAX_UP_CARDS) {
+        ranks_key <<= LOW_KEY_RANK_BITS;
+    }
+
+    // Put the category, by the two largest groups, above the ranks, which
+    // take 4 bits per card, and at most 4 up cards fits in 16 bits.
+    Showing(u32::from(most) << 20 | u32::from(second_most) << 16 | ranks_key)
+}
+
+#[test]
+fn showing_ranks_groups_above_high_cards() {
+    // Ranks are 0 for ace up to 12 for king, and suits advance by 13.
+    const CLUBS: models::Card = 0;
+    const DIAMONDS: models::Card = 13;
+
+    // A pair of twos beats ace king.
+    assert!(showing(&[CLUBS + 1, DIAMONDS + 1]) > showing(&[CLUBS, CLUBS + 12]));
+    // Ace high beats king high.
+    assert!(showing(&[CLUBS]) > showing(&[CLUBS + 12]));
+    // Three of a kind beats two pair.
+    assert!(
+        showing(&[CLUBS + 2, DIAMONDS + 2, CLUBS + 2 + 26, CLUBS + 5])
+        > showing(&[CLUBS + 3, DIAMONDS + 3, CLUBS + 4, DIAMONDS + 4])
+    );
+    // Suits do not matter.
+    assert_eq!(showing(&[CLUBS + 7]), showing(&[DIAMONDS + 7]));
+}
+
+#[test]
+fn omaha_hand_uses_exactly_two_hole_cards() {
+    // Ranks are 0 for ace up to 12 for king, and suits advance by 13.
+    const CLUBS: models::Card = 0;
+    const DIAMONDS: models::Card = 13;
+    const HEARTS: models::Card = 26;
+
+    // Four clubs in the hand, and one on the board, is not a flush. The best
+    // that can be done is a pair of twos.
+    let board = holdem::CommunityCards::from(
+        [CLUBS + 12, DIAMONDS + 1, HEARTS + 9, DIAMONDS + 10, HEARTS + 2]
+    );
+    assert_eq!(
+        omaha_hand(board, [CLUBS + 1, CLUBS + 3, CLUBS + 5, CLUBS + 7]),
+        five_card_hand([CLUBS + 1, CLUBS + 7, DIAMONDS + 1, CLUBS + 12, DIAMONDS + 10]),
+    );
+
+    // Four aces on the board only gives three of them to the hand.
+    let board = holdem::CommunityCards::from(
+        [CLUBS, DIAMONDS, HEARTS, HEARTS + 13, CLUBS + 4]
+    );
+    assert!(
+        omaha_hand(board, [DIAMONDS + 5, HEARTS + 7, CLUBS + 9, DIAMONDS + 11])
+        < holdem_hand(board, [DIAMONDS + 5, HEARTS + 7])
+    );
+}
+
 pub fn five_card_hand(hand: [models::Card; 5]) -> Eval {
     let mut cards = [to_poker_card(0); 5];
 
@@ -83,7 +355,7 @@ pub fn five_card_hand(hand: [models::Card; 5]) -> Eval {
     cards[4] = to_poker_card(hand[4]);
 
     match poker::evaluate::static_lookup::evaluate(&cards[..]) {
-        Ok(eval) => Eval(eval),
+        Ok(eval) => Eval(EvalInner::Poker(eval)),
         Err(err) => {
             debug_assert!(false, "five_card_hand: evaluate Err: {err}");
             Eval::default()
@@ -91,6 +363,620 @@ pub fn five_card_hand(hand: [models::Card; 5]) -> Eval {
     }
 }
 
+/// The kind of hand, without any of the information about ranks that would be
+/// needed to break ties between hands of the same kind.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Category {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    Straight,
+    Flush,
+    FullHouse,
+    FourOfAKind,
+    StraightFlush,
+    /// Only possible with wild cards.
+    FiveOfAKind,
+}
+
+impl Category {
+    pub fn text(self) -> &'static str {
+        use Category::*;
+        match self {
+            HighCard => "High card",
+            OnePair => "One pair",
+            TwoPair => "Two pair",
+            ThreeOfAKind => "Three of a kind",
+            Straight => "Straight",
+            Flush => "Flush",
+            FullHouse => "Full house",
+            FourOfAKind => "Four of a kind",
+            StraightFlush => "Straight flush",
+            FiveOfAKind => "Five of a kind",
+        }
+    }
+}
+
+pub fn five_card_category(hand: [models::Card; 5]) -> Category {
+    five_card_hand(hand).category()
+}
+
+#[test]
+fn five_card_category_works_on_these_examples() {
+    use Category::*;
+    // Ranks are 0 for ace up to 12 for king, and suits advance by 13.
+    const CLUBS: models::Card = 0;
+    const DIAMONDS: models::Card = 13;
+    const HEARTS: models::Card = 26;
+
+    assert_eq!(five_card_category([0, 2, 4, 6, 21]), HighCard);
+    assert_eq!(five_card_category([0, 13, 4, 6, 21]), OnePair);
+    assert_eq!(five_card_category([0, 13, 4, 17, 21]), TwoPair);
+    assert_eq!(five_card_category([0, 13, 26, 17, 21]), ThreeOfAKind);
+    assert_eq!(five_card_category([CLUBS, DIAMONDS + 1, HEARTS + 2, CLUBS + 3, CLUBS + 4]), Straight);
+    assert_eq!(five_card_category([CLUBS, DIAMONDS + 9, HEARTS + 10, CLUBS + 11, CLUBS + 12]), Straight);
+    assert_eq!(five_card_category([CLUBS, CLUBS + 2, CLUBS + 4, CLUBS + 6, CLUBS + 8]), Flush);
+    assert_eq!(five_card_category([0, 13, 26, 4, 17]), FullHouse);
+    assert_eq!(five_card_category([0, 13, 26, 39, 17]), FourOfAKind);
+    assert_eq!(five_card_category([CLUBS + 9, CLUBS + 10, CLUBS + 11, CLUBS + 12, CLUBS]), StraightFlush);
+}
+
+/// Splits `hand` into the cards that are not wild, in the first returned
+/// element, and the amount of cards that are.
+fn natural_cards(hand: [models::Card; 5], wilds: WildCards) -> ([models::Card; 5], usize) {
+    let mut naturals = [0; 5];
+    let mut natural_len = 0;
+    for card in hand {
+        if !wilds.is_wild(card) {
+            naturals[natural_len] = card;
+            natural_len += 1;
+        }
+    }
+
+    (naturals, natural_len)
+}
+
+/// The rank, with aces high, of the five of a kind that the wild cards can make
+/// with `naturals`, if they all share a rank.
+fn five_of_a_kind_rank(naturals: &[models::Card]) -> Option<models::Rank> {
+    use models::{get_rank, ranks};
+
+    let rank = naturals.first().map_or(ranks::ACE, |&card| get_rank(card));
+
+    if naturals.iter().all(|&card| get_rank(card) == rank) {
+        Some(if rank == ranks::ACE { ranks::HIGH_ACE } else { rank })
+    } else {
+        None
+    }
+}
+
+/// The best hand that can be made by replacing each of the cards in `hand`
+/// past `natural_len` with a card not already in `hand`.
+fn best_substitution(mut hand: [models::Card; 5], natural_len: usize) -> ([models::Card; 5], Eval) {
+    fn fill(
+        hand: &mut [models::Card; 5],
+        filled: usize,
+        min_card: models::Card,
+        used: models::CardBitset,
+        best: &mut ([models::Card; 5], Eval),
+    ) {
+        if filled >= hand.len() {
+            let eval = five_card_hand(*hand);
+            if eval > best.1 {
+                *best = (*hand, eval);
+            }
+            return
+        }
+
+        // Substitutes are tried in ascending order, so each set of them is only
+        // evaluated once.
+        for card in min_card..models::DECK_SIZE {
+            if used.contains(card) {
+                continue
+            }
+
+            hand[filled] = card;
+            fill(hand, filled + 1, card + 1, used, best);
+        }
+    }
+
+    let mut used = models::CardBitset::default();
+    for &card in &hand[..natural_len] {
+        used.set(card);
+    }
+
+    let mut best = (hand, Eval::WORST);
+    fill(&mut hand, natural_len, 0, used, &mut best);
+
+    best
+}
+
+/// `hand` with each card that is wild under `wilds` replaced by the card it
+/// stands in for in `five_card_hand_wild`. Returns `None` if the hand is five
+/// of a kind, since no hand of distinct cards can stand for that.
+pub fn substitute_wilds(hand: [models::Card; 5], wilds: WildCards) -> Option<[models::Card; 5]> {
+    let (naturals, natural_len) = natural_cards(hand, wilds);
+
+    if natural_len == hand.len() {
+        return Some(hand)
+    }
+
+    if five_of_a_kind_rank(&naturals[..natural_len]).is_some() {
+        return None
+    }
+
+    Some(best_substitution(naturals, natural_len).0)
+}
+
+/// The best hand that can be made from `hand`, where each card that is wild
+/// under `wilds` stands in for whichever card makes the hand best. Wild cards
+/// can also make five of a kind, which beats a straight flush.
+pub fn five_card_hand_wild(hand: [models::Card; 5], wilds: WildCards) -> Eval {
+    let (naturals, natural_len) = natural_cards(hand, wilds);
+
+    if natural_len == hand.len() {
+        return five_card_hand(hand)
+    }
+
+    if let Some(rank) = five_of_a_kind_rank(&naturals[..natural_len]) {
+        return Eval(EvalInner::FiveOfAKind(rank))
+    }
+
+    best_substitution(naturals, natural_len).1
+}
+
+/// The category of `five_card_hand_wild(hand, wilds)`, worked out directly,
+/// which is much faster than trying the substitutions.
+pub fn five_card_category_wild(hand: [models::Card; 5], wilds: WildCards) -> Category {
+    use models::{get_rank, get_suit, ranks, RANK_COUNT};
+
+    let (naturals, natural_len) = natural_cards(hand, wilds);
+    let naturals = &naturals[..natural_len];
+    let wild_count = (hand.len() - natural_len) as u8;
+
+    if wild_count == 0 {
+        return five_card_category(hand)
+    }
+
+    if five_of_a_kind_rank(naturals).is_some() {
+        return Category::FiveOfAKind
+    }
+
+    let mut rank_counts = [0u8; RANK_COUNT as usize];
+    for &card in naturals {
+        rank_counts[usize::from(get_rank(card))] += 1;
+    }
+
+    let most = rank_counts.iter().copied().max().unwrap_or_default();
+    let pair_count = rank_counts.iter().filter(|&&count| count == 2).count();
+
+    let is_suited = naturals.iter().all(|&card| get_suit(card) == get_suit(naturals[0]));
+
+    // The wild cards can fill in any gaps, as long as all the ranks fit in
+    // the span of a single straight, with aces either high or low.
+    let can_straight = most == 1 && {
+        let mut aces_low: u16 = 0;
+        for (rank, count) in rank_counts.iter().enumerate() {
+            if *count > 0 {
+                aces_low |= 1 << rank;
+            }
+        }
+        let aces_high = if aces_low & 1 != 0 {
+            (aces_low & !1) | 1 << ranks::HIGH_ACE
+        } else {
+            aces_low
+        };
+
+        (0..=(ranks::HIGH_ACE - 4)).any(|start| {
+            let span = 0b11111 << start;
+            aces_low & !span == 0 || aces_high & !span == 0
+        })
+    };
+
+    use Category::*;
+    match (can_straight, is_suited) {
+        (true, true) => StraightFlush,
+        _ if most + wild_count >= 4 => FourOfAKind,
+        // With more wild cards than one, the naturals would make four of a
+        // kind instead.
+        _ if pair_count == 2 => FullHouse,
+        (_, true) => Flush,
+        (true, _) => Straight,
+        _ if most + wild_count >= 3 => ThreeOfAKind,
+        _ => OnePair,
+    }
+}
+
+#[test]
+fn five_card_category_wild_matches_the_best_substitution() {
+    // A simple linear congruential generator is enough to get a spread of
+    // hands, without needing a dependency on `xs` just for this test.
+    let mut state: u32 = 0x16;
+    let mut next_card = |wilds: WildCards| {
+        state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+        let count = if wilds == WildCards::Jokers { models::MAX_DECK_SIZE } else { models::DECK_SIZE };
+        ((state >> 16) % u32::from(count)) as models::Card
+    };
+
+    for wilds in [WildCards::Jokers, WildCards::Deuces] {
+        for _ in 0..256 {
+            let mut used = models::CardBitset::default();
+            let mut hand = [0; 5];
+            for card in &mut hand {
+                *card = loop {
+                    let card = next_card(wilds);
+                    if !used.contains(card) {
+                        used.set(card);
+                        break card
+                    }
+                };
+            }
+
+            let expected = match substitute_wilds(hand, wilds) {
+                Some(substituted) => five_card_category(substituted),
+                None => Category::FiveOfAKind,
+            };
+
+            assert_eq!(five_card_category_wild(hand, wilds), expected, "{hand:?}");
+        }
+    }
+}
+
+#[test]
+fn wild_cards_make_these_hands() {
+    use Category::*;
+    // Ranks are 0 for ace up to 12 for king, and suits advance by 13.
+    const CLUBS: models::Card = 0;
+    const DIAMONDS: models::Card = 13;
+    const HEARTS: models::Card = 26;
+    const JOKER: models::Card = models::JOKER;
+
+    let five_aces = five_card_hand_wild([CLUBS, DIAMONDS, HEARTS, 39, JOKER], WildCards::Jokers);
+    let royal_flush = five_card_hand_wild([CLUBS, CLUBS + 12, CLUBS + 11, CLUBS + 10, JOKER], WildCards::Jokers);
+    let five_threes = five_card_hand_wild([CLUBS + 1, DIAMONDS + 2, HEARTS + 2, CLUBS + 2, 40], WildCards::Deuces);
+
+    assert_eq!(five_aces.to_string(), "Five of a kind");
+    assert!(five_aces > five_threes);
+    assert!(five_threes > royal_flush);
+    assert!(royal_flush > five_card_hand([CLUBS + 9, CLUBS + 12, CLUBS + 11, CLUBS + 10, CLUBS + 8]));
+
+    assert_eq!(five_card_category_wild([CLUBS + 4, DIAMONDS + 4, HEARTS + 7, CLUBS + 7, JOKER], WildCards::Jokers), FullHouse);
+    assert_eq!(five_card_category_wild([CLUBS + 4, DIAMONDS + 4, HEARTS + 7, CLUBS + 7, JOKER], WildCards::None), TwoPair);
+    assert_eq!(five_card_category_wild([CLUBS + 3, DIAMONDS + 5, HEARTS + 6, CLUBS + 1, 40], WildCards::Deuces), Straight);
+}
+
+/// A ranking of a hand in ace-to-five lowball, where aces are low, and
+/// straights and flushes do not count against the hand. Like `Eval`, better
+/// hands compare greater, so the best hand is A-2-3-4-5, of any suits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct AceToFiveEval(core::cmp::Reverse<LowKey>);
+
+impl AceToFiveEval {
+    pub const WORST: AceToFiveEval = AceToFiveEval(core::cmp::Reverse(LowKey::MAX));
+
+    /// Whether this hand qualifies for the low half of the pot in hi/lo split
+    /// games, which needs five different ranks, all eight or lower.
+    pub fn is_eight_or_better(self) -> bool {
+        const EIGHT: LowKey = 7;
+
+        let key = self.0.0;
+
+        key >> LOW_KEY_RANKS_WIDTH == Category::HighCard as LowKey
+        && (key >> (LOW_KEY_RANK_BITS * 4)) & ((1 << LOW_KEY_RANK_BITS) - 1) <= EIGHT
+    }
+}
+
+impl Default for AceToFiveEval {
+    fn default() -> Self {
+        AceToFiveEval::WORST
+    }
+}
+
+impl core::fmt::Display for AceToFiveEval {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write_low_key(f, self.0.0)
+    }
+}
+
+/// A ranking of a hand in deuce-to-seven lowball, where aces are high, and
+/// straights and flushes count against the hand. Like `Eval`, better hands
+/// compare greater, so the best hand is 7-5-4-3-2, not all of one suit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DeuceToSevenEval(core::cmp::Reverse<LowKey>);
+
+impl DeuceToSevenEval {
+    pub const WORST: DeuceToSevenEval = DeuceToSevenEval(core::cmp::Reverse(LowKey::MAX));
+}
+
+impl Default for DeuceToSevenEval {
+    fn default() -> Self {
+        DeuceToSevenEval::WORST
+    }
+}
+
+impl core::fmt::Display for DeuceToSevenEval {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write_low_key(f, self.0.0)
+    }
+}
+
+/// Higher keys are worse low hands. The `Category` is in the top bits, and the
+/// ranks are in the bottom 20 bits, 4 bits each, ordered by how much they
+/// matter when breaking ties.
+type LowKey = u32;
+
+const LOW_KEY_RANK_BITS: u32 = 4;
+const LOW_KEY_RANKS_WIDTH: u32 = LOW_KEY_RANK_BITS * 5;
+
+fn low_key(hand: [models::Card; 5], aces_high: bool, count_straights_and_flushes: bool) -> LowKey {
+    use models::get_suit;
+
+    let Grouped { ranks_key, most, second_most } = grouped(&rank_counts(&hand, aces_high));
+
+    let is_flush = count_straights_and_flushes
+        && hand.iter().all(|&card| get_suit(card) == get_suit(hand[0]));
+
+    // Only aces high straights are checked for, since aces low straights
+    // never count.
+    let is_straight = count_straights_and_flushes
+        && most == 1
+        && {
+            let highest = ranks_key >> (LOW_KEY_RANK_BITS * 4);
+            let lowest = ranks_key & ((1 << LOW_KEY_RANK_BITS) - 1);
+            highest - lowest == 4
+        };
+
+    use Category::*;
+    let category = match (is_straight, is_flush, most, second_most) {
+        (true, true, _, _) => StraightFlush,
+        (_, _, 4, _) => FourOfAKind,
+        (_, _, 3, 2) => FullHouse,
+        (_, true, _, _) => Flush,
+        (true, _, _, _) => Straight,
+        (_, _, 3, _) => ThreeOfAKind,
+        (_, _, 2, 2) => TwoPair,
+        (_, _, 2, _) => OnePair,
+        _ => HighCard,
+    };
+
+    (category as LowKey) << LOW_KEY_RANKS_WIDTH | ranks_key
+}
+
+struct Grouped {
+    /// The ranks, 4 bits each, with larger groups first, then higher ranks
+    /// within groups of the same size.
+    ranks_key: u32,
+    most: u8,
+    second_most: u8,
+}
+
+/// How many of `cards` there are of each rank. Index 0 is unused when aces are
+/// high, and index 13 is unused when aces are low, so both can use the same
+/// array.
+type RankCounts = [u8; models::RANK_COUNT as usize + 1];
+
+fn rank_counts(cards: &[models::Card], aces_high: bool) -> RankCounts {
+    use models::{get_rank, ranks};
+
+    let mut rank_counts = RankCounts::default();
+    for &card in cards {
+        let rank = get_rank(card);
+        let rank = if aces_high && rank == ranks::ACE { ranks::HIGH_ACE } else { rank };
+        rank_counts[usize::from(rank)] += 1;
+    }
+
+    rank_counts
+}
+
+fn grouped(rank_counts: &RankCounts) -> Grouped {
+    let mut ranks_key = 0;
+    let mut most = 0;
+    let mut second_most = 0;
+    for count in (1..=4).rev() {
+        for rank in (0..rank_counts.len()).rev() {
+            if rank_counts[rank] == count {
+                for _ in 0..count {
+                    ranks_key = ranks_key << LOW_KEY_RANK_BITS | rank as u32;
+                }
+
+                if count > most {
+                    second_most = most;
+                    most = count;
+                } else if count > second_most {
+                    second_most = count;
+                }
+            }
+        }
+    }
+
+    Grouped { ranks_key, most, second_most }
+}
+
+fn write_low_key(f: &mut core::fmt::Formatter<'_>, key: LowKey) -> core::fmt::Result {
+    if key == LowKey::MAX {
+        return write!(f, "no low")
+    }
+
+    const RANK_CHARS: &[u8; 14] = b"a23456789tjqka";
+
+    for i in (0..5).rev() {
+        let rank = (key >> (LOW_KEY_RANK_BITS * i)) & ((1 << LOW_KEY_RANK_BITS) - 1);
+        write!(f, "{}", char::from(RANK_CHARS[rank as usize]))?;
+        if i > 0 {
+            write!(f, "-")?;
+        }
+    }
+
+    Ok(())
+}
+
+pub fn ace_to_five_hand(hand: [models::Card; 5]) -> AceToFiveEval {
+    AceToFiveEval(core::cmp::Reverse(low_key(hand, false, false)))
+}
+
+pub fn deuce_to_seven_hand(hand: [models::Card; 5]) -> DeuceToSevenEval {
+    DeuceToSevenEval(core::cmp::Reverse(low_key(hand, true, true)))
+}
+
+/// The best ace-to-five low that can be made from any five of `cards`, as in
+/// razz. Fewer than five cards give `AceToFiveEval::WORST`.
+pub fn best_ace_to_five_hand(cards: &[models::Card]) -> AceToFiveEval {
+    best_of_fives(cards, ace_to_five_hand)
+        .map_or(AceToFiveEval::WORST, |(eval, _)| eval)
+}
+
+/// The best hand that can be made from any five of `cards`, along with the
+/// five cards that make it, or `None` if there are fewer than five cards.
+fn best_of_fives<E: Ord>(
+    cards: &[models::Card],
+    eval_five: impl Fn([models::Card; 5]) -> E,
+) -> Option<(E, [models::Card; 5])> {
+    let mut best: Option<(E, [models::Card; 5])> = None;
+
+    let len = cards.len();
+    for a in 0..len {
+        for b in (a + 1)..len {
+            for c in (b + 1)..len {
+                for d in (c + 1)..len {
+                    for e in (d + 1)..len {
+                        let five = [cards[a], cards[b], cards[c], cards[d], cards[e]];
+
+                        let eval = eval_five(five);
+
+                        match &best {
+                            Some((best_eval, _)) if *best_eval >= eval => {},
+                            _ => {
+                                best = Some((eval, five));
+                            },
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    best
+}
+
+/// Higher keys are better short deck hands. The index into
+/// `SHORT_DECK_CATEGORIES` is in the top bits, and the ranks, aces high, are in
+/// the bottom 20 bits, laid out the same way as in `LowKey`.
+type ShortDeckKey = u32;
+
+const HIGH_KEY_RANKS_WIDTH: u32 = LOW_KEY_RANKS_WIDTH;
+
+/// With the twos through fives removed, flushes are rarer than full houses,
+/// so they rank above them.
+const SHORT_DECK_CATEGORIES: [Category; 9] = {
+    use Category::*;
+    [
+        HighCard,
+        OnePair,
+        TwoPair,
+        ThreeOfAKind,
+        Straight,
+        FullHouse,
+        Flush,
+        FourOfAKind,
+        StraightFlush,
+    ]
+};
+
+fn short_deck_key(hand: [models::Card; 5]) -> ShortDeckKey {
+    use models::get_suit;
+
+    let Grouped { mut ranks_key, most, second_most } = grouped(&rank_counts(&hand, true));
+
+    let is_flush = hand.iter().all(|&card| get_suit(card) == get_suit(hand[0]));
+
+    let is_straight = most == 1 && {
+        let highest = ranks_key >> (LOW_KEY_RANK_BITS * 4);
+        let lowest = ranks_key & ((1 << LOW_KEY_RANK_BITS) - 1);
+        // The ace plays low, under the six, in A-6-7-8-9.
+        const LOW_STRAIGHT: u32 = 0xD_8765;
+        if ranks_key == LOW_STRAIGHT {
+            ranks_key = 0x8_7654;
+            true
+        } else {
+            highest - lowest == 4
+        }
+    };
+
+    use Category::*;
+    let category = match (is_straight, is_flush, most, second_most) {
+        (true, true, _, _) => StraightFlush,
+        (_, _, 4, _) => FourOfAKind,
+        (_, true, _, _) => Flush,
+        (_, _, 3, 2) => FullHouse,
+        (true, _, _, _) => Straight,
+        (_, _, 3, _) => ThreeOfAKind,
+        (_, _, 2, 2) => TwoPair,
+        (_, _, 2, _) => OnePair,
+        _ => HighCard,
+    };
+
+    let category_index = SHORT_DECK_CATEGORIES
+        .iter()
+        .position(|&c| c == category)
+        .unwrap_or_default();
+
+    (category_index as ShortDeckKey) << HIGH_KEY_RANKS_WIDTH | ranks_key
+}
+
+/// The best hand that can be made from any five of the cards, ranked as in
+/// short deck hold'em.
+pub fn short_deck_holdem_hand(
+    community_cards: holdem::CommunityCards,
+    hand: holdem::Hand,
+) -> Eval {
+    let (cards, len) = holdem_cards(community_cards, hand);
+
+    best_of_fives(&cards[..len], short_deck_five)
+        .map_or(Eval::WORST, |(eval, _)| eval)
+}
+
+fn short_deck_five(five: [models::Card; 5]) -> Eval {
+    Eval(EvalInner::ShortDeck(short_deck_key(five)))
+}
+
+/// `holdem_hand` or `short_deck_holdem_hand`, depending on `composition`.
+pub fn holdem_hand_with(
+    composition: DeckComposition,
+    community_cards: holdem::CommunityCards,
+    hand: holdem::Hand,
+) -> Eval {
+    match composition {
+        DeckComposition::Full => holdem_hand(community_cards, hand),
+        DeckComposition::Short => short_deck_holdem_hand(community_cards, hand),
+    }
+}
+
+/// The five cards that make up the hand that `holdem_hand_with` evaluates, and
+/// what kind of hand they are, for showing to the players.
+pub fn holdem_best_five_with(
+    composition: DeckComposition,
+    community_cards: holdem::CommunityCards,
+    hand: holdem::Hand,
+) -> ([models::Card; 5], Category) {
+    let (cards, len) = holdem_cards(community_cards, hand);
+
+    // There are always at least the two hole cards and the flop.
+    let (eval, five) = best_of_fives(&cards[..len], five_card_hand_with(composition))
+        .unwrap_or((Eval::WORST, [cards[0], cards[1], cards[2], cards[3], cards[4]]));
+
+    (five, eval.category())
+}
+
+/// `five_card_hand`, or the short deck version of it, depending on
+/// `composition`.
+fn five_card_hand_with(composition: DeckComposition) -> fn([models::Card; 5]) -> Eval {
+    match composition {
+        DeckComposition::Full => five_card_hand,
+        DeckComposition::Short => short_deck_five,
+    }
+}
+
 const fn to_poker_card(card: models::Card) -> poker::Card {
     let rank = models::get_rank(card);
 
@@ -176,4 +1062,193 @@ const ALL_CARDS: [poker::Card; 52] = [
     poker::Card::new(poker::Rank::Ace, poker::Suit::Diamonds),
     poker::Card::new(poker::Rank::Ace, poker::Suit::Hearts),
     poker::Card::new(poker::Rank::Ace, poker::Suit::Spades),
-];
\ No newline at end of file
+];
+
+#[cfg(test)]
+mod low_tests {
+    use super::*;
+
+    // Ranks are 0 for ace up to 12 for king, and suits advance by 13.
+    const CLUBS: models::Card = 0;
+    const DIAMONDS: models::Card = 13;
+    const HEARTS: models::Card = 26;
+    const SPADES: models::Card = 39;
+
+    // Ranks, named by what the card shows.
+    const A: models::Card = 0;
+    const TWO: models::Card = 1;
+    const THREE: models::Card = 2;
+    const FOUR: models::Card = 3;
+    const FIVE: models::Card = 4;
+    const SIX: models::Card = 5;
+    const SEVEN: models::Card = 6;
+    const EIGHT: models::Card = 7;
+    const NINE: models::Card = 8;
+    const JACK: models::Card = 10;
+    const QUEEN: models::Card = 11;
+    const KING: models::Card = 12;
+
+    #[test]
+    fn ace_to_five_hand_orders_these_known_hands() {
+        // Each hand is better than the next one.
+        let hands = [
+            // The wheel, which is the best, even when suited.
+            [CLUBS + FIVE, CLUBS + FOUR, CLUBS + THREE, CLUBS + TWO, CLUBS + A],
+            [CLUBS + SIX, DIAMONDS + FOUR, CLUBS + THREE, HEARTS + TWO, SPADES + A],
+            [CLUBS + SIX, DIAMONDS + FIVE, CLUBS + FOUR, HEARTS + THREE, SPADES + TWO],
+            [CLUBS + SEVEN, DIAMONDS + FIVE, CLUBS + FOUR, HEARTS + THREE, SPADES + TWO],
+            [CLUBS + EIGHT, DIAMONDS + SIX, CLUBS + FOUR, HEARTS + THREE, SPADES + TWO],
+            [CLUBS + KING, DIAMONDS + QUEEN, CLUBS + JACK, HEARTS + NINE, SPADES + EIGHT],
+            // Any pair is worse than no pair.
+            [CLUBS + A, DIAMONDS + A, CLUBS + TWO, HEARTS + THREE, SPADES + FOUR],
+            [CLUBS + TWO, DIAMONDS + TWO, CLUBS + A, HEARTS + THREE, SPADES + FOUR],
+            [CLUBS + TWO, DIAMONDS + TWO, CLUBS + THREE, HEARTS + THREE, SPADES + A],
+            [CLUBS + TWO, DIAMONDS + TWO, HEARTS + TWO, HEARTS + THREE, SPADES + A],
+        ];
+
+        for pair in hands.windows(2) {
+            assert!(
+                ace_to_five_hand(pair[0]) > ace_to_five_hand(pair[1]),
+                "{} <= {}",
+                ace_to_five_hand(pair[0]),
+                ace_to_five_hand(pair[1]),
+            );
+        }
+    }
+
+    #[test]
+    fn deuce_to_seven_hand_orders_these_known_hands() {
+        // Each hand is better than the next one.
+        let hands = [
+            [CLUBS + SEVEN, DIAMONDS + FIVE, CLUBS + FOUR, HEARTS + THREE, SPADES + TWO],
+            [CLUBS + SEVEN, DIAMONDS + SIX, CLUBS + FOUR, HEARTS + THREE, SPADES + TWO],
+            [CLUBS + EIGHT, DIAMONDS + FIVE, CLUBS + FOUR, HEARTS + THREE, SPADES + TWO],
+            [CLUBS + KING, DIAMONDS + QUEEN, CLUBS + JACK, HEARTS + NINE, SPADES + EIGHT],
+            // Aces are high, and the wheel is not a straight.
+            [CLUBS + A, DIAMONDS + FIVE, CLUBS + FOUR, HEARTS + THREE, SPADES + TWO],
+            [CLUBS + TWO, DIAMONDS + TWO, CLUBS + THREE, HEARTS + FOUR, SPADES + FIVE],
+            // Straights and flushes count against the hand.
+            [CLUBS + SIX, DIAMONDS + FIVE, CLUBS + FOUR, HEARTS + THREE, SPADES + TWO],
+            [CLUBS + SEVEN, CLUBS + FIVE, CLUBS + FOUR, CLUBS + THREE, CLUBS + TWO],
+        ];
+
+        for pair in hands.windows(2) {
+            assert!(
+                deuce_to_seven_hand(pair[0]) > deuce_to_seven_hand(pair[1]),
+                "{} <= {}",
+                deuce_to_seven_hand(pair[0]),
+                deuce_to_seven_hand(pair[1]),
+            );
+        }
+    }
+
+    #[test]
+    fn is_eight_or_better_works_on_these_examples() {
+        let qualifies = |hand| ace_to_five_hand(hand).is_eight_or_better();
+
+        assert!(qualifies([CLUBS + EIGHT, DIAMONDS + SEVEN, CLUBS + SIX, HEARTS + FOUR, SPADES + THREE]));
+        assert!(qualifies([CLUBS + FIVE, CLUBS + FOUR, CLUBS + THREE, CLUBS + TWO, CLUBS + A]));
+        assert!(!qualifies([CLUBS + NINE, DIAMONDS + FOUR, CLUBS + THREE, HEARTS + TWO, SPADES + A]));
+        assert!(!qualifies([CLUBS + TWO, DIAMONDS + TWO, CLUBS + THREE, HEARTS + FOUR, SPADES + A]));
+        assert!(!AceToFiveEval::WORST.is_eight_or_better());
+    }
+
+    #[test]
+    fn best_ace_to_five_hand_finds_the_low_among_seven_cards() {
+        let cards = [
+            CLUBS + KING, DIAMONDS + FIVE, CLUBS + FOUR, HEARTS + FOUR,
+            SPADES + TWO, CLUBS + A, DIAMONDS + EIGHT,
+        ];
+
+        assert_eq!(
+            best_ace_to_five_hand(&cards),
+            ace_to_five_hand([DIAMONDS + EIGHT, DIAMONDS + FIVE, CLUBS + FOUR, SPADES + TWO, CLUBS + A]),
+        );
+        assert_eq!(best_ace_to_five_hand(&cards[..4]), AceToFiveEval::WORST);
+        assert_eq!(
+            best_ace_to_five_hand(&cards).to_string(),
+            "8-5-4-2-a",
+        );
+    }
+}
+
+#[cfg(test)]
+mod short_deck_tests {
+    use super::*;
+    use holdem::CommunityCards::River;
+
+    // Ranks are 0 for ace up to 12 for king, and suits advance by 13.
+    const CLUBS: models::Card = 0;
+    const DIAMONDS: models::Card = 13;
+    const HEARTS: models::Card = 26;
+    const SPADES: models::Card = 39;
+
+    const A: models::Card = 0;
+    const SIX: models::Card = 5;
+    const SEVEN: models::Card = 6;
+    const EIGHT: models::Card = 7;
+    const NINE: models::Card = 8;
+    const TEN: models::Card = 9;
+    const JACK: models::Card = 10;
+    const KING: models::Card = 12;
+
+    #[test]
+    fn short_deck_flushes_beat_full_houses() {
+        let board = River([CLUBS + SIX, CLUBS + NINE, HEARTS + NINE], CLUBS + KING, SPADES + SEVEN);
+
+        let flush = short_deck_holdem_hand(board, [CLUBS + JACK, CLUBS + EIGHT]);
+        let full_house = short_deck_holdem_hand(board, [DIAMONDS + NINE, DIAMONDS + KING]);
+
+        assert!(flush > full_house, "{flush} <= {full_house}");
+        assert_eq!(flush.to_string(), "Flush");
+        assert_eq!(full_house.to_string(), "Full house");
+    }
+
+    #[test]
+    fn short_deck_ace_can_play_under_the_six() {
+        let board = River([CLUBS + SIX, DIAMONDS + SEVEN, HEARTS + EIGHT], SPADES + KING, SPADES + JACK);
+
+        let low_straight = short_deck_holdem_hand(board, [CLUBS + A, HEARTS + NINE]);
+        let ten_high_straight = short_deck_holdem_hand(board, [CLUBS + TEN, HEARTS + NINE]);
+        let aces = short_deck_holdem_hand(board, [CLUBS + A, HEARTS + A]);
+
+        assert_eq!(low_straight.to_string(), "Straight");
+        assert!(ten_high_straight > low_straight);
+        assert!(low_straight > aces);
+        assert!(aces > Eval::WORST);
+    }
+
+    #[test]
+    fn holdem_best_five_with_finds_the_cards_that_make_the_hand() {
+        let board = River([CLUBS + SIX, CLUBS + NINE, HEARTS + NINE], CLUBS + KING, SPADES + SEVEN);
+
+        let (mut five, category) = holdem_best_five_with(
+            DeckComposition::Short,
+            board,
+            [CLUBS + JACK, CLUBS + EIGHT],
+        );
+        five.sort();
+        assert_eq!(five, [CLUBS + SIX, CLUBS + EIGHT, CLUBS + NINE, CLUBS + JACK, CLUBS + KING]);
+        assert_eq!(category, Category::Flush);
+
+        let (mut five, category) = holdem_best_five_with(
+            DeckComposition::Full,
+            board,
+            [HEARTS + A, DIAMONDS + SEVEN],
+        );
+        five.sort();
+        assert_eq!(five, [CLUBS + NINE, DIAMONDS + SEVEN, HEARTS + A, HEARTS + NINE, SPADES + SEVEN]);
+        assert_eq!(category, Category::TwoPair);
+    }
+
+    #[test]
+    fn stud_best_five_finds_the_cards_that_make_the_hand() {
+        let hand = [CLUBS + SIX, CLUBS + NINE, HEARTS + NINE, CLUBS + KING, SPADES + SEVEN, CLUBS + JACK, CLUBS + EIGHT];
+
+        let (mut five, category) = stud_best_five(hand);
+        five.sort();
+        assert_eq!(five, [CLUBS + SIX, CLUBS + EIGHT, CLUBS + NINE, CLUBS + JACK, CLUBS + KING]);
+        assert_eq!(category, Category::Flush);
+        assert_eq!(stud_hand(hand).to_string(), "Flush");
+    }
+}
@@ -1,14 +1,15 @@
 use gfx::{card, pre_nul_len, Commands, SPACING_W, SPACING_H};
-use models::{Card, CardBitset, ALL_CARDS, INITIAL_ANTE_AMOUNT, MIN_MONEY_UNIT, Deck, Money, MoneyInner, MoneyMove, NonZeroMoney, NonZeroMoneyInner, Rank, gen_deck, get_rank, ranks};
+use models::{Card, CardBitset, ALL_CARDS, INITIAL_ANTE_AMOUNT, MIN_MONEY_UNIT, Deck, Money, MoneyInner, MoneyMove, NonZeroMoney, NonZeroMoneyInner, Rank, gen_deck, gen_deck_without, get_rank, ranks};
 use platform_types::{Button, Dir, Input, PaletteIndex, Speaker, SFX, command, unscaled, TEXT};
-use probability::{EvalCount};
+use probability::{EvalCount, Probability};
 
 use std::io::Write;
 
 use xs::Xs;
 
-use crate::shared_game_types::{CpuPersonality, Personality, ModeCmd, SkipState};
-use crate::ui::{self, draw_money_in_rect, stack_money_text, ButtonSpec, Id::*, do_button};
+use crate::shared_game_types::{HandCount, Personality, ModeCmd, SkipState, Standings, StartingSeat, gen_personalities, SeatEditor, SeatSetup, SeatSetups, default_seat_setups, set_unedited_starting_money};
+use crate::strategy::PotBetView;
+use crate::ui::{self, StandingsCmd, draw_money_in_rect, stack_money_text, ButtonSpec, Id::*, do_button};
 
 type Posts = [Card; 2];
 
@@ -38,6 +39,109 @@ fn get_ranks(posts: Posts, first_post: HighLow) -> [HighLowRank; 2] {
     ]
 }
 
+#[derive(PartialEq, Eq)]
+enum Outcome {
+    Loss,
+    Win,
+}
+
+/// `pair_call` is whether the third card needs to be higher or lower than the
+/// posts, when the posts are a pair and pairs are played high/low.
+fn calc_outcome(
+    posts: Posts,
+    third: Card,
+    ace: Ace,
+    pair_call: Option<HighLow>,
+) -> Outcome {
+    use Outcome::*;
+    let ranks = get_ranks(
+        posts,
+        match ace {
+            Ace::Undecided => HighLow::default(),
+            Ace::Decided(high_low) => high_low,
+        }
+    );
+    let min_rank = core::cmp::min(ranks[0], ranks[1]);
+    let max_rank = core::cmp::max(ranks[0], ranks[1]);
+    let third_rank = get_rank(third);
+    let is_win = match pair_call {
+        None => min_rank < third_rank && third_rank < max_rank,
+        Some(HighLow::High) => third_rank > max_rank,
+        Some(HighLow::Low) => third_rank < min_rank,
+    };
+
+    if is_win {
+        Win
+    } else {
+        Loss
+    }
+}
+
+fn posts_bitset(posts: Posts) -> CardBitset {
+    let mut output = CardBitset::default();
+    output.set(posts[0]);
+    output.set(posts[1]);
+    output
+}
+
+/// The exact probability that the third card wins, given every card that has
+/// been `seen` since the last shuffle, which includes the posts.
+fn win_probability(
+    posts: Posts,
+    high_low: HighLow,
+    pair_call: Option<HighLow>,
+    seen: CardBitset,
+) -> Probability {
+    let mut remaining_cards = CardBitset::full();
+    for card in seen.iter() {
+        remaining_cards.remove(card);
+    }
+
+    if remaining_cards.len() == 0 {
+        // The deck will be reshuffled, without the posts, before the third card
+        // is drawn.
+        remaining_cards = CardBitset::full();
+        remaining_cards.remove(posts[0]);
+        remaining_cards.remove(posts[1]);
+    }
+
+    let mut eval_count = EvalCount {
+        total: 0,
+        win_count: 0,
+    };
+    for card in remaining_cards.iter() {
+        eval_count.total += 1;
+
+        if calc_outcome(posts, card, Ace::Decided(high_low), pair_call) == Outcome::Win {
+            eval_count.win_count += 1;
+        }
+    }
+
+    eval_count.probability()
+}
+
+/// Whether calling a first post ace high or low gives the better chance of
+/// winning, given the cards `seen` since the last shuffle.
+fn best_high_low(posts: Posts, seen: CardBitset) -> HighLow {
+    if win_probability(posts, HighLow::High, None, seen)
+    > win_probability(posts, HighLow::Low, None, seen) {
+        HighLow::High
+    } else {
+        HighLow::Low
+    }
+}
+
+/// Whether calling higher or lower than a pair gives the better chance of
+/// winning, given the cards `seen` since the last shuffle.
+fn best_pair_call(posts: Posts, high_low: HighLow, seen: CardBitset) -> HighLow {
+    if win_probability(posts, high_low, Some(HighLow::High), seen)
+    > win_probability(posts, high_low, Some(HighLow::Low), seen) {
+        HighLow::High
+    } else {
+        HighLow::Low
+    }
+}
+
 fn deal(rng: &mut Xs) -> (Posts, Deck) {
     let mut deck = gen_deck(rng);
 
@@ -54,6 +158,23 @@ fn deal(rng: &mut Xs) -> (Posts, Deck) {
     (posts, deck)
 }
 
+/// Replaces the posts with the next two cards from the deck, reshuffling if
+/// needed, so the current player can have another go.
+fn redeal_posts(rng: &mut Xs, bundle: &mut StateBundle) {
+    if let (Some(card1), Some(card2)) = (bundle.deck.draw(), bundle.deck.draw()) {
+        bundle.posts = [card1, card2];
+        bundle.seen.set(card1);
+        bundle.seen.set(card2);
+    } else {
+        let (posts, deck) = deal(rng);
+        bundle.posts = posts;
+        bundle.deck = deck;
+        bundle.seen = posts_bitset(posts);
+    }
+
+    bundle.selection = MenuSelection::default();
+}
+
 /// In some sense any number of players could play, but we want some maximum.
 /// Each turn up to 3 cards may be dealt, so if more than 17 players play, 
 /// then the deck will need to be reshuffled every single round. This seems
@@ -216,10 +337,304 @@ pub struct Seats {
     pub moneys: [Money; MAX_PLAYERS as usize],
     pub personalities: [Personality; MAX_PLAYERS as usize],
     pub skip: SkipState,
+    /// Each turn at the posts counts as one hand.
+    pub hands_played: HandCount,
+    pub standings: Standings,
+    pub rules: Rules,
 }
 
 type Pot = Money;
 
+const CONNECTORS_AMOUNT: NonZeroMoneyInner = MIN_MONEY_UNIT;
+const PAIR_AMOUNT: NonZeroMoneyInner = MIN_MONEY_UNIT.saturating_add(MIN_MONEY_UNIT.get());
+
+/// The house rules the game is played with, since there are many variations
+/// of acey-deucey around.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rules {
+    pub max_bet: MaxBet,
+    pub pairs: PairRule,
+    /// What a player pays into the pot when the posts are a pair, and pairs
+    /// are burned.
+    pub pair_burn: NonZeroMoneyInner,
+    /// What a player pays into the pot when the posts are consecutive, so
+    /// nothing can land between them.
+    pub connectors_burn: NonZeroMoneyInner,
+    pub re_ante: ReAnte,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            max_bet: <_>::default(),
+            pairs: <_>::default(),
+            pair_burn: PAIR_AMOUNT,
+            connectors_burn: CONNECTORS_AMOUNT,
+            re_ante: <_>::default(),
+        }
+    }
+}
+
+/// The most that can be bet on the third card.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MaxBet {
+    /// Half the pot on the first turn, and the whole pot after that.
+    #[default]
+    Pot,
+    /// At most this much, and still no more than the pot limit.
+    Capped(NonZeroMoneyInner),
+}
+
+impl MaxBet {
+    pub const PRESETS: [Self; 4] = [
+        Self::Pot,
+        Self::Capped(INITIAL_ANTE_AMOUNT),
+        Self::Capped(INITIAL_ANTE_AMOUNT.saturating_mul(NonZeroMoneyInner::MIN.saturating_add(1))),
+        Self::Capped(INITIAL_ANTE_AMOUNT.saturating_mul(NonZeroMoneyInner::MIN.saturating_add(3))),
+    ];
+
+    fn preset_index(self) -> usize {
+        Self::PRESETS.iter()
+            .position(|&preset| preset == self)
+            .unwrap_or(0)
+    }
+
+    pub fn next_up(self) -> Self {
+        let index = self.preset_index();
+        Self::PRESETS[if index == 0 { Self::PRESETS.len() - 1 } else { index - 1 }]
+    }
+
+    pub fn next_down(self) -> Self {
+        Self::PRESETS[(self.preset_index() + 1) % Self::PRESETS.len()]
+    }
+
+    fn limit(self, pot: MoneyInner, round: Round) -> MoneyInner {
+        let pot_limit = match round {
+            Round::One => pot / 2,
+            Round::AfterOne => pot,
+        };
+
+        match self {
+            Self::Pot => pot_limit,
+            Self::Capped(cap) => core::cmp::min(pot_limit, cap.get()),
+        }
+    }
+}
+
+impl core::fmt::Display for MaxBet {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Pot => write!(f, "pot"),
+            Self::Capped(cap) => write!(f, "${cap}"),
+        }
+    }
+}
+
+/// What happens when the posts are a pair, so nothing can land between them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PairRule {
+    /// The player pays `Rules::pair_burn` into the pot, and the turn passes.
+    #[default]
+    Burn,
+    /// New posts are dealt to the same player.
+    Redeal,
+    /// The player calls whether the third card will be higher or lower than
+    /// the pair, and bets on that.
+    HighLow,
+}
+
+impl PairRule {
+    pub const ALL: [Self; 3] = [
+        Self::Burn,
+        Self::Redeal,
+        Self::HighLow,
+    ];
+
+    pub fn text(self) -> &'static [u8] {
+        match self {
+            Self::Burn => b"burn",
+            Self::Redeal => b"redeal",
+            Self::HighLow => b"hi-lo",
+        }
+    }
+
+    pub fn next_up(self) -> Self {
+        match self {
+            Self::Burn => Self::HighLow,
+            Self::Redeal => Self::Burn,
+            Self::HighLow => Self::Redeal,
+        }
+    }
+
+    pub fn next_down(self) -> Self {
+        match self {
+            Self::Burn => Self::Redeal,
+            Self::Redeal => Self::HighLow,
+            Self::HighLow => Self::Burn,
+        }
+    }
+}
+
+/// What happens when someone wins everything in the pot.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ReAnte {
+    /// The game is over.
+    #[default]
+    EndGame,
+    /// Everyone with money antes again, and the game goes on until one
+    /// player has all the money.
+    Everyone,
+}
+
+impl ReAnte {
+    pub const ALL: [Self; 2] = [
+        Self::EndGame,
+        Self::Everyone,
+    ];
+
+    pub fn text(self) -> &'static [u8] {
+        match self {
+            Self::EndGame => b"end",
+            Self::Everyone => b"re-ante",
+        }
+    }
+
+    pub fn next_up(self) -> Self {
+        match self {
+            Self::EndGame => Self::Everyone,
+            Self::Everyone => Self::EndGame,
+        }
+    }
+
+    pub fn next_down(self) -> Self {
+        // With only two options, going either way is the same.
+        self.next_up()
+    }
+}
+
+fn seats_with_money(moneys: &[Money]) -> usize {
+    moneys.iter()
+        .filter(|money| **money != 0)
+        .count()
+}
+
+/// Everyone puts the ante into the pot, or as much of it as they have.
+fn collect_antes(moneys: &mut [Money], pot: &mut Pot) {
+    for money in moneys {
+        MoneyMove {
+            from: money,
+            to: pot,
+            amount: INITIAL_ANTE_AMOUNT,
+        }.perform();
+    }
+}
+
+/// Once the pot has been emptied, everyone antes again if the house rules say
+/// to, as long as there are still at least two players to play.
+fn re_ante_if_emptied(re_ante: ReAnte, moneys: &mut [Money], pot: &mut Pot) {
+    if *pot == 0
+    && re_ante == ReAnte::Everyone
+    && seats_with_money(moneys) >= 2 {
+        collect_antes(moneys, pot);
+    }
+}
+
+/// Gives out what is left in the pot once fewer than two players have money.
+/// Returns the player who took all of it, if there was one.
+fn hand_out_leftover_pot(
+    moneys: &mut [Money],
+    pot: &mut Pot,
+    current: HandIndex,
+) -> Option<usize> {
+    match moneys.iter().position(|money| *money != 0) {
+        Some(last_player_with_money) => {
+            // Nobody else is left to put money in, so the last player
+            // takes what is left in the pot.
+            MoneyMove {
+                from: pot,
+                to: &mut moneys[last_player_with_money],
+                amount: NonZeroMoneyInner::MAX,
+            }.perform();
+
+            Some(last_player_with_money)
+        },
+        None => {
+            // This is traditionally played against "the house", so
+            // all the money collecting in the pot is a feature, not a
+            // bug. But there is no house here, and the money needs to
+            // go back in case this is dealer's choice, so split it up.
+            pot.split_among(
+                moneys,
+                usize::from(current)
+            );
+
+            None
+        },
+    }
+}
+
+#[test]
+fn max_bet_is_limited_by_the_pot_and_the_cap() {
+    assert_eq!(MaxBet::Pot.limit(100, Round::One), 50);
+    assert_eq!(MaxBet::Pot.limit(100, Round::AfterOne), 100);
+
+    let capped = MaxBet::Capped(INITIAL_ANTE_AMOUNT);
+    assert_eq!(capped.limit(1000, Round::One), INITIAL_ANTE_AMOUNT.get());
+    assert_eq!(capped.limit(1000, Round::AfterOne), INITIAL_ANTE_AMOUNT.get());
+    assert_eq!(capped.limit(10, Round::One), 5);
+    assert_eq!(capped.limit(10, Round::AfterOne), 10);
+}
+
+#[test]
+fn the_last_player_with_money_takes_the_rest_of_the_pot() {
+    let mut moneys = Money::array_from_inner_array([0, 40, 0]);
+    let [mut pot] = Money::array_from_inner_array([30]);
+
+    assert_eq!(hand_out_leftover_pot(&mut moneys, &mut pot, 0), Some(1));
+    assert_eq!(moneys, Money::array_from_inner_array([0, 70, 0]));
+    assert_eq!(pot, 0);
+}
+
+#[test]
+fn the_pot_is_split_up_when_nobody_has_money_left() {
+    let unit = MIN_MONEY_UNIT.get();
+
+    let mut moneys = Money::array_from_inner_array([0, 0, 0]);
+    let [mut pot] = Money::array_from_inner_array([unit * 4]);
+
+    assert_eq!(hand_out_leftover_pot(&mut moneys, &mut pot, 1), None);
+    // The remainder starts with the current player.
+    assert_eq!(moneys, Money::array_from_inner_array([unit, unit * 2, unit]));
+    assert_eq!(pot, 0);
+}
+
+#[test]
+fn everyone_re_antes_only_while_two_players_have_money() {
+    let ante = INITIAL_ANTE_AMOUNT.get();
+
+    let mut moneys = Money::array_from_inner_array([100, 0, 100]);
+    let mut pot = Money::ZERO;
+    re_ante_if_emptied(ReAnte::Everyone, &mut moneys, &mut pot);
+    assert_eq!(moneys, Money::array_from_inner_array([100 - ante, 0, 100 - ante]));
+    assert_eq!(pot, ante * 2);
+
+    // With the pot not emptied, nobody antes.
+    re_ante_if_emptied(ReAnte::Everyone, &mut moneys, &mut pot);
+    assert_eq!(pot, ante * 2);
+
+    let mut moneys = Money::array_from_inner_array([100, 0, 0]);
+    let mut pot = Money::ZERO;
+    re_ante_if_emptied(ReAnte::Everyone, &mut moneys, &mut pot);
+    assert_eq!(moneys, Money::array_from_inner_array([100, 0, 0]));
+    assert_eq!(pot, 0);
+
+    let mut moneys = Money::array_from_inner_array([100, 0, 100]);
+    let mut pot = Money::ZERO;
+    re_ante_if_emptied(ReAnte::EndGame, &mut moneys, &mut pot);
+    assert_eq!(moneys, Money::array_from_inner_array([100, 0, 100]));
+    assert_eq!(pot, 0);
+}
+
 #[derive(Clone, Debug, Default)]
 pub enum Action {
     #[default]
@@ -228,11 +643,6 @@ pub enum Action {
     Burn(NonZeroMoneyInner),
 }
 
-const CONNECTORS_AMOUNT: NonZeroMoneyInner = MIN_MONEY_UNIT;
-const CONNECTORS_BURN: Action = Action::Burn(CONNECTORS_AMOUNT);
-const PAIR_AMOUNT: NonZeroMoneyInner = MIN_MONEY_UNIT.saturating_add(MIN_MONEY_UNIT.get());
-const PAIR_BURN: Action = Action::Burn(PAIR_AMOUNT);
-
 #[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
 pub enum ActionKind {
     #[default]
@@ -267,7 +677,7 @@ impl ActionKind {
     }
 }
 
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum HighLow {
     #[default]
     Low,
@@ -313,6 +723,9 @@ pub struct MenuSelection {
     pub bet: NonZeroMoneyInner,
     pub ace: Ace,
     pub temp_high_low: HighLow,
+    /// Whether the third card needs to be higher or lower than a pair, if
+    /// that has been called yet.
+    pub pair_call: Option<HighLow>,
     pub cpu_passed: bool,
 }
 
@@ -323,6 +736,7 @@ impl Default for MenuSelection {
             bet: MIN_MONEY_UNIT,
             ace: Ace::default(),
             temp_high_low: <_>::default(),
+            pair_call: <_>::default(),
             cpu_passed: <_>::default(),
         }
     }
@@ -338,6 +752,9 @@ pub enum Round {
 #[derive(Clone)]
 pub struct StateBundle {
     pub deck: Deck,
+    /// Every card dealt face up since `deck` was last shuffled, including the
+    /// current posts.
+    pub seen: CardBitset,
     pub posts: Posts,
     pub current: HandIndex,
     pub pot: Pot,
@@ -348,7 +765,14 @@ pub struct StateBundle {
 
 #[derive(Clone)]
 pub enum TableState {
-    Undealt { player_count: PlayerCount, starting_money: MoneyInner },
+    Undealt {
+        player_count: PlayerCount,
+        starting_money: MoneyInner,
+        seats: SeatSetups,
+        /// Set while the seat setup screen is open.
+        seat_editor: Option<SeatEditor>,
+        rules: Rules,
+    },
     DealtPosts {
         bundle: StateBundle,
     },
@@ -357,6 +781,8 @@ pub enum TableState {
         third: Card,
         bet: NonZeroMoneyInner,
     },
+    /// The game is over, and the final standings are being shown.
+    Finished,
 }
 
 impl Default for TableState {
@@ -364,6 +790,9 @@ impl Default for TableState {
         Self::Undealt {
             player_count: <_>::default(),
             starting_money: 500,
+            seats: default_seat_setups(500),
+            seat_editor: None,
+            rules: <_>::default(),
         }
     }
 }
@@ -379,25 +808,17 @@ impl Table {
         rng: &mut Xs,
         player_count: PlayerCount,
         mut moneys: [Money; MAX_PLAYERS as usize],
+        setups: &[SeatSetup],
+        rules: Rules,
     ) -> Self {
-        let mut pot: Pot = Money::ZERO;
+        let mut personalities: [Personality; MAX_PLAYERS as usize] = <_>::default();
+        gen_personalities(rng, &mut personalities[..player_count.usize()], setups);
 
-        for i in 0..player_count.usize() {
-            MoneyMove {
-                from: &mut moneys[i],
-                to: &mut pot,
-                amount: INITIAL_ANTE_AMOUNT,
-            }.perform();
-        }
+        let standings = Standings::new(&moneys, setups);
 
-        let mut personalities: [Personality; MAX_PLAYERS as usize] = <_>::default();
+        let mut pot: Pot = Money::ZERO;
 
-        personalities[0] = None;
-        // TODO Make each element of this array user selectable too.
-        // Start at 1 to make the first player user controlled
-        for i in 1..player_count.usize() {
-            personalities[i] = Some(CpuPersonality{});
-        }
+        collect_antes(&mut moneys[..player_count.usize()], &mut pot);
 
         let (posts, deck) = deal(rng);
 
@@ -421,16 +842,21 @@ impl Table {
             selected
         };
 
-        // TODO handle case where the pot has all the money in it!
+        // If the antes left fewer than two players with money, the game ends
+        // as soon as it is updated, instead of being played.
         Self {
             seats: Seats {
                 moneys,
                 personalities,
                 skip: <_>::default(),
+                hands_played: 0,
+                standings,
+                rules,
             },
             state: TableState::DealtPosts {
                 bundle: StateBundle {
                     deck,
+                    seen: posts_bitset(posts),
                     posts,
                     current,
                     pot,
@@ -576,13 +1002,34 @@ pub fn update_and_render(
         }
     }
 
+    macro_rules! record_stacks {
+        () => {
+            // Acey-deucey does not condense the seats, so the seats never change.
+            state.table.seats.standings.record_stacks(
+                state.table.seats.hands_played,
+                state.table.seats.moneys.iter()
+                    .enumerate()
+                    .map(|(seat, money)| (seat as StartingSeat, money.as_inner())),
+            );
+        }
+    }
+
     macro_rules! next_bundle {
         ($bundle: ident =
             $deck: expr,
+            $seen: expr,
             $current: expr,
             $player_count: expr,
             $pot: expr
         ) => {
+            state.table.seats.hands_played = state.table.seats.hands_played.saturating_add(1);
+            record_stacks!();
+
+            let only_cpus_left = !state.table.seats.moneys.iter()
+                .zip(state.table.seats.personalities.iter())
+                .any(|(money, personality)| *money != 0 && personality.is_none());
+            state.table.seats.skip.simulate_if_only_cpus_left(only_cpus_left);
+
             let mut deck = $deck;
             let previous_index = $current;
             let player_count = $player_count;
@@ -603,14 +1050,19 @@ pub fn update_and_render(
                 index
             };
 
-            let (posts, deck) = if let (Some(card1), Some(card2)) = (deck.draw(), deck.draw()) {
-                ([card1, card2], deck)
+            let (posts, deck, seen) = if let (Some(card1), Some(card2)) = (deck.draw(), deck.draw()) {
+                let mut seen = $seen;
+                seen.set(card1);
+                seen.set(card2);
+                ([card1, card2], deck, seen)
             } else {
-                deal(rng)
+                let (posts, deck) = deal(rng);
+                (posts, deck, posts_bitset(posts))
             };
 
-            let mut $bundle = StateBundle {
+            let $bundle = StateBundle {
                 deck,
+                seen,
                 posts,
                 current,
                 pot: $pot.take_all(),
@@ -618,57 +1070,20 @@ pub fn update_and_render(
                 selection: MenuSelection::default(),
                 round: Round::AfterOne,
             };
+        }
+    }
 
-            let pot_has_all_the_money: bool = 
-                state.table.seats.moneys.iter()
-                    .all(|m| m.as_inner() == 0);
-
-            if pot_has_all_the_money {
-                // This is traditionally played against "the house", so there all the
-                // money collecting there is a feature, not a bug.
-                // Maybe we'll make that matter later, but for now it's just a 
-                // disappointing outcome, so split up the money in case this is 
-                // dealer's choice, and go back.
-                $bundle.pot.split_among(
-                    &mut state.table.seats.moneys[..],
-                    usize::from(previous_index)
-                );
+    macro_rules! finish_game {
+        ($speaker: expr) => {
+            record_stacks!();
 
-                cmd = ModeCmd::BackToTitleScreen;
-            }
+            $speaker.request_sfx(SFX::CardPlace);
+            state.table.state = Finished;
+            cmd = ModeCmd::FinishedRound;
         }
     }
 
-    #[derive(PartialEq, Eq)]
-    enum Outcome {
-        Loss,
-        Win,
-    }
     use Outcome::*;
-    
-    fn calc_outcome(
-        posts: Posts,
-        third: Card,
-        ace: Ace,
-    ) -> Outcome {
-        use Outcome::*;
-        let ranks = get_ranks(
-            posts,
-            match ace {
-                Ace::Undecided => HighLow::default(),
-                Ace::Decided(high_low) => high_low,
-            }
-        );
-        let min_rank = core::cmp::min(ranks[0], ranks[1]);
-        let max_rank = core::cmp::max(ranks[0], ranks[1]);
-        let third_rank = get_rank(third);
-        if min_rank < third_rank
-        && third_rank < max_rank {
-            Win
-        } else {
-            Loss
-        }
-    }
 
     const MENU_H: unscaled::H = unscaled::h_const_div(
         command::HEIGHT_H,
@@ -689,9 +1104,48 @@ pub fn update_and_render(
     };
 
     match &mut state.table.state {
+        Finished => {
+            match ui::do_standings(new_group!(), &state.table.seats.standings) {
+                StandingsCmd::NoOp => {},
+                StandingsCmd::PlayAgain => {
+                    let standings = &state.table.seats.standings;
+
+                    let mut moneys = [0; MAX_PLAYERS as usize];
+                    for (money, standing) in moneys.iter_mut().zip(standings.seats.iter()) {
+                        *money = standing.starting_money;
+                    }
+
+                    if let Ok(player_count) = PlayerCount::try_from(standings.player_count) {
+                        speaker.request_sfx(SFX::CardPlace);
+
+                        *state.table = Table::selected(
+                            rng,
+                            player_count,
+                            Money::array_from_inner_array(moneys),
+                            &standings.setups,
+                            state.table.seats.rules,
+                        );
+                    }
+                },
+                StandingsCmd::BackToTitleScreen => {
+                    cmd = ModeCmd::BackToTitleScreen;
+                },
+            }
+        },
+        Undealt {
+            ref player_count,
+            ref mut seats,
+            ref mut seat_editor,
+            ..
+        } if seat_editor.is_some() => {
+            ui::do_seat_editor(new_group!(), seats, player_count.u8(), seat_editor);
+        },
         Undealt {
             ref mut player_count,
             ref mut starting_money,
+            ref mut seats,
+            ref mut seat_editor,
+            ref mut rules,
         } => {
             let group = new_group!();
 
@@ -711,37 +1165,252 @@ pub fn update_and_render(
                 cmd = ModeCmd::BackToTitleScreen;
             }
 
-            let player_count_rect = unscaled::Rect {
-                x: unscaled::X(100),
-                y: unscaled::Y(100),
-                w: unscaled::W(50),
-                h: unscaled::H(100),
-            };
+            let player_count_rect = unscaled::Rect {
+                x: unscaled::X(50),
+                y: unscaled::Y(100),
+                w: unscaled::W(50),
+                h: unscaled::H(100),
+            };
+
+            let player_count_text = player_count.text().as_bytes();
+
+            {
+                let xy = gfx::center_line_in_rect(
+                    player_count_text.len() as _,
+                    player_count_rect,
+                );
+                group.commands.print_chars(
+                    player_count_text,
+                    xy.x,
+                    xy.y,
+                    TEXT
+                );
+            }
+            {
+                let players_label = b"players";
+
+                let xy = gfx::center_line_in_rect(
+                    players_label.len() as _,
+                    player_count_rect,
+                );
+
+                group.commands.print_chars(
+                    players_label,
+                    xy.x,
+                    xy.y + gfx::CHAR_H,
+                    TEXT
+                );
+            }
+
+            ui::draw_quick_select(
+                group,
+                player_count_rect,
+                PlayerCountSelect,
+            );
+
+            if do_button(
+                group,
+                ButtonSpec {
+                    id: EditSeats,
+                    rect: unscaled::Rect {
+                        x: player_count_rect.x,
+                        y: player_count_rect.y + player_count_rect.h,
+                        w: unscaled::W(50),
+                        h: unscaled::H(50),
+                    },
+                    text: b"seats",
+                }
+            ) {
+                *seat_editor = Some(SeatEditor::default());
+            }
+
+            let starting_money_rect = unscaled::Rect {
+                x: unscaled::X(100),
+                y: unscaled::Y(100),
+                w: unscaled::W(50),
+                h: unscaled::H(100),
+            };
+
+            draw_money_in_rect!(group, starting_money, starting_money_rect);
+
+            ui::draw_quick_select(
+                group,
+                starting_money_rect,
+                StartingMoneySelect,
+            );
+
+            let max_bet_rect = unscaled::Rect {
+                x: starting_money_rect.x + starting_money_rect.w,
+                ..starting_money_rect
+            };
+
+            {
+                let mut max_bet_text = [0u8; 16];
+                let _cant_actually_fail = write!(
+                    &mut max_bet_text[..],
+                    "{}",
+                    rules.max_bet,
+                );
+
+                let xy = gfx::center_line_in_rect(
+                    pre_nul_len(&max_bet_text),
+                    max_bet_rect,
+                );
+                group.commands.print_chars(
+                    &max_bet_text,
+                    xy.x,
+                    xy.y,
+                    TEXT
+                );
+            }
+            {
+                let max_bet_label = b"max bet";
+
+                let xy = gfx::center_line_in_rect(
+                    max_bet_label.len() as _,
+                    max_bet_rect,
+                );
+
+                group.commands.print_chars(
+                    max_bet_label,
+                    xy.x,
+                    xy.y + gfx::CHAR_H,
+                    TEXT
+                );
+            }
+
+            ui::draw_quick_select(
+                group,
+                max_bet_rect,
+                MaxBetSelect,
+            );
+
+            let pairs_rect = unscaled::Rect {
+                x: max_bet_rect.x + max_bet_rect.w,
+                ..max_bet_rect
+            };
+
+            {
+                let pairs_text = rules.pairs.text();
+
+                let xy = gfx::center_line_in_rect(
+                    pairs_text.len() as _,
+                    pairs_rect,
+                );
+                group.commands.print_chars(
+                    pairs_text,
+                    xy.x,
+                    xy.y,
+                    TEXT
+                );
+            }
+            {
+                let pairs_label = b"pairs";
+
+                let xy = gfx::center_line_in_rect(
+                    pairs_label.len() as _,
+                    pairs_rect,
+                );
+
+                group.commands.print_chars(
+                    pairs_label,
+                    xy.x,
+                    xy.y + gfx::CHAR_H,
+                    TEXT
+                );
+            }
+
+            ui::draw_quick_select(
+                group,
+                pairs_rect,
+                PairRuleSelect,
+            );
+
+            let pair_burn_rect = unscaled::Rect {
+                x: pairs_rect.x + pairs_rect.w,
+                ..pairs_rect
+            };
+
+            draw_money_in_rect!(group, rules.pair_burn, pair_burn_rect);
+            {
+                let pair_burn_label = b"pair burn";
+
+                let xy = gfx::center_line_in_rect(
+                    pair_burn_label.len() as _,
+                    pair_burn_rect,
+                );
+
+                group.commands.print_chars(
+                    pair_burn_label,
+                    xy.x,
+                    xy.y + gfx::CHAR_H,
+                    TEXT
+                );
+            }
+
+            ui::draw_quick_select(
+                group,
+                pair_burn_rect,
+                PairBurnSelect,
+            );
+
+            let connectors_burn_rect = unscaled::Rect {
+                x: pair_burn_rect.x + pair_burn_rect.w,
+                ..pair_burn_rect
+            };
+
+            draw_money_in_rect!(group, rules.connectors_burn, connectors_burn_rect);
+            {
+                let connectors_burn_label = b"conn. burn";
+
+                let xy = gfx::center_line_in_rect(
+                    connectors_burn_label.len() as _,
+                    connectors_burn_rect,
+                );
+
+                group.commands.print_chars(
+                    connectors_burn_label,
+                    xy.x,
+                    xy.y + gfx::CHAR_H,
+                    TEXT
+                );
+            }
+
+            ui::draw_quick_select(
+                group,
+                connectors_burn_rect,
+                ConnectorsBurnSelect,
+            );
 
-            let player_count_text = player_count.text().as_bytes();
+            let re_ante_rect = unscaled::Rect {
+                x: connectors_burn_rect.x + connectors_burn_rect.w,
+                ..connectors_burn_rect
+            };
 
             {
+                let re_ante_text = rules.re_ante.text();
+
                 let xy = gfx::center_line_in_rect(
-                    player_count_text.len() as _,
-                    player_count_rect,
+                    re_ante_text.len() as _,
+                    re_ante_rect,
                 );
                 group.commands.print_chars(
-                    player_count_text,
+                    re_ante_text,
                     xy.x,
                     xy.y,
                     TEXT
                 );
             }
             {
-                let players_label = b"players";
+                let re_ante_label = b"empty pot";
 
                 let xy = gfx::center_line_in_rect(
-                    players_label.len() as _,
-                    player_count_rect,
+                    re_ante_label.len() as _,
+                    re_ante_rect,
                 );
 
                 group.commands.print_chars(
-                    players_label,
+                    re_ante_label,
                     xy.x,
                     xy.y + gfx::CHAR_H,
                     TEXT
@@ -750,23 +1419,8 @@ pub fn update_and_render(
 
             ui::draw_quick_select(
                 group,
-                player_count_rect,
-                PlayerCountSelect,
-            );
-
-            let starting_money_rect = unscaled::Rect {
-                x: unscaled::X(150),
-                y: unscaled::Y(100),
-                w: unscaled::W(50),
-                h: unscaled::H(100),
-            };
-
-            draw_money_in_rect!(group, starting_money, starting_money_rect);
-
-            ui::draw_quick_select(
-                group,
-                starting_money_rect,
-                StartingMoneySelect,
+                re_ante_rect,
+                ReAnteSelect,
             );
 
             if do_button(
@@ -774,7 +1428,7 @@ pub fn update_and_render(
                 ButtonSpec {
                     id: Submit,
                     rect: unscaled::Rect {
-                        x: starting_money_rect.x + starting_money_rect.w,
+                        x: re_ante_rect.x + re_ante_rect.w,
                         y: unscaled::Y(100),
                         w: unscaled::W(50),
                         h: unscaled::H(100),
@@ -788,7 +1442,7 @@ pub fn update_and_render(
 
                 let mut moneys = [0; MAX_PLAYERS as usize];
                 for i in 0..player_count.usize() {
-                    moneys[i] = *starting_money;
+                    moneys[i] = seats[i].starting_money;
                 }
                 let moneys = Money::array_from_inner_array(moneys);
 
@@ -796,9 +1450,11 @@ pub fn update_and_render(
                     rng,
                     player_count,
                     moneys,
+                    &seats[..],
+                    *rules,
                 );
             } else {
-                let menu = [BackToTitleScreen, PlayerCountSelect, StartingMoneySelect, Submit];
+                let menu = [BackToTitleScreen, PlayerCountSelect, EditSeats, StartingMoneySelect, MaxBetSelect, PairRuleSelect, PairBurnSelect, ConnectorsBurnSelect, ReAnteSelect, Submit];
 
                 match group.ctx.hot {
                     BackToTitleScreen => {
@@ -813,16 +1469,118 @@ pub fn update_and_render(
                         }
                     }
                     StartingMoneySelect => {
-                        let menu_i = 2;
+                        let menu_i = 3;
                         match input.dir_pressed_this_frame() {
                             Some(Dir::Up) => {
+                                let previous = *starting_money;
                                 *starting_money = starting_money.saturating_add(MIN_MONEY_UNIT.get());
+                                set_unedited_starting_money(seats, previous, *starting_money);
                             },
                             Some(Dir::Down) => {
+                                let previous = *starting_money;
                                 *starting_money = starting_money.saturating_sub(MIN_MONEY_UNIT.get());
                                 if *starting_money == 0 {
                                     *starting_money = MIN_MONEY_UNIT.get();
                                 }
+                                set_unedited_starting_money(seats, previous, *starting_money);
+                            },
+                            Some(Dir::Left) => {
+                                group.ctx.set_next_hot(menu[menu_i - 1]);
+                            }
+                            Some(Dir::Right) => {
+                                group.ctx.set_next_hot(menu[menu_i + 1]);
+                            }
+                            None => {}
+                        }
+                    }
+                    MaxBetSelect => {
+                        let menu_i = 4;
+                        match input.dir_pressed_this_frame() {
+                            Some(Dir::Up) => {
+                                rules.max_bet = rules.max_bet.next_up();
+                            },
+                            Some(Dir::Down) => {
+                                rules.max_bet = rules.max_bet.next_down();
+                            },
+                            Some(Dir::Left) => {
+                                group.ctx.set_next_hot(menu[menu_i - 1]);
+                            }
+                            Some(Dir::Right) => {
+                                group.ctx.set_next_hot(menu[menu_i + 1]);
+                            }
+                            None => {}
+                        }
+                    }
+                    PairRuleSelect => {
+                        let menu_i = 5;
+                        match input.dir_pressed_this_frame() {
+                            Some(Dir::Up) => {
+                                rules.pairs = rules.pairs.next_up();
+                            },
+                            Some(Dir::Down) => {
+                                rules.pairs = rules.pairs.next_down();
+                            },
+                            Some(Dir::Left) => {
+                                group.ctx.set_next_hot(menu[menu_i - 1]);
+                            }
+                            Some(Dir::Right) => {
+                                group.ctx.set_next_hot(menu[menu_i + 1]);
+                            }
+                            None => {}
+                        }
+                    }
+                    PairBurnSelect => {
+                        let menu_i = 6;
+                        match input.dir_pressed_this_frame() {
+                            Some(Dir::Up) => {
+                                rules.pair_burn = rules.pair_burn.saturating_add(MIN_MONEY_UNIT.get());
+                            },
+                            Some(Dir::Down) => {
+                                let new_value = rules.pair_burn.get()
+                                    .saturating_sub(MIN_MONEY_UNIT.get());
+                                if let Some(new_burn) = NonZeroMoneyInner::new(new_value) {
+                                    rules.pair_burn = new_burn;
+                                }
+                            },
+                            Some(Dir::Left) => {
+                                group.ctx.set_next_hot(menu[menu_i - 1]);
+                            }
+                            Some(Dir::Right) => {
+                                group.ctx.set_next_hot(menu[menu_i + 1]);
+                            }
+                            None => {}
+                        }
+                    }
+                    ConnectorsBurnSelect => {
+                        let menu_i = 7;
+                        match input.dir_pressed_this_frame() {
+                            Some(Dir::Up) => {
+                                rules.connectors_burn = rules.connectors_burn.saturating_add(MIN_MONEY_UNIT.get());
+                            },
+                            Some(Dir::Down) => {
+                                let new_value = rules.connectors_burn.get()
+                                    .saturating_sub(MIN_MONEY_UNIT.get());
+                                if let Some(new_burn) = NonZeroMoneyInner::new(new_value) {
+                                    rules.connectors_burn = new_burn;
+                                }
+                            },
+                            Some(Dir::Left) => {
+                                group.ctx.set_next_hot(menu[menu_i - 1]);
+                            }
+                            Some(Dir::Right) => {
+                                group.ctx.set_next_hot(menu[menu_i + 1]);
+                            }
+                            None => {}
+                        }
+                    }
+                    ReAnteSelect => {
+                        let menu_i = 8;
+                        match input.dir_pressed_this_frame() {
+                            Some(Dir::Up) => {
+                                rules.re_ante = rules.re_ante.next_up();
+                            },
+                            Some(Dir::Down) => {
+                                rules.re_ante = rules.re_ante.next_down();
                             },
                             Some(Dir::Left) => {
                                 group.ctx.set_next_hot(menu[menu_i - 1]);
@@ -851,6 +1609,18 @@ pub fn update_and_render(
                             None => {}
                         }
                     }
+                    EditSeats => {
+                        let menu_i = 2;
+                        match input.dir_pressed_this_frame() {
+                            Some(Dir::Left) => {
+                                group.ctx.set_next_hot(menu[menu_i - 1]);
+                            }
+                            Some(Dir::Right) => {
+                                group.ctx.set_next_hot(menu[menu_i + 1]);
+                            }
+                            _ => {}
+                        }
+                    }
                     Submit => {
                         let menu_i = menu.len() - 1;
                         match input.dir_pressed_this_frame() {
@@ -868,6 +1638,25 @@ pub fn update_and_render(
                 }
             }
         },
+        DealtPosts { bundle, } if seats_with_money(
+            &state.table.seats.moneys[..bundle.player_count.usize()]
+        ) <= 1 => {
+            let player_count = bundle.player_count;
+            let leftover = bundle.pot.as_inner();
+
+            if let Some(last_player_with_money) = hand_out_leftover_pot(
+                &mut state.table.seats.moneys[..player_count.usize()],
+                &mut bundle.pot,
+                bundle.current,
+            ) {
+                state.table.seats.standings.record_pot_won(
+                    last_player_with_money as StartingSeat,
+                    leftover,
+                );
+            }
+
+            finish_game!(speaker);
+        },
         DealtPosts { bundle, } => {
             let group = new_group!();
 
@@ -905,6 +1694,112 @@ pub fn update_and_render(
                 }
             }
 
+            macro_rules! do_high_low_menu {
+                ($prompt: expr) => ({
+                    draw_menu_rect_with_money!();
+
+                    let high_low_rect = unscaled::Rect {
+                        x: MENU_UI_BASE_X,
+                        y: MENU_UI_BASE_Y,
+                        w: unscaled::W(50),
+                        h: MENU_RECT.h - SPACING_H * 2,
+                    };
+
+                    let high_low_text = bundle.selection.temp_high_low.text();
+
+                    {
+                        let xy = gfx::center_line_in_rect(
+                            high_low_text.len() as _,
+                            high_low_rect,
+                        );
+                        group.commands.print_chars(
+                            high_low_text,
+                            xy.x,
+                            xy.y,
+                            TEXT
+                        );
+                    }
+
+                    ui::draw_quick_select(
+                        group,
+                        high_low_rect,
+                        HighLowSelect,
+                    );
+
+                    let submitted = if do_button(
+                        group,
+                        ButtonSpec {
+                            id: HighLowSubmit,
+                            rect: unscaled::Rect {
+                                x: high_low_rect.x + high_low_rect.w + high_low_rect.w,
+                                ..high_low_rect
+                            },
+                            text: b"submit",
+                        }
+                    ) {
+                        Some(bundle.selection.temp_high_low)
+                    } else {
+                        None
+                    };
+
+                    {
+                        let prompt: &[u8] = $prompt;
+
+                        let submit_far_x = high_low_rect.x + high_low_rect.w * 3;
+
+                        let prompt_rect = unscaled::Rect {
+                            x: submit_far_x + SPACING_W,
+                            w: MENU_RECT.w - (
+                                submit_far_x - MENU_RECT.x
+                            ),
+                            ..high_low_rect
+                        };
+
+                        let xy = gfx::center_line_in_rect(
+                            prompt.len() as _,
+                            prompt_rect,
+                        );
+
+                        group.commands.print_chars(
+                            prompt,
+                            xy.x,
+                            xy.y,
+                            TEXT
+                        );
+                    }
+
+                    if group.input.pressed_this_frame(Button::LEFT)
+                    || group.input.pressed_this_frame(Button::RIGHT) {
+                        match group.ctx.hot {
+                            HighLowSubmit => {
+                                group.ctx.set_next_hot(HighLowSelect);
+                            }
+                            HighLowSelect => {
+                                group.ctx.set_next_hot(HighLowSubmit);
+                            }
+                            _ => {}
+                        }
+                    } else {
+                        match group.ctx.hot {
+                            HighLowSelect => {
+                                if group.input.pressed_this_frame(Button::UP) {
+                                    bundle.selection.temp_high_low = bundle.selection.temp_high_low.next_up();
+                                } else if group.input.pressed_this_frame(Button::DOWN) {
+                                    bundle.selection.temp_high_low = bundle.selection.temp_high_low.next_down();
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+
+                    if let Zero = group.ctx.hot {
+                        group.ctx.set_next_hot(HighLowSelect);
+                    }
+
+                    submitted
+                })
+            }
+
             if bundle.selection.cpu_passed {
                 draw_menu_rect_with_money!();
 
@@ -926,6 +1821,7 @@ pub fn update_and_render(
                     next_bundle!(
                         new_bundle =
                             bundle.deck.clone(),
+                            bundle.seen,
                             bundle.current,
                             bundle.player_count,
                             bundle.pot
@@ -948,11 +1844,12 @@ pub fn update_and_render(
                 };
 
                 {
-                    let mut passed_text = [0 as u8; 32];
+                    let mut passed_text = [0u8; 32];
 
                     let _cant_actually_fail = write!(
                         &mut passed_text[..],
-                        "player {current_i} passed",
+                        "{} passed",
+                        state.table.seats.standings.name(current_i as StartingSeat),
                     );
 
                     let xy = gfx::center_line_in_rect(
@@ -988,82 +1885,14 @@ pub fn update_and_render(
                                 current_i
                             ]
                         {
-                            // TODO? Have the CPU player count cards enough to
-                            // know to choose high sometimes?
                             Some(_) => {
-                                bundle.selection.ace = Ace::Decided(HighLow::Low);
+                                bundle.selection.ace = Ace::Decided(
+                                    best_high_low(bundle.posts, bundle.seen)
+                                );
                             },
                             None => {
-                                draw_menu_rect_with_money!();
-    
-                                let high_low_rect = unscaled::Rect {
-                                    x: MENU_UI_BASE_X,
-                                    y: MENU_UI_BASE_Y,
-                                    w: unscaled::W(50),
-                                    h: MENU_RECT.h - SPACING_H * 2,
-                                };
-    
-                                let high_low_text = bundle.selection.temp_high_low.text();
-    
-                                {
-                                    let xy = gfx::center_line_in_rect(
-                                        high_low_text.len() as _,
-                                        high_low_rect,
-                                    );
-                                    group.commands.print_chars(
-                                        high_low_text,
-                                        xy.x,
-                                        xy.y,
-                                        TEXT
-                                    );
-                                }
-    
-                                ui::draw_quick_select(
-                                    group,
-                                    high_low_rect,
-                                    HighLowSelect,
-                                );
-    
-                                if do_button(
-                                    group,
-                                    ButtonSpec {
-                                        id: HighLowSubmit,
-                                        rect: unscaled::Rect {
-                                            x: high_low_rect.x + high_low_rect.w + high_low_rect.w,
-                                            ..high_low_rect
-                                        },
-                                        text: b"submit",
-                                    }
-                                ) {
-                                    bundle.selection.ace = Ace::Decided(bundle.selection.temp_high_low);
-                                }
-    
-                                if group.input.pressed_this_frame(Button::LEFT)
-                                || group.input.pressed_this_frame(Button::RIGHT) {
-                                    match group.ctx.hot {
-                                        HighLowSubmit => {
-                                            group.ctx.set_next_hot(HighLowSelect);
-                                        }
-                                        HighLowSelect => {
-                                            group.ctx.set_next_hot(HighLowSubmit);
-                                        }
-                                        _ => {}
-                                    }
-                                } else {
-                                    match group.ctx.hot {
-                                        HighLowSelect => {
-                                            if group.input.pressed_this_frame(Button::UP) {
-                                                bundle.selection.temp_high_low = bundle.selection.temp_high_low.next_up();
-                                            } else if group.input.pressed_this_frame(Button::DOWN) {
-                                                bundle.selection.temp_high_low = bundle.selection.temp_high_low.next_down();
-                                            }
-                                        }
-                                        _ => {}
-                                    }
-                                }
-    
-                                if let Zero = group.ctx.hot {
-                                    group.ctx.set_next_hot(HighLowSelect);
+                                if let Some(high_low) = do_high_low_menu!(b"is the ace high or low?") {
+                                    bundle.selection.ace = Ace::Decided(high_low);
                                 }
                             }
                         }
@@ -1073,6 +1902,10 @@ pub fn update_and_render(
                             Open,
                             Connectors,
                             Pair,
+                            /// A pair, which will be replaced with new posts.
+                            RedealPair,
+                            /// A pair, which still needs to be called high or low.
+                            CallPair,
                         }
                         use PostsKind::*;
                         let posts_kind = {
@@ -1091,9 +1924,20 @@ pub fn update_and_render(
                                 Open
                             }
                         };
+
+                        let rules = state.table.seats.rules;
+
+                        let posts_kind = match (posts_kind, rules.pairs, bundle.selection.pair_call) {
+                            (Pair, PairRule::Burn, _) => Pair,
+                            (Pair, PairRule::Redeal, _) => RedealPair,
+                            (Pair, PairRule::HighLow, None) => CallPair,
+                            // Once called, a pair is bet on like any other posts.
+                            (Pair, PairRule::HighLow, Some(_)) => Open,
+                            (posts_kind, _, _) => posts_kind,
+                        };
     
                         macro_rules! do_burn_menu {
-                            ($amount: ident) => ({
+                            ($amount: expr) => ({
                                 draw_menu_rect_with_money!();
     
                                 let burn_button_rect = unscaled::Rect {
@@ -1180,34 +2024,52 @@ pub fn update_and_render(
                                 posts_kind
                             )
                         {
-                            (Some(_), Open) => {
-                                let mut remaining_cards = CardBitset::full();
-    
-                                // TODO? Count cards so we can remove more?
-                                remaining_cards.remove(bundle.posts[0]);
-                                remaining_cards.remove(bundle.posts[1]);
-    
-                                let mut eval_count = EvalCount {
-                                    total: 0,
-                                    win_count: 0,
-                                };
-                                for card in remaining_cards.iter() {
-                                    eval_count.total += 1;
-    
-                                    if calc_outcome(bundle.posts, card, bundle.selection.ace)
-                                    == Outcome::Win {
-                                        eval_count.win_count += 1;
-                                    }
+                            (_, RedealPair) => {
+                                redeal_posts(rng, bundle);
+                                None
+                            }
+                            (Some(_), CallPair) => {
+                                bundle.selection.pair_call = Some(
+                                    best_pair_call(bundle.posts, high_low, bundle.seen)
+                                );
+                                None
+                            }
+                            (None, CallPair) => {
+                                if let Some(call) = do_high_low_menu!(b"will the third card be higher or lower?") {
+                                    bundle.selection.pair_call = Some(call);
                                 }
-                                
-                                if eval_count.probability() >= probability::FIFTY_PERCENT {
-                                    Some(Action::Bet(INITIAL_ANTE_AMOUNT))
-                                } else {
-                                    Some(Action::Pass)
+                                None
+                            }
+                            (Some(strategy), Open) => {
+                                let pot_limit = rules.max_bet.limit(
+                                    bundle.pot.as_inner(),
+                                    bundle.round,
+                                );
+
+                                let max_bet = core::cmp::min(
+                                    state.table.seats.moneys[current_i].as_inner(),
+                                    pot_limit,
+                                );
+
+                                match strategy.pot_bet(
+                                    rng,
+                                    &PotBetView {
+                                        win_probability: win_probability(
+                                            bundle.posts,
+                                            high_low,
+                                            bundle.selection.pair_call,
+                                            bundle.seen,
+                                        ),
+                                        stack: state.table.seats.moneys[current_i].as_inner(),
+                                        max_bet,
+                                    },
+                                ) {
+                                    Some(bet) => Some(Action::Bet(bet)),
+                                    None => Some(Action::Pass),
                                 }
                             }
-                            (Some(_), Connectors) => Some(CONNECTORS_BURN),
-                            (Some(_), Pair) => Some(PAIR_BURN),
+                            (Some(_), Connectors) => Some(Action::Burn(rules.connectors_burn)),
+                            (Some(_), Pair) => Some(Action::Burn(rules.pair_burn)),
                             (None, Open) => {
                                 const ACTION_KIND: ui::AceyDeuceyMenuId = 0;
                                 const MONEY_AMOUNT: ui::AceyDeuceyMenuId = 1;
@@ -1352,10 +2214,10 @@ pub fn update_and_render(
                                 player_action_opt
                             }
                             (None, Connectors) => {
-                                do_burn_menu!(CONNECTORS_AMOUNT)
+                                do_burn_menu!(rules.connectors_burn)
                             }
                             (None, Pair) => {
-                                do_burn_menu!(PAIR_AMOUNT)
+                                do_burn_menu!(rules.pair_burn)
                             }
                         };
     
@@ -1368,10 +2230,10 @@ pub fn update_and_render(
                             }
                         }
     
-                        let pot_limit = match bundle.round {
-                            Round::One => bundle.pot.as_inner() / 2,
-                            Round::AfterOne => bundle.pot.as_inner(),
-                        };
+                        let pot_limit = rules.max_bet.limit(
+                            bundle.pot.as_inner(),
+                            bundle.round,
+                        );
     
                         // You can't bet more than the pot limit
                         if bundle.selection.bet.get() > pot_limit {
@@ -1392,6 +2254,7 @@ pub fn update_and_render(
                                         next_bundle!(
                                             new_bundle =
                                                 bundle.deck.clone(),
+                                                bundle.seen,
                                                 bundle.current,
                                                 bundle.player_count,
                                                 bundle.pot
@@ -1408,9 +2271,12 @@ pub fn update_and_render(
                                     if let Some(third) = bundle.deck.draw() {
                                         break third;
                                     } else {
-                                        bundle.deck = gen_deck(rng);
+                                        // The posts are still on the table.
+                                        bundle.seen = posts_bitset(bundle.posts);
+                                        bundle.deck = gen_deck_without(rng, bundle.seen);
                                     }
                                 };
+                                bundle.seen.set(third);
     
                                 state.table.state = Reveal {
                                     bundle: bundle.clone(),
@@ -1428,6 +2294,7 @@ pub fn update_and_render(
                                 next_bundle!(
                                     new_bundle =
                                         bundle.deck.clone(),
+                                        bundle.seen,
                                         bundle.current,
                                         bundle.player_count,
                                         bundle.pot
@@ -1457,7 +2324,12 @@ pub fn update_and_render(
                 MENU_RECT
             );
 
-            let outcome = calc_outcome(bundle.posts, *third, bundle.selection.ace);
+            let outcome = calc_outcome(
+                bundle.posts,
+                *third,
+                bundle.selection.ace,
+                bundle.selection.pair_call,
+            );
 
             {
                 let mut outcome_text = [0u8; 20];
@@ -1467,15 +2339,15 @@ pub fn update_and_render(
                     Loss => {
                         let _cant_actually_fail = write!(
                             &mut outcome_text[..],
-                            "player {} lost!",
-                            bundle.current
+                            "{} lost!",
+                            state.table.seats.standings.name(bundle.current),
                         );
                     }
                     Win => {
                         let _cant_actually_fail = write!(
                             &mut outcome_text[..],
-                            "player {} won!",
-                            bundle.current
+                            "{} won!",
+                            state.table.seats.standings.name(bundle.current),
                         );
                     }
                 }
@@ -1519,6 +2391,10 @@ pub fn update_and_render(
                         }.perform();
                     }
                     Win => {
+                        state.table.seats.standings.record_pot_won(
+                            bundle.current,
+                            bet.get(),
+                        );
                         MoneyMove {
                             from: &mut bundle.pot,
                             to: &mut state.table.seats.moneys[current_i],
@@ -1527,21 +2403,23 @@ pub fn update_and_render(
                     }
                 }
 
+                let player_count = bundle.player_count;
+
+                re_ante_if_emptied(
+                    state.table.seats.rules.re_ante,
+                    &mut state.table.seats.moneys[..player_count.usize()],
+                    &mut bundle.pot,
+                );
+
                 if bundle.pot == 0 {
-                    // TODO show a winner screen with more winner info.
-                    if state.table.seats.personalities[current_i].is_none() {
-                        println!("User wins!");
-                    } else {
-                        println!("Cpu player wins!");
-                    }
+                    state.table.seats.hands_played = state.table.seats.hands_played.saturating_add(1);
 
-                    group.speaker.request_sfx(SFX::CardPlace);
-                    state.table.state = <_>::default();
-                    cmd = ModeCmd::FinishedRound;
+                    finish_game!(group.speaker);
                 } else {
                     next_bundle!(
                         new_bundle =
                             bundle.deck.clone(),
+                            bundle.seen,
                             bundle.current,
                             bundle.player_count,
                             bundle.pot
@@ -1561,4 +2439,55 @@ pub fn update_and_render(
 
 
     cmd
-}
\ No newline at end of file
+}
+
+#[test]
+fn win_probability_counts_the_cards_seen_since_the_shuffle() {
+    // Ranks are 0 for ace up to 12 for king, and suits advance by 13.
+    let posts = [1, 3];
+
+    let mut seen = posts_bitset(posts);
+
+    assert_eq!(
+        win_probability(posts, HighLow::Low, None, seen),
+        EvalCount { win_count: 4, total: 50 }.probability()
+    );
+
+    // Three of the four cards between the posts, and one other card, have
+    // gone by already.
+    seen.set(2);
+    seen.set(15);
+    seen.set(28);
+    seen.set(27);
+
+    assert_eq!(
+        win_probability(posts, HighLow::Low, None, seen),
+        EvalCount { win_count: 1, total: 46 }.probability()
+    );
+}
+
+#[test]
+fn best_high_low_picks_the_wider_gap() {
+    let seen = CardBitset::default();
+
+    // Ace and five
+    assert_eq!(best_high_low([0, 4], seen), HighLow::High);
+    // Ace and king
+    assert_eq!(best_high_low([0, 12], seen), HighLow::Low);
+}
+
+#[test]
+fn called_pairs_need_the_third_card_on_the_called_side() {
+    // A pair of sevens.
+    let posts = [6, 19];
+
+    assert!(calc_outcome(posts, 7, Ace::Undecided, Some(HighLow::High)) == Outcome::Win);
+    assert!(calc_outcome(posts, 7, Ace::Undecided, Some(HighLow::Low)) == Outcome::Loss);
+    assert!(calc_outcome(posts, 5, Ace::Undecided, Some(HighLow::Low)) == Outcome::Win);
+    // Matching the pair loses either way.
+    assert!(calc_outcome(posts, 32, Ace::Undecided, Some(HighLow::High)) == Outcome::Loss);
+    assert!(calc_outcome(posts, 32, Ace::Undecided, Some(HighLow::Low)) == Outcome::Loss);
+
+    assert_eq!(best_pair_call([11, 24], HighLow::Low, posts_bitset([11, 24])), HighLow::Low);
+    assert_eq!(best_pair_call([2, 15], HighLow::Low, posts_bitset([2, 15])), HighLow::High);
+}
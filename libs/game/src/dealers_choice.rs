@@ -6,20 +6,54 @@ use std::io::Write;
 
 use xs::Xs;
 
-use crate::{acey_deucey, five_card_draw, holdem, PlayerCount, SubGame, SubGameState, SubGameBitset, OVERALL_MAX_PLAYER_COUNT};
-use crate::shared_game_types::{CpuPersonality, Personality, ModeCmd, SkipState};
-use crate::ui::{self, draw_money_in_rect, stack_money_text, ButtonSpec, Id::*, do_button, do_checkbox};
+use crate::{acey_deucey, five_card_draw, headless::{self, CpuTable}, holdem::{self, HoleCards}, omaha, seven_card_stud, PlayerCount, SubGame, SubGameState, SubGameBitset, OVERALL_MAX_PLAYER_COUNT};
+use crate::shared_game_types::{CpuPersonality, HandCount, Personality, ModeCmd, SkipState, Standings, StartingSeat, SeatEditor, SeatSetup, SeatSetups, default_seat_setups, gen_personalities, set_unedited_starting_money};
+use crate::strategy::SubGameView;
+use crate::ui::{self, StandingsCmd, draw_money_in_rect, stack_money_text, ButtonSpec, Id::*, do_button, do_checkbox};
 
 type Moneys = [Money; OVERALL_MAX_PLAYER_COUNT as usize];
+type Personalities = [Personality; OVERALL_MAX_PLAYER_COUNT as usize];
+type Seats = [StartingSeat; OVERALL_MAX_PLAYER_COUNT as usize];
+
+/// The players of a session, who each sub-game is dealt from.
+#[derive(Clone)]
+pub struct SessionSeats {
+    moneys: Moneys,
+    /// These are made once for the whole session, so a CPU player with a
+    /// random preset keeps the same personality from one sub-game to the next.
+    personalities: Personalities,
+    /// The seat of each player in the current sub-game, since only the
+    /// players with money left get dealt in.
+    dealt: Seats,
+    /// How many players were dealt into the current sub-game.
+    dealt_count: PlayerCount,
+}
 
 #[derive(Clone)]
 pub enum TableState {
-    Undealt { player_count: PlayerCount, starting_money: MoneyInner },
+    Undealt {
+        player_count: PlayerCount,
+        starting_money: MoneyInner,
+        seats: SeatSetups,
+        /// Set while the seat setup screen is open.
+        seat_editor: Option<SeatEditor>,
+    },
     Playing { 
         player_count: PlayerCount,
-        moneys: Moneys,
+        seats: SessionSeats,
+        /// The seat that chooses the next game.
+        dealer: StartingSeat,
+        skip: SkipState,
+        /// The index into the `RotationOrder` of the current game, or the next
+        /// one while choosing. Only used when there is a `Rotation`.
+        rotation_index: usize,
         sub_game_state: SubGameState,
+        /// The hands played across all the sub-games so far.
+        hands_played: HandCount,
+        standings: Standings,
     },
+    /// The session is over, and the final standings are being shown.
+    Finished { standings: Standings },
 }
 
 impl Default for TableState {
@@ -27,6 +61,8 @@ impl Default for TableState {
         Self::Undealt {
             player_count: <_>::default(),
             starting_money: 500,
+            seats: default_seat_setups(500),
+            seat_editor: None,
         }
     }
 }
@@ -38,6 +74,143 @@ pub struct Table {
     // TODO default to all selected
     // TODO make a button to toggle all bits
     pub chooseable_games: SubGameBitset,
+    pub rotation: Rotation,
+    pub rotation_order: RotationOrder,
+}
+
+/// Whether the selected games are played in a fixed order, like in a HORSE
+/// game, and if so, how long each one lasts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Rotation {
+    /// The dealer chooses between the selected games, once each orbit.
+    #[default]
+    Off,
+    /// Each game lasts one orbit, so every player deals once.
+    Orbit,
+    /// Each game lasts this many hands.
+    Hands(HandCount),
+}
+
+impl Rotation {
+    pub const PRESETS: [Self; 4] = [
+        Self::Off,
+        Self::Orbit,
+        Self::Hands(4),
+        Self::Hands(8),
+    ];
+
+    fn preset_index(self) -> usize {
+        Self::PRESETS.iter()
+            .position(|&preset| preset == self)
+            .unwrap_or(0)
+    }
+
+    pub fn next_up(self) -> Self {
+        let index = self.preset_index();
+        Self::PRESETS[if index == 0 { Self::PRESETS.len() - 1 } else { index - 1 }]
+    }
+
+    pub fn next_down(self) -> Self {
+        Self::PRESETS[(self.preset_index() + 1) % Self::PRESETS.len()]
+    }
+
+    /// The amount of hands to play before changing games, with `live_count`
+    /// players dealt in. Acey-deucey ignores this, and lasts until the pot is
+    /// empty.
+    fn hand_limit(self, live_count: PlayerCount) -> HandCount {
+        match self {
+            Self::Off
+            | Self::Orbit => HandCount::from(live_count),
+            Self::Hands(hands) => hands,
+        }
+    }
+}
+
+impl core::fmt::Display for Rotation {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Off => write!(f, "no"),
+            Self::Orbit => write!(f, "orbit"),
+            Self::Hands(hands) => write!(f, "{hands} hands"),
+        }
+    }
+}
+
+/// The order the selected games are played in, when there is a `Rotation`.
+/// Games that are not selected keep their place, but are skipped over.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RotationOrder([SubGame; SubGame::ALL.len()]);
+
+impl Default for RotationOrder {
+    fn default() -> Self {
+        Self(SubGame::ALL)
+    }
+}
+
+impl RotationOrder {
+    fn index_of(&self, game: SubGame) -> usize {
+        self.0.iter()
+            .position(|&g| g == game)
+            .unwrap_or(0)
+    }
+
+    fn first(&self) -> SubGame {
+        self.0[0]
+    }
+
+    /// The game before `game`, wrapping around to the last one.
+    fn wrapping_up(&self, game: SubGame) -> SubGame {
+        let index = self.index_of(game);
+        self.0[if index == 0 { self.0.len() - 1 } else { index - 1 }]
+    }
+
+    /// The game after `game`, wrapping around to the first one.
+    fn wrapping_down(&self, game: SubGame) -> SubGame {
+        self.0[(self.index_of(game) + 1) % self.0.len()]
+    }
+
+    /// Swap `game` with the game before it, wrapping around to the last one.
+    fn move_up(&mut self, game: SubGame) {
+        let index = self.index_of(game);
+        let other = self.index_of(self.wrapping_up(game));
+        self.0.swap(index, other);
+    }
+
+    /// The index and game of the first game at or after `start`, wrapping
+    /// around, that `is_playable`.
+    fn find_from(
+        &self,
+        start: usize,
+        is_playable: impl Fn(SubGame) -> bool,
+    ) -> Option<(usize, SubGame)> {
+        (0..self.0.len())
+            .map(|offset| (start + offset) % self.0.len())
+            .map(|index| (index, self.0[index]))
+            .find(|&(_, game)| is_playable(game))
+    }
+}
+
+#[test]
+fn rotation_order_moves_games_up_and_skips_missing_ones() {
+    use SubGame::*;
+
+    let mut order = RotationOrder::default();
+
+    order.move_up(Omaha);
+    order.move_up(Omaha);
+    assert_eq!(order.0, [Holdem, Omaha, AceyDeucey, FiveCardDraw, SevenCardStud]);
+
+    order.move_up(Holdem);
+    assert_eq!(order.0, [SevenCardStud, Omaha, AceyDeucey, FiveCardDraw, Holdem]);
+
+    assert_eq!(order.wrapping_up(SevenCardStud), Holdem);
+    assert_eq!(order.wrapping_down(Holdem), SevenCardStud);
+
+    let is_playable = |game| game == Holdem || game == FiveCardDraw;
+    assert_eq!(order.find_from(0, is_playable), Some((3, FiveCardDraw)));
+    assert_eq!(order.find_from(4, is_playable), Some((4, Holdem)));
+    assert_eq!(order.find_from(5, is_playable), Some((3, FiveCardDraw)));
+    assert_eq!(order.find_from(0, |_| false), None);
 }
 
 pub struct State<'state> {
@@ -58,6 +231,312 @@ fn clamp_player_count(
     }
 }
 
+/// The games in `sub_games` that can be dealt to `live_count` players, along
+/// with how many of them there are. The rest of the array is padding.
+fn legal_sub_games(
+    sub_games: SubGameBitset,
+    live_count: PlayerCount,
+) -> ([SubGame; SubGame::ALL.len()], usize) {
+    let mut output = [SubGame::default(); SubGame::ALL.len()];
+    let mut len = 0;
+
+    for game in sub_games.iter() {
+        if game.min_player_count() <= live_count
+        && live_count <= game.max_player_count() {
+            output[len] = game;
+            len += 1;
+        }
+    }
+
+    (output, len)
+}
+
+#[test]
+fn legal_sub_games_only_includes_selected_games_with_room_for_everyone() {
+    let all = SubGameBitset((-1i128) as _);
+
+    let (games, len) = legal_sub_games(all, 2);
+    assert_eq!(&games[..len], &SubGame::ALL);
+
+    let (games, len) = legal_sub_games(all, holdem::MAX_PLAYERS);
+    assert!(games[..len].contains(&SubGame::Holdem));
+    assert!(!games[..len].contains(&SubGame::AceyDeucey));
+
+    let (_, len) = legal_sub_games(all, 1);
+    assert_eq!(len, 0);
+
+    let acey_deucey_set = SubGameBitset(0b10);
+    let (games, len) = legal_sub_games(acey_deucey_set, 2);
+    assert_eq!(&games[..len], &[SubGame::AceyDeucey]);
+}
+
+/// Seat `player_count` players with the given money, set up as in `setups`,
+/// ready to choose the first game.
+fn new_session(
+    rng: &mut Xs,
+    player_count: PlayerCount,
+    moneys: Moneys,
+    setups: &[SeatSetup],
+) -> TableState {
+    let mut personalities: Personalities = <_>::default();
+    gen_personalities(rng, &mut personalities[..usize::from(player_count)], setups);
+
+    let dealer = xs::range(rng, 0..u32::from(player_count)) as StartingSeat;
+
+    TableState::Playing {
+        player_count,
+        standings: Standings::new(&moneys, setups),
+        seats: SessionSeats {
+            moneys,
+            personalities,
+            dealt: <_>::default(),
+            dealt_count: 0,
+        },
+        dealer,
+        skip: <_>::default(),
+        rotation_index: 0,
+        sub_game_state: <_>::default(),
+        hands_played: 0,
+    }
+}
+
+/// Deal a new table of `sub_game` to the players with money left, moving their
+/// money onto it. The names in `setups` are used, but the personalities are
+/// the session's. Returns `None` if the sub-game can't seat that many players.
+fn deal_sub_game(
+    rng: &mut Xs,
+    sub_game: SubGame,
+    seats: &mut SessionSeats,
+    setups: &[SeatSetup],
+    skip: SkipState,
+    rotation: Rotation,
+) -> Option<SubGameState> {
+    let mut live_count = 0;
+    for (seat, money) in seats.moneys.iter().enumerate() {
+        if *money != 0 {
+            seats.dealt[live_count] = seat as StartingSeat;
+            live_count += 1;
+        }
+    }
+    seats.dealt_count = live_count as PlayerCount;
+
+    macro_rules! deal {
+        ($path: ident $(, $extra_args: expr)* $(,)?) => ({
+            let player_count = $path::PlayerCount::try_from(live_count as u8).ok()?;
+
+            // The length comes from the table, which can have room for more
+            // players than the game allows, as Omaha's does.
+            let mut sub_game_moneys = core::array::from_fn(|_| Money::ZERO);
+            let mut sub_game_setups = default_seat_setups(0);
+            for i in 0..player_count.usize() {
+                let seat = usize::from(seats.dealt[i]);
+                sub_game_moneys[i] = seats.moneys[seat].take_all();
+                sub_game_setups[i] = setups[seat];
+            }
+
+            let mut table = $path::Table::selected(
+                rng,
+                player_count,
+                sub_game_moneys,
+                &sub_game_setups[..player_count.usize()],
+                $($extra_args),*
+            );
+
+            // The table made its own personalities from the setups, which
+            // would differ from the session's for random presets.
+            for i in 0..player_count.usize() {
+                table.seats.personalities[i] =
+                    seats.personalities[usize::from(seats.dealt[i])].clone();
+            }
+            table.seats.skip = skip;
+
+            table
+        })
+    }
+
+    if live_count > usize::from(sub_game.max_player_count()) {
+        return None
+    }
+
+    let hand_limit = Some(rotation.hand_limit(live_count as PlayerCount));
+
+    Some(match sub_game {
+        SubGame::Holdem => {
+            let mut table = deal!(holdem, <_>::default(), <_>::default(), <_>::default());
+            table.seats.hand_limit = hand_limit;
+            SubGameState::Holdem(table)
+        }
+        SubGame::AceyDeucey => {
+            SubGameState::AceyDeucey(deal!(acey_deucey, <_>::default()))
+        }
+        SubGame::FiveCardDraw => {
+            let mut table = deal!(
+                five_card_draw,
+                <_>::default(),
+                <_>::default(),
+                <_>::default(),
+            );
+            table.seats.hand_limit = hand_limit;
+            SubGameState::FiveCardDraw(table)
+        }
+        SubGame::Omaha => {
+            let mut table = deal!(
+                omaha,
+                omaha::Hand::DEFAULT_BETTING_STRUCTURE,
+                <_>::default(),
+                <_>::default(),
+            );
+            table.seats.hand_limit = hand_limit;
+            SubGameState::Omaha(table)
+        }
+        SubGame::SevenCardStud => {
+            let mut table = deal!(seven_card_stud, <_>::default(), <_>::default(), <_>::default());
+            table.seats.hand_limit = hand_limit;
+            SubGameState::SevenCardStud(table)
+        }
+    })
+}
+
+/// The game after the one at `index` in the rotation, skipping the ones that
+/// were not selected or can't seat `live_count` players. This is the current
+/// game if no other one can be played.
+fn next_in_rotation(
+    order: &RotationOrder,
+    index: usize,
+    sub_games: SubGameBitset,
+    live_count: PlayerCount,
+) -> SubGame {
+    let current = order.0[index % order.0.len()];
+    let (choices, choice_count) = legal_sub_games(sub_games, live_count);
+    let choices = &choices[..choice_count];
+    order.find_from(index + 1, |game| choices.contains(&game))
+        .map_or(current, |(_, game)| game)
+}
+
+#[test]
+fn next_in_rotation_skips_games_that_cannot_seat_everyone() {
+    use SubGame::*;
+
+    let order = RotationOrder::default();
+    let all = SubGameBitset((-1i128) as _);
+
+    let holdem_index = order.index_of(Holdem);
+    assert_eq!(next_in_rotation(&order, holdem_index, all, 2), order.wrapping_down(Holdem));
+
+    // Only hold'em can seat this many.
+    assert_eq!(next_in_rotation(&order, holdem_index, all, holdem::MAX_PLAYERS), Holdem);
+
+    let stud_index = order.index_of(SevenCardStud);
+    let stud_and_holdem = SubGameBitset(0b10001);
+    assert_eq!(next_in_rotation(&order, stud_index, stud_and_holdem, 2), Holdem);
+    assert_eq!(
+        next_in_rotation(&order, holdem_index, stud_and_holdem, seven_card_stud::MAX_PLAYERS + 1),
+        Holdem
+    );
+}
+
+/// Show which game of the rotation is being played, and which one is next.
+/// The next one can change if players run out of money before then.
+fn draw_rotation_banner(
+    commands: &mut Commands,
+    order: &RotationOrder,
+    index: usize,
+    sub_games: SubGameBitset,
+    live_count: PlayerCount,
+) {
+    let current = order.0[index % order.0.len()];
+    let next = next_in_rotation(order, index, sub_games, live_count);
+
+    let mut banner_text = [0u8; 64];
+    {
+        let mut unwritten = &mut banner_text[..];
+        for part in [b"now: " as &[u8], current.text(), b", next: ", next.text()] {
+            let _cant_actually_fail = unwritten.write_all(part);
+        }
+    }
+
+    let banner_rect = unscaled::Rect {
+        x: unscaled::X(0),
+        y: unscaled::Y(0),
+        w: command::WIDTH_W,
+        h: gfx::CHAR_H + SPACING_H * 2,
+    };
+
+    let xy = gfx::center_line_in_rect(
+        pre_nul_len(&banner_text),
+        banner_rect,
+    );
+    commands.print_chars(
+        &banner_text,
+        xy.x,
+        xy.y,
+        TEXT
+    );
+}
+
+/// Show a human dealer the games they can choose between, and return the one
+/// they chose, if they have chosen yet.
+fn choose_sub_game(
+    group: &mut ui::Group<'_, '_, '_>,
+    choices: &[SubGame],
+) -> Option<SubGame> {
+    let mut output = None;
+
+    group.commands.print_chars(
+        b"your deal. choose a game:",
+        unscaled::X(50),
+        unscaled::Y(25),
+        TEXT
+    );
+
+    let mut rect = unscaled::Rect {
+        x: unscaled::X(50),
+        y: unscaled::Y(50),
+        w: unscaled::W(150),
+        h: unscaled::H(30),
+    };
+
+    for &game in choices {
+        if do_button(
+            group,
+            ButtonSpec {
+                id: SubGameChoice(game),
+                rect,
+                text: game.text(),
+            }
+        ) {
+            output = Some(game);
+        }
+
+        rect.y += rect.h + SPACING_H;
+    }
+
+    let hot_i = choices.iter().position(|&game| group.ctx.hot == SubGameChoice(game));
+
+    match hot_i {
+        Some(i) => {
+            match group.input.dir_pressed_this_frame() {
+                Some(Dir::Up) => {
+                    let i = if i == 0 { choices.len() - 1 } else { i - 1 };
+                    group.ctx.set_next_hot(SubGameChoice(choices[i]));
+                },
+                Some(Dir::Down) => {
+                    let i = if i + 1 >= choices.len() { 0 } else { i + 1 };
+                    group.ctx.set_next_hot(SubGameChoice(choices[i]));
+                },
+                _ => {}
+            }
+        }
+        None => {
+            if let Some(&game) = choices.first() {
+                group.ctx.set_next_hot(SubGameChoice(game));
+            }
+        }
+    }
+
+    output
+}
+
 #[test]
 fn clamp_player_count_works_on_this_found_example() {
     let mut player_count = holdem::MAX_PLAYERS;
@@ -93,11 +572,42 @@ pub fn update_and_render(
     }
 
     let mut cmd = ModeCmd::NoOp;
+    let mut session_over = false;
 
     match &mut state.table.state {
+        Finished { standings } => {
+            match ui::do_standings(new_group!(), standings) {
+                StandingsCmd::NoOp => {},
+                StandingsCmd::PlayAgain => {
+                    let player_count = standings.player_count;
+
+                    let mut moneys = [0; OVERALL_MAX_PLAYER_COUNT as usize];
+                    for (money, standing) in moneys.iter_mut().zip(standings.seats.iter()) {
+                        *money = standing.starting_money;
+                    }
+                    let moneys = Money::array_from_inner_array(moneys);
+
+                    speaker.request_sfx(SFX::CardPlace);
+                    state.table.state = new_session(rng, player_count, moneys, &standings.setups);
+                },
+                StandingsCmd::BackToTitleScreen => {
+                    cmd = ModeCmd::BackToTitleScreen;
+                },
+            }
+        },
+        Undealt {
+            ref player_count,
+            ref mut seats,
+            ref mut seat_editor,
+            ..
+        } if seat_editor.is_some() => {
+            ui::do_seat_editor(new_group!(), seats, *player_count, seat_editor);
+        },
         Undealt {
             ref mut player_count,
             ref mut starting_money,
+            ref mut seats,
+            ref mut seat_editor,
         } => {
             let group = new_group!();
 
@@ -128,7 +638,7 @@ pub fn update_and_render(
                 let label_h = unscaled::W(50);
                 let line_h = gfx::CHAR_H + SPACING_H;
                 let mut y = game_set_rect.y;
-                for game in SubGame::ALL {
+                for game in state.table.rotation_order.0 {
                     if do_checkbox(
                         group,
                         game_set_rect.x, 
@@ -164,6 +674,15 @@ pub fn update_and_render(
 
                     y += line_h;
                 }
+
+                if state.table.rotation != Rotation::Off {
+                    group.commands.print_chars(
+                        b"b: move up",
+                        game_set_rect.x,
+                        y + SPACING_H,
+                        TEXT
+                    );
+                }
             }
 
             let player_count_rect = unscaled::Rect {
@@ -172,7 +691,7 @@ pub fn update_and_render(
                 ..game_set_rect
             };
     
-            let mut player_count_text = [0 as u8; 20];
+            let mut player_count_text = [0u8; 20];
             {
                 use std::io::Write;
                 let _cant_actually_fail = write!(
@@ -215,6 +734,22 @@ pub fn update_and_render(
                 PlayerCountSelect,
             );
 
+            if do_button(
+                group,
+                ButtonSpec {
+                    id: EditSeats,
+                    rect: unscaled::Rect {
+                        x: player_count_rect.x,
+                        y: player_count_rect.y + player_count_rect.h,
+                        w: unscaled::W(50),
+                        h: unscaled::H(50),
+                    },
+                    text: b"seats",
+                }
+            ) {
+                *seat_editor = Some(SeatEditor::default());
+            }
+
             let starting_money_rect = unscaled::Rect {
                 x: player_count_rect.x + player_count_rect.w,
                 ..player_count_rect
@@ -228,6 +763,56 @@ pub fn update_and_render(
                 StartingMoneySelect,
             );
 
+            let rotation_rect = unscaled::Rect {
+                x: starting_money_rect.x + starting_money_rect.w,
+                ..starting_money_rect
+            };
+
+            {
+                let mut rotation_text = [0u8; 16];
+                let _cant_actually_fail = write!(
+                    &mut rotation_text[..],
+                    "{}",
+                    state.table.rotation,
+                );
+
+                let xy = gfx::center_line_in_rect(
+                    pre_nul_len(&rotation_text),
+                    rotation_rect,
+                );
+                group.commands.print_chars(
+                    &rotation_text,
+                    xy.x,
+                    xy.y,
+                    TEXT
+                );
+            }
+            {
+                let rotation_label: &[u8] = match state.table.rotation {
+                    Rotation::Off => b"rotation",
+                    Rotation::Orbit
+                    | Rotation::Hands(_) => b"per game",
+                };
+
+                let xy = gfx::center_line_in_rect(
+                    rotation_label.len() as _,
+                    rotation_rect,
+                );
+
+                group.commands.print_chars(
+                    rotation_label,
+                    xy.x,
+                    xy.y + gfx::CHAR_H,
+                    TEXT
+                );
+            }
+
+            ui::draw_quick_select(
+                group,
+                rotation_rect,
+                RotationSelect,
+            );
+
             let is_valid_to_submit = state.table.chooseable_games.len() > 1;
 
             if is_valid_to_submit 
@@ -236,7 +821,7 @@ pub fn update_and_render(
                 ButtonSpec {
                     id: Submit,
                     rect: unscaled::Rect {
-                        x: starting_money_rect.x + starting_money_rect.w,
+                        x: rotation_rect.x + rotation_rect.w,
                         y: unscaled::Y(100),
                         w: unscaled::W(50),
                         h: unscaled::H(100),
@@ -247,17 +832,13 @@ pub fn update_and_render(
                 let player_count = *player_count;
                 let mut moneys = [0; OVERALL_MAX_PLAYER_COUNT as usize];
                 for i in 0..usize::from(player_count) {
-                    moneys[i] = *starting_money;
+                    moneys[i] = seats[i].starting_money;
                 }
                 let moneys = Money::array_from_inner_array(moneys);
 
-                state.table.state = Playing {
-                    player_count,
-                    moneys,
-                    sub_game_state: <_>::default(),
-                };
+                state.table.state = new_session(rng, player_count, moneys, &seats[..]);
             } else {
-                let menu = [BackToTitleScreen, SubGameCheckbox(SubGame::default()), PlayerCountSelect, StartingMoneySelect, Submit];
+                let menu = [BackToTitleScreen, SubGameCheckbox(state.table.rotation_order.first()), PlayerCountSelect, EditSeats, StartingMoneySelect, RotationSelect, Submit];
 
                 match group.ctx.hot {
                     BackToTitleScreen => {
@@ -273,12 +854,19 @@ pub fn update_and_render(
                     }
                     SubGameCheckbox(game) => {
                         let menu_i = 1;
+
+                        let order = &mut state.table.rotation_order;
+                        if state.table.rotation != Rotation::Off
+                        && input.pressed_this_frame(Button::B) {
+                            order.move_up(game);
+                        }
+
                         match input.dir_pressed_this_frame() {
                             Some(Dir::Up) => {
-                                group.ctx.set_next_hot(SubGameCheckbox(game.wrapping_up()));
+                                group.ctx.set_next_hot(SubGameCheckbox(order.wrapping_up(game)));
                             },
                             Some(Dir::Down) => {
-                                group.ctx.set_next_hot(SubGameCheckbox(game.wrapping_down()));
+                                group.ctx.set_next_hot(SubGameCheckbox(order.wrapping_down(game)));
                             },
                             Some(Dir::Left) => {
                                 group.ctx.set_next_hot(menu[menu_i - 1]);
@@ -309,17 +897,51 @@ pub fn update_and_render(
 
                         clamp_player_count(player_count, state.table.chooseable_games);
                     }
-                    StartingMoneySelect => {
+                    EditSeats => {
                         let menu_i = 3;
+                        match input.dir_pressed_this_frame() {
+                            Some(Dir::Left) => {
+                                group.ctx.set_next_hot(menu[menu_i - 1]);
+                            }
+                            Some(Dir::Right) => {
+                                group.ctx.set_next_hot(menu[menu_i + 1]);
+                            }
+                            _ => {}
+                        }
+                    }
+                    StartingMoneySelect => {
+                        let menu_i = 4;
                         match input.dir_pressed_this_frame() {
                             Some(Dir::Up) => {
+                                let previous = *starting_money;
                                 *starting_money = starting_money.saturating_add(MIN_MONEY_UNIT.get());
+                                set_unedited_starting_money(seats, previous, *starting_money);
                             },
                             Some(Dir::Down) => {
+                                let previous = *starting_money;
                                 *starting_money = starting_money.saturating_sub(MIN_MONEY_UNIT.get());
                                 if *starting_money == 0 {
                                     *starting_money = MIN_MONEY_UNIT.get();
                                 }
+                                set_unedited_starting_money(seats, previous, *starting_money);
+                            },
+                            Some(Dir::Left) => {
+                                group.ctx.set_next_hot(menu[menu_i - 1]);
+                            }
+                            Some(Dir::Right) => {
+                                group.ctx.set_next_hot(menu[menu_i + 1]);
+                            }
+                            None => {}
+                        }
+                    }
+                    RotationSelect => {
+                        let menu_i = 5;
+                        match input.dir_pressed_this_frame() {
+                            Some(Dir::Up) => {
+                                state.table.rotation = state.table.rotation.next_up();
+                            },
+                            Some(Dir::Down) => {
+                                state.table.rotation = state.table.rotation.next_down();
                             },
                             Some(Dir::Left) => {
                                 group.ctx.set_next_hot(menu[menu_i - 1]);
@@ -349,162 +971,367 @@ pub fn update_and_render(
         }
         Playing { 
             ref mut player_count,
-            ref mut moneys,
+            ref mut seats,
+            ref mut dealer,
+            ref mut skip,
+            ref mut rotation_index,
             ref mut sub_game_state,
+            ref mut hands_played,
+            ref mut standings,
         } => {
             use SubGameState::*;
+
+            // Moves the money from each seat of a finished sub-game back to the
+            // seat that player sits in here. `$seat_of` maps a sub-game index to
+            // the index the player had when the sub-game was dealt.
+            macro_rules! finish_sub_game {
+                ($table: ident, $seat_of: expr) => {
+                    if cmd == ModeCmd::FinishedRound {
+                        let seat_of = $seat_of;
+                        for i in 0..$table.seats.moneys.len() {
+                            let seat = seats.dealt[usize::from(seat_of(&$table.seats, i))];
+                            MoneyMove {
+                                from: &mut $table.seats.moneys[i],
+                                to: &mut seats.moneys[usize::from(seat)],
+                                amount: NonZeroMoneyInner::MAX,
+                            }.perform();
+                        }
+
+                        // The sub-game's standings are indexed by the seats
+                        // the players were dealt into.
+                        for (i, standing) in $table.seats.standings.seats.iter().enumerate() {
+                            standings.record_pot_won(seats.dealt[i], standing.biggest_pot);
+                        }
+                        *hands_played = hands_played.saturating_add($table.seats.hands_played);
+                        standings.record_stacks(
+                            *hands_played,
+                            seats.moneys.iter()
+                                .enumerate()
+                                .map(|(seat, money)| (seat as StartingSeat, money.as_inner())),
+                        );
+
+                        *skip = $table.seats.skip;
+                        *dealer = (*dealer + 1) % *player_count;
+                        *rotation_index = (*rotation_index + 1) % SubGame::ALL.len();
+                        *sub_game_state = Choosing;
+                    }
+                }
+            }
+
             match sub_game_state {
                 Choosing => {
-                    // TODO actual choosing
-                    let sub_game = SubGame::Holdem;
-
-                    match sub_game {
-                        SubGame::Holdem => {
-                            let player_count: holdem::PlayerCount =
-                                match holdem::PlayerCount::try_from(*player_count) {
-                                    Ok(player_count) => player_count,
-                                    Err(()) => {
-                                        debug_assert!(
-                                            false
-                                        );
-                                        return ModeCmd::BackToTitleScreen;
-                                    }
-                                };
-
-                            let mut holdem_moneys = 
-                                [Money::ZERO; holdem::MAX_PLAYERS as usize];
-                            for i in 0..player_count.usize() {
-                                holdem_moneys[i] = moneys[i].take_all();
-                            }
+                    let seated = usize::from(*player_count);
 
-                            *sub_game_state = Holdem(
-                                holdem::Table::selected(
-                                    rng,
-                                    player_count,
-                                    holdem_moneys,
-                                )
-                            );
-                        }
-                        SubGame::AceyDeucey => {
-                            let player_count: acey_deucey::PlayerCount =
-                                match acey_deucey::PlayerCount::try_from(*player_count) {
-                                    Ok(player_count) => player_count,
-                                    Err(error) => {
-                                        debug_assert!(
-                                            false,
-                                            "{error}"
-                                        );
-                                        return ModeCmd::BackToTitleScreen;
-                                    }
-                                };
-
-                            let mut acey_deucey_moneys = 
-                                [Money::ZERO; acey_deucey::MAX_PLAYERS as usize];
-                            for i in 0..player_count.usize() {
-                                acey_deucey_moneys[i] = moneys[i].take_all();
-                            }
+                    let live_count = seats.moneys[..seated]
+                        .iter()
+                        .filter(|money| **money != 0)
+                        .count() as PlayerCount;
+
+                    let user_has_money = (0..seated)
+                        .any(|i| seats.moneys[i] != 0 && seats.personalities[i].is_none());
 
-                            *sub_game_state = AceyDeucey(
-                                acey_deucey::Table::selected(
-                                    rng,
-                                    player_count,
-                                    acey_deucey_moneys,
-                                )
-                            );
+                    skip.simulate_if_only_cpus_left(!user_has_money);
+
+                    let (choices, choice_count) = legal_sub_games(
+                        state.table.chooseable_games,
+                        live_count,
+                    );
+                    let choices = &choices[..choice_count];
+
+                    if choices.is_empty() {
+                        session_over = true;
+                    } else {
+                        // Players who have run out of money don't deal.
+                        while seats.moneys[usize::from(*dealer)] == 0 {
+                            *dealer = (*dealer + 1) % *player_count;
                         }
-                        SubGame::FiveCardDraw => {
-                            let player_count: five_card_draw::PlayerCount =
-                                match five_card_draw::PlayerCount::try_from(*player_count) {
-                                    Ok(player_count) => player_count,
-                                    Err(error) => {
-                                        debug_assert!(
-                                            false,
-                                            "{error}"
-                                        );
-                                        return ModeCmd::BackToTitleScreen;
-                                    }
-                                };
-
-                            let mut five_card_draw_moneys = 
-                                [Money::ZERO; five_card_draw::MAX_PLAYERS as usize];
-                            for i in 0..player_count.usize() {
-                                five_card_draw_moneys[i] = moneys[i].take_all();
+
+                        let view = SubGameView { choices };
+
+                        let chosen = if state.table.rotation != Rotation::Off {
+                            state.table.rotation_order
+                                .find_from(*rotation_index, |game| choices.contains(&game))
+                                .map(|(index, sub_game)| {
+                                    *rotation_index = index;
+                                    sub_game
+                                })
+                        } else {
+                            match &seats.personalities[usize::from(*dealer)] {
+                                Some(strategy) => Some(
+                                    view.legal_choice(strategy.sub_game(rng, &view))
+                                ),
+                                None => choose_sub_game(new_group!(), choices),
                             }
+                        };
 
-                            *sub_game_state = FiveCardDraw(
-                                five_card_draw::Table::selected(
-                                    rng,
-                                    player_count,
-                                    five_card_draw_moneys,
-                                )
-                            );
+                        if let Some(sub_game) = chosen {
+                            match deal_sub_game(
+                                rng,
+                                sub_game,
+                                seats,
+                                &standings.setups,
+                                *skip,
+                                state.table.rotation,
+                            ) {
+                                Some(new_state) => {
+                                    *sub_game_state = new_state;
+                                }
+                                None => {
+                                    debug_assert!(
+                                        false,
+                                        "{sub_game:?} could not seat {live_count} players"
+                                    );
+                                    return ModeCmd::BackToTitleScreen;
+                                }
+                            }
                         }
                     }
                 }
                 Holdem(ref mut table) => {
-                    cmd = holdem::update_and_render(
+                    cmd = headless::update_or_simulate(
                         commands,
-                        holdem::State {
-                            rng,
-                            ctx: state.ctx,
-                            table
-                        },
+                        rng,
+                        state.ctx,
+                        table,
                         input,
                         speaker,
                     );
 
-                    if cmd == ModeCmd::FinishedRound {
-                        for i in 0..usize::from(*player_count) {
-                            moneys[i] = table.seats.moneys[i].take_all();
-                        }
-
-                        *sub_game_state = Choosing;
-                    }
+                    finish_sub_game!(table, |seats: &holdem::Seats, i| seats.starting_seats[i]);
                 }
                 AceyDeucey(ref mut table) => {
-                    cmd = acey_deucey::update_and_render(
+                    cmd = headless::update_or_simulate(
                         commands,
-                        acey_deucey::State {
-                            rng,
-                            ctx: state.ctx,
-                            table
-                        },
+                        rng,
+                        state.ctx,
+                        table,
                         input,
                         speaker,
                     );
 
-                    if cmd == ModeCmd::FinishedRound {
-                        for i in 0..usize::from(*player_count) {
-                            moneys[i] = table.seats.moneys[i].take_all();
-                        }
-
-                        *sub_game_state = Choosing;
-                    }
+                    // Acey-deucey doesn't move players between seats.
+                    finish_sub_game!(table, |_: &acey_deucey::Seats, i| i as StartingSeat);
                 }
                 FiveCardDraw(ref mut table) => {
-                    cmd = five_card_draw::update_and_render(
+                    cmd = headless::update_or_simulate(
                         commands,
-                        five_card_draw::State {
-                            rng,
-                            ctx: state.ctx,
-                            table
-                        },
+                        rng,
+                        state.ctx,
+                        table,
                         input,
                         speaker,
                     );
 
-                    if cmd == ModeCmd::FinishedRound {
-                        for i in 0..usize::from(*player_count) {
-                            moneys[i] = table.seats.moneys[i].take_all();
-                        }
+                    finish_sub_game!(table, |seats: &five_card_draw::Seats, i| seats.starting_seats[i]);
+                }
+                Omaha(ref mut table) => {
+                    cmd = headless::update_or_simulate(
+                        commands,
+                        rng,
+                        state.ctx,
+                        table,
+                        input,
+                        speaker,
+                    );
 
-                        *sub_game_state = Choosing;
-                    }
+                    finish_sub_game!(table, |seats: &omaha::Seats, i| seats.starting_seats[i]);
+                }
+                SevenCardStud(ref mut table) => {
+                    cmd = headless::update_or_simulate(
+                        commands,
+                        rng,
+                        state.ctx,
+                        table,
+                        input,
+                        speaker,
+                    );
+
+                    finish_sub_game!(table, |seats: &seven_card_stud::Seats, i| seats.starting_seats[i]);
                 }
             }
+
+            if state.table.rotation != Rotation::Off
+            && !matches!(sub_game_state, Choosing) {
+                draw_rotation_banner(
+                    commands,
+                    &state.table.rotation_order,
+                    *rotation_index,
+                    state.table.chooseable_games,
+                    seats.dealt_count,
+                );
+            }
             
         },
     }
 
+    if session_over {
+        speaker.request_sfx(SFX::CardPlace);
+        if let Playing { ref mut standings, .. } = state.table.state {
+            let standings = core::mem::take(standings);
+            state.table.state = Finished { standings };
+        }
+    }
+
     cmd
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+const TEST_PLAYER_COUNT: PlayerCount = 4;
+#[cfg(test)]
+const TEST_STARTING_MONEY: MoneyInner = 500;
+
+/// A table of CPU players, one of each preset, that is ready to choose the
+/// first game.
+#[cfg(test)]
+fn cpu_table(chooseable_games: SubGameBitset, rotation: Rotation, rotation_order: RotationOrder) -> Table {
+    let mut moneys = [0; OVERALL_MAX_PLAYER_COUNT as usize];
+    let mut personalities: Personalities = <_>::default();
+    for i in 0..usize::from(TEST_PLAYER_COUNT) {
+        moneys[i] = TEST_STARTING_MONEY;
+        personalities[i] = Some(Box::new(
+            CpuPersonality::PRESETS[i % CpuPersonality::PRESETS.len()]
+        ));
+    }
+
+    let moneys = Money::array_from_inner_array(moneys);
+
+    let mut setups = default_seat_setups(TEST_STARTING_MONEY);
+    for setup in setups.iter_mut() {
+        setup.is_human = false;
+    }
+
+    Table {
+        state: TableState::Playing {
+            player_count: TEST_PLAYER_COUNT,
+            standings: Standings::new(&moneys, &setups),
+            seats: SessionSeats {
+                moneys,
+                personalities,
+                dealt: <_>::default(),
+                dealt_count: 0,
+            },
+            dealer: 0,
+            skip: <_>::default(),
+            rotation_index: 0,
+            sub_game_state: <_>::default(),
+            hands_played: 0,
+        },
+        chooseable_games,
+        rotation,
+        rotation_order,
+    }
+}
+
+/// Play until `round_count` rounds have finished, checking that no money goes
+/// missing between them. Returns the dealer and the game of each round.
+#[cfg(test)]
+fn play_cpu_rounds(
+    seed: xs::Seed,
+    table: &mut Table,
+    round_count: usize,
+) -> (Vec<StartingSeat>, Vec<SubGame>) {
+    use platform_types::{Button, Input};
+
+    let mut rng = xs::from_seed(seed);
+
+    let mut ctx = ui::Context::default();
+    let mut commands = Commands::default();
+    let mut speaker = Speaker::default();
+
+    let mut dealers = Vec::new();
+    let mut games = Vec::new();
+    let mut was_choosing = false;
+
+    for frame in 0..1_000_000 {
+        ctx.frame_init();
+        commands.clear();
+        speaker.clear();
+
+        // Press A every other frame, to get past any buttons that the CPU
+        // players wait on.
+        let input = if frame % 2 == 0 {
+            Input { gamepad: Button::A, previous_gamepad: <_>::default() }
+        } else {
+            Input { gamepad: <_>::default(), previous_gamepad: Button::A }
+        };
+
+        let TableState::Playing { ref seats, dealer, ref sub_game_state, .. } = table.state else {
+            panic!("The session ended early");
+        };
+
+        let is_choosing = matches!(sub_game_state, SubGameState::Choosing);
+        if is_choosing && !was_choosing {
+            let total: MoneyInner = seats.moneys.iter().map(|money| money.as_inner()).sum();
+            assert_eq!(total, TEST_STARTING_MONEY * MoneyInner::from(TEST_PLAYER_COUNT));
+
+            if games.len() >= round_count {
+                break
+            }
+            dealers.push(dealer);
+        }
+
+        if !is_choosing && was_choosing {
+            games.push(match sub_game_state {
+                SubGameState::Choosing => unreachable!(),
+                SubGameState::Holdem(_) => SubGame::Holdem,
+                SubGameState::AceyDeucey(_) => SubGame::AceyDeucey,
+                SubGameState::FiveCardDraw(_) => SubGame::FiveCardDraw,
+                SubGameState::Omaha(_) => SubGame::Omaha,
+                SubGameState::SevenCardStud(_) => SubGame::SevenCardStud,
+            });
+        }
+        was_choosing = is_choosing;
+
+        update_and_render(
+            &mut commands,
+            State {
+                rng: &mut rng,
+                ctx: &mut ctx,
+                table,
+            },
+            input,
+            &mut speaker,
+        );
+    }
+
+    assert_eq!(games.len(), round_count, "Not enough rounds finished");
+
+    (dealers, games)
+}
+
+#[test]
+fn cpu_dealers_switch_games_and_the_money_follows_the_players() {
+    let mut table = cpu_table(
+        SubGameBitset((-1i128) as _),
+        Rotation::Off,
+        <_>::default(),
+    );
+
+    let (dealers, games) = play_cpu_rounds([17; 16], &mut table, 4);
+
+    // The deal passes to the left, unless somebody went broke.
+    assert_ne!(dealers[0], dealers[1]);
+    assert_ne!(dealers[1], dealers[2]);
+    // The presets each prefer a different game.
+    assert!(games.iter().any(|&game| game != games[0]), "{games:?}");
+}
+
+#[test]
+fn rotations_play_the_selected_games_in_order() {
+    use SubGame::*;
+
+    let mut order = RotationOrder::default();
+    order.move_up(SevenCardStud);
+
+    let holdem_omaha_and_stud = SubGameBitset(0b11001);
+
+    let mut table = cpu_table(
+        holdem_omaha_and_stud,
+        Rotation::Hands(2),
+        order,
+    );
+
+    let (_, games) = play_cpu_rounds([18; 16], &mut table, 4);
+
+    assert_eq!(games, [Holdem, SevenCardStud, Omaha, Holdem]);
+}
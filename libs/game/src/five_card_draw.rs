@@ -2,7 +2,7 @@ use look_up::{
     five_card::{hand_win_probability},
 };
 use gfx::{card, pre_nul_len, Commands, SPACING_W, SPACING_H};
-use models::{Action, ActionKind, ActionSpec, AllowedKindMode, BetKind, Card, CardBitset, RoundOutcome, ALL_CARDS, INITIAL_ANTE_AMOUNT, MIN_MONEY_UNIT, Deck, Money, MoneyInner, MoneyMove, NonZeroMoney, NonZeroMoneyInner, Pot, PotAction, Rank, gen_action, gen_deck, get_rank, ranks};
+use models::{Action, ActionKind, BettingStructure, RaiseRange, ActionSpec, AllowedKindMode, BetKind, Card, CardBitset, RoundOutcome, ALL_CARDS, INITIAL_ANTE_AMOUNT, MIN_MONEY_UNIT, Deck, Money, MoneyInner, MoneyMove, NonZeroMoney, NonZeroMoneyInner, Pot, PotAction, Rank, WildCards, gen_action, gen_deck_from, gen_deck_without, get_rank, ranks};
 use platform_types::{Button, Dir, Input, PaletteIndex, Speaker, SFX, command, unscaled, TEXT};
 use probability::{EvalCount};
 use probability::{FIFTY_PERCENT, SEVENTY_FIVE_PERCENT, EIGHTY_SEVEN_POINT_FIVE_PERCENT, Probability};
@@ -11,8 +11,9 @@ use std::io::Write;
 
 use xs::Xs;
 
-use crate::shared_game_types::{CpuPersonality, Personality, ModeCmd, SkipState};
-use crate::ui::{self, draw_money_in_rect, stack_money_text, ButtonSpec, Id::*, do_button};
+use crate::shared_game_types::{HandCount, LevelSchedule, Levels, Personality, PlayerStats, ModeCmd, SkipState, Standings, StartingSeat, gen_personalities, HotSeat, SeatEditor, SeatSetup, SeatSetups, default_seat_setups, set_unedited_starting_money};
+use crate::strategy::{BettingView, Cards, DrawView, Strategy};
+use crate::ui::{self, StandingsCmd, draw_money_in_rect, stack_money_text, ButtonSpec, Id::*, do_button};
 
 pub const MIN_PLAYERS: u8 = 2;
 // At 9 players that's 45 of 52 cards at the start, so each player only gets to draw
@@ -22,8 +23,7 @@ pub const MAX_PLAYERS: u8 = 9;
 pub const HAND_LEN: u8 = 5;
 
 /// The index for a `Card` in a `Hand`.
-// TODO will we need this?
-//pub type CardIndex = u8;
+pub type CardIndex = u8;
 type Hand = [Card; HAND_LEN as usize];
 
 /// The index for a `Hand` in `Hands`, not for indexing into a `Hand`.
@@ -33,8 +33,8 @@ pub type HandsLen = u8;
 
 type Hands = [Hand; MAX_PLAYERS as usize];
 
-fn deal(rng: &mut Xs, player_count: PlayerCount) -> (Hands, Deck) {
-    let mut deck = gen_deck(rng);
+fn deal(rng: &mut Xs, player_count: PlayerCount, wilds: WildCards) -> (Hands, Deck) {
+    let mut deck = wilds.gen_deck(rng);
 
     let mut hands = Hands::default();
 
@@ -161,25 +161,287 @@ pub fn gen_hand_index(rng: &mut Xs, player_count: PlayerCount) -> HandIndex {
     xs::range(rng, 0..player_count.u8() as _) as HandIndex
 }
 
+/// What a player needs in their hand to make the first bet of a hand.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum OpeningRequirement {
+    #[default]
+    Any,
+    /// At least a pair of jacks. If nobody can open, or nobody wants to,
+    /// the hand is dealt again with the antes left in the pot.
+    JacksOrBetter,
+}
+
+impl OpeningRequirement {
+    pub const ALL: [Self; 2] = [
+        Self::Any,
+        Self::JacksOrBetter,
+    ];
+
+    pub fn can_open(self, hand: Hand, wilds: WildCards) -> bool {
+        use evaluate::Category;
+
+        match self {
+            Self::Any => true,
+            Self::JacksOrBetter => match evaluate::five_card_category_wild(hand, wilds) {
+                Category::HighCard => false,
+                Category::OnePair => {
+                    // `None` means five of a kind, which won't be categorized
+                    // as one pair, but is certainly good enough.
+                    let Some(hand) = evaluate::substitute_wilds(hand, wilds)
+                        else { return true };
+
+                    let mut counts = [0u8; models::RANK_COUNT as usize];
+                    for card in hand {
+                        counts[usize::from(get_rank(card))] += 1;
+                    }
+
+                    counts.iter()
+                        .enumerate()
+                        .any(|(rank, &count)| {
+                            count >= 2
+                            && (rank as Rank == ranks::ACE || rank as Rank >= ranks::JACK)
+                        })
+                },
+                _ => true,
+            },
+        }
+    }
+
+    /// A short name for the requirement, meant to be shown above "to open".
+    pub fn text(self) -> &'static [u8] {
+        match self {
+            Self::Any => b"any",
+            Self::JacksOrBetter => b"jacks+",
+        }
+    }
+
+    pub fn next_up(self) -> Self {
+        match self {
+            Self::Any => Self::JacksOrBetter,
+            Self::JacksOrBetter => Self::Any,
+        }
+    }
+
+    pub fn next_down(self) -> Self {
+        // With only two options, going either way is the same.
+        self.next_up()
+    }
+}
+
+/// Who acts first in the betting round after the draw.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum SecondRoundStart {
+    /// The player to the dealer's left, the same as before the draw.
+    #[default]
+    DealersLeft,
+    /// The last player to bet or raise before the draw.
+    LastAggressor,
+    /// The player who made the first bet before the draw.
+    Opener,
+}
+
+impl SecondRoundStart {
+    pub const ALL: [Self; 3] = [
+        Self::DealersLeft,
+        Self::LastAggressor,
+        Self::Opener,
+    ];
+
+    /// If nobody bet before the draw, this falls back to the dealer's left.
+    fn first_to_act(self, bundle: &StateBundle) -> HandIndex {
+        let dealers_left = dealers_left(bundle.dealer, bundle.player_count);
+        match self {
+            Self::DealersLeft => dealers_left,
+            Self::LastAggressor => bundle.last_aggressor.unwrap_or(dealers_left),
+            Self::Opener => bundle.opener.unwrap_or(dealers_left),
+        }
+    }
+
+    /// A short name for the option, meant to be shown above "bets 2nd".
+    pub fn text(self) -> &'static [u8] {
+        match self {
+            Self::DealersLeft => b"left",
+            Self::LastAggressor => b"raiser",
+            Self::Opener => b"opener",
+        }
+    }
+
+    pub fn next_up(self) -> Self {
+        match self {
+            Self::DealersLeft => Self::Opener,
+            Self::LastAggressor => Self::DealersLeft,
+            Self::Opener => Self::LastAggressor,
+        }
+    }
+
+    pub fn next_down(self) -> Self {
+        match self {
+            Self::DealersLeft => Self::LastAggressor,
+            Self::LastAggressor => Self::Opener,
+            Self::Opener => Self::DealersLeft,
+        }
+    }
+}
+
+/// The house rules the game is played with, past the betting structure.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Rules {
+    pub wilds: WildCards,
+    pub opening: OpeningRequirement,
+    pub second_round_start: SecondRoundStart,
+}
+
+fn dealers_left(dealer: HandIndex, player_count: PlayerCount) -> HandIndex {
+    if dealer + 1 >= player_count.u8() {
+        0
+    } else {
+        dealer + 1
+    }
+}
+
+#[test]
+fn jacks_or_better_is_needed_to_open_on_these_examples() {
+    use OpeningRequirement::*;
+
+    // A pair of tens.
+    let hand = [9, 22, 1, 15, 30];
+    assert!(Any.can_open(hand, WildCards::None));
+    assert!(!JacksOrBetter.can_open(hand, WildCards::None));
+
+    // A pair of jacks.
+    assert!(JacksOrBetter.can_open([10, 23, 1, 15, 30], WildCards::None));
+
+    // A pair of aces.
+    assert!(JacksOrBetter.can_open([0, 13, 1, 15, 30], WildCards::None));
+
+    // Two low pairs.
+    assert!(JacksOrBetter.can_open([1, 14, 2, 15, 30], WildCards::None));
+
+    // A deuce pairs up with the jack.
+    assert!(JacksOrBetter.can_open([1, 10, 3, 5, 30], WildCards::Deuces));
+    assert!(!JacksOrBetter.can_open([1, 10, 3, 5, 30], WildCards::None));
+
+    // A deuce can only make a pair of tens here.
+    assert!(!JacksOrBetter.can_open([1, 9, 3, 5, 33], WildCards::Deuces));
+}
+
 #[derive(Clone)]
 pub struct Seats {
     pub moneys: [Money; MAX_PLAYERS as usize],
     pub personalities: [Personality; MAX_PLAYERS as usize],
+    pub stats: [PlayerStats; MAX_PLAYERS as usize],
+    pub starting_seats: [StartingSeat; MAX_PLAYERS as usize],
     pub skip: SkipState,
-    // TODO Increase these as the game goes on {
+    pub hands_played: HandCount,
+    pub standings: Standings,
+    /// Which human has the device, for when more than one is playing.
+    pub hot_seat: HotSeat,
+    /// If set, the round finishes once this many hands have been played, even
+    /// if more than one player has money left. Dealer's choice uses this to
+    /// switch games.
+    pub hand_limit: Option<HandCount>,
+    pub betting_structure: BettingStructure,
+    pub rules: Rules,
+    pub levels: Levels,
+    /// This is set from `levels` between hands, so it doesn't change in the
+    /// middle of one.
     pub ante: NonZeroMoneyInner,
-    // }
 }
 
+/// The ante at the first level.
+const BASE_ANTE: NonZeroMoneyInner = MIN_MONEY_UNIT;
+
 impl Default for Seats {
     fn default() -> Self {
         Self {
             moneys: <_>::default(),
             personalities: <_>::default(),
+            stats: <_>::default(),
+            starting_seats: core::array::from_fn(|i| i as StartingSeat),
             skip: <_>::default(),
-            ante: MIN_MONEY_UNIT,
+            hands_played: 0,
+            standings: <_>::default(),
+            hot_seat: <_>::default(),
+            hand_limit: None,
+            betting_structure: <_>::default(),
+            rules: <_>::default(),
+            levels: <_>::default(),
+            ante: BASE_ANTE,
+        }
+    }
+}
+
+impl Seats {
+    /// Set the ante to the one for the current level.
+    fn update_ante(&mut self) {
+        self.ante = self.levels.scale(BASE_ANTE);
+    }
+
+    /// The smallest bet allowed in a betting round. Under fixed-limit, every
+    /// bet is this size, and the round after the draw uses a big bet, which is
+    /// twice the size of the small bet used before the draw.
+    pub fn bet_size(&self, is_first_round: bool) -> NonZeroMoneyInner {
+        match self.betting_structure {
+            BettingStructure::FixedLimit if !is_first_round => {
+                self.ante.saturating_mul(NonZeroMoneyInner::MIN.saturating_add(1))
+            },
+            _ => self.ante,
         }
     }
+
+    /// The range the player at `current` can raise within, if any, and which
+    /// kinds of action they can take.
+    fn betting_options(
+        &self,
+        pot: &Pot,
+        current: HandIndex,
+        hand: Hand,
+        is_first_round: bool,
+        has_opener: bool,
+    ) -> (Option<RaiseRange>, AllowedKindMode) {
+        let money = self.moneys[usize::from(current)].as_inner();
+
+        // The amount extra needed to call
+        let call_remainder = pot.call_amount().saturating_sub(
+            pot.amount_for(current)
+        );
+        // The amount that would be leftover if the player was to call
+        let call_leftover = money.checked_sub(call_remainder);
+
+        // Until somebody opens, only players whose hands meet the
+        // opening requirement can bet.
+        let can_open = !is_first_round
+            || has_opener
+            || self.rules.opening.can_open(hand, self.rules.wilds);
+
+        let raise_range: Option<RaiseRange> = if can_open {
+            pot.raise_range(
+                current,
+                money,
+                self.betting_structure,
+                self.bet_size(is_first_round),
+            )
+        } else {
+            None
+        };
+
+        let allowed_kind_mode =
+            if raise_range.is_none() && call_remainder > 0 {
+                AllowedKindMode::NoRaising
+            } else if call_remainder > 0 {
+                AllowedKindMode::All
+            } else if call_leftover.unwrap_or(0) > 0 {
+                if can_open {
+                    AllowedKindMode::NoFolding
+                } else {
+                    AllowedKindMode::NoRaising
+                }
+            } else {
+                AllowedKindMode::AllIn
+            };
+
+        (raise_range, allowed_kind_mode)
+    }
 }
 
 #[derive(Clone, Default)]
@@ -197,45 +459,198 @@ pub struct StateBundle {
     pub pot: Pot,
     pub player_count: PlayerCount,
     pub selection: MenuSelection,
+    /// The player who made the first bet of the hand, if anyone has yet.
+    pub opener: Option<HandIndex>,
+    /// The player who made the latest bet or raise of the hand, if anyone
+    /// has yet.
+    pub last_aggressor: Option<HandIndex>,
 }
 
+/// Players must keep at least one card.
+pub const MAX_DISCARDS: u8 = HAND_LEN - 1;
+
 type DrawBits = u8;
-#[derive(Clone, Copy, Debug, Default)]
+/// Which cards in a hand to discard. The bit at `i` being set means the card at
+/// `CardIndex` `i` is discarded.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub struct DrawBitset(DrawBits);
 
 compile_time_assert!{
     DrawBits::BITS >= HAND_LEN as u32
 }
 
+compile_time_assert!{
+    equity::five_card::DISCARD_MASK_COUNT == 1 << HAND_LEN
+}
+
+impl DrawBitset {
+    pub fn len(&self) -> u8 {
+        self.0.count_ones() as u8
+    }
+
+    pub fn contains(&self, index: CardIndex) -> bool {
+        if index >= HAND_LEN { return false }
+        self.0 & (1 << index) != 0
+    }
+
+    /// Does nothing if toggling would discard more than `MAX_DISCARDS` cards.
+    pub fn toggle(&mut self, index: CardIndex) {
+        if index >= HAND_LEN { return }
+        let toggled = Self(self.0 ^ (1 << index));
+        if toggled.len() <= MAX_DISCARDS {
+            *self = toggled;
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum DrawAction {
+    StandPat,
+    Discard(DrawBitset),
+}
+
+fn cpu_draw_action(
+    rng: &mut Xs,
+    strategy: &dyn Strategy,
+    hand: Hand,
+    wilds: WildCards,
+) -> DrawAction {
+    let discards = DrawBitset(
+        strategy.discards(rng, &DrawView { hand, max_discards: MAX_DISCARDS, wilds })
+        & ((1 << HAND_LEN) - 1)
+    );
+
+    if discards.len() == 0 || discards.len() > MAX_DISCARDS {
+        DrawAction::StandPat
+    } else {
+        DrawAction::Discard(discards)
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct DrawingState {
     current: Option<HandIndex>,
-    // TODO track when to stop, drawing specific UI state etc. here.
+    /// The cards a human player has selected to discard so far.
+    selection: DrawBitset,
+    has_drawn: bool,
+    /// The cards that have been discarded so far. These are shuffled into a
+    /// new deck if the deck runs out.
+    discards: CardBitset,
 }
 
 impl DrawingState {
     fn new(current: HandIndex) -> Self {
         Self {
             current: Some(current),
+            selection: <_>::default(),
+            has_drawn: false,
+            discards: <_>::default(),
         }
     }
 
     fn ready_to_advance_to_next_player(&self) -> bool {
-        false
+        self.has_drawn
     }
 
     fn ready_to_ask_next(&self) -> bool {
-        self.animations_done()
+        !self.has_drawn && self.animations_done()
     }
 
     fn animations_done(&self) -> bool {
         true
     }
+
+    fn perform(
+        &mut self,
+        rng: &mut Xs,
+        deck: &mut Deck,
+        hand: &mut Hand,
+        action: DrawAction,
+    ) {
+        if let DrawAction::Discard(discards) = action {
+            let mut discarded = CardBitset::default();
+
+            for i in 0..HAND_LEN {
+                if !discards.contains(i) {
+                    continue
+                }
+
+                let replacement = match deck.draw() {
+                    Some(card) => Some(card),
+                    None => {
+                        // Shuffle the previous discards into a new deck.
+                        *deck = gen_deck_from(rng, self.discards);
+                        self.discards = <_>::default();
+
+                        deck.draw()
+                    },
+                };
+
+                // If there are no cards left at all, the player keeps the card.
+                if let Some(replacement) = replacement {
+                    let card = &mut hand[usize::from(i)];
+                    discarded.set(*card);
+                    *card = replacement;
+                }
+            }
+
+            for card in discarded.iter() {
+                self.discards.set(card);
+            }
+        }
+
+        self.has_drawn = true;
+    }
+}
+
+#[test]
+fn drawing_reuses_discards_when_the_deck_runs_out() {
+    let mut rng = xs::from_seed([5; 16]);
+
+    let mut drawing_state = DrawingState::new(0);
+    for card in [10, 11, 12] {
+        drawing_state.discards.set(card);
+    }
+
+    let mut deck = gen_deck_without(&mut rng, CardBitset::full());
+    let mut hand = [0, 1, 2, 3, 4];
+
+    let mut discards = DrawBitset::default();
+    discards.toggle(0);
+    discards.toggle(1);
+    discards.toggle(2);
+
+    drawing_state.perform(
+        &mut rng,
+        &mut deck,
+        &mut hand,
+        DrawAction::Discard(discards),
+    );
+
+    let mut new_cards = [hand[0], hand[1], hand[2]];
+    new_cards.sort();
+    assert_eq!(new_cards, [10, 11, 12]);
+    assert_eq!(&hand[3..], &[3, 4]);
+
+    for card in [0, 1, 2] {
+        assert!(drawing_state.discards.contains(card));
+    }
+    assert_eq!(drawing_state.discards.len(), 3);
+    assert!(drawing_state.ready_to_advance_to_next_player());
 }
 
 #[derive(Clone)]
 pub enum TableState {
-    Undealt { player_count: PlayerCount, starting_money: MoneyInner },
+    Undealt {
+        player_count: PlayerCount,
+        starting_money: MoneyInner,
+        seats: SeatSetups,
+        /// Set while the seat setup screen is open.
+        seat_editor: Option<SeatEditor>,
+        betting_structure: BettingStructure,
+        rules: Rules,
+        level_schedule: LevelSchedule,
+    },
     FirstRound {
         bundle: StateBundle,
     },
@@ -249,6 +664,8 @@ pub enum TableState {
     Showdown {
         bundle: StateBundle,
     },
+    /// The game is over, and the final standings are being shown.
+    Finished,
 }
 
 impl Default for TableState {
@@ -256,6 +673,11 @@ impl Default for TableState {
         Self::Undealt {
             player_count: <_>::default(),
             starting_money: 500,
+            seats: default_seat_setups(500),
+            seat_editor: None,
+            betting_structure: <_>::default(),
+            rules: <_>::default(),
+            level_schedule: <_>::default(),
         }
     }
 }
@@ -271,82 +693,103 @@ impl Table {
         rng: &mut Xs,
         player_count: PlayerCount,
         mut moneys: [Money; MAX_PLAYERS as usize],
+        setups: &[SeatSetup],
+        betting_structure: BettingStructure,
+        rules: Rules,
+        level_schedule: LevelSchedule,
     ) -> Self {
-        let mut pot: Pot = Pot::with_capacity(player_count.u8(), 16);
-
-        let ante = MIN_MONEY_UNIT;
-
-        for i in 0..player_count.u8() {
-            pot.push_bet_of_kind(
-                i, 
-                PotAction::Bet(
-                    moneys[usize::from(i)]
-                        .take(ante.get())
-                ),
-                BetKind::Ante,
-            );
-        }
+        let ante = BASE_ANTE;
 
         let mut personalities: [Personality; MAX_PLAYERS as usize] = <_>::default();
+        gen_personalities(rng, &mut personalities[..player_count.usize()], setups);
 
-        personalities[0] = None;
-        // TODO Make each element of this array user selectable too.
-        // Start at 1 to make the first player user controlled
-        for i in 1..player_count.usize() {
-            personalities[i] = Some(CpuPersonality{});
-        }
-
-        let (hands, deck) = deal(rng, player_count);
-
-        let selected = gen_hand_index(rng, player_count);
-
-        let dealer = if moneys[usize::from(selected)] == 0 {
-            let mut index = selected + 1;
-            while {
-                if index >= player_count.u8() {
-                    index = 0;
-                }
-
-                index != selected
-                && moneys[usize::from(index)] == 0
-            } {
-                index += 1;
-            }
+        let standings = Standings::new(&moneys, setups);
 
-            index
-        } else {
-            selected
-        };
+        let bundle = deal_bundle(rng, player_count, &mut moneys, ante, rules.wilds);
 
-        let current = if dealer + 1 >= player_count.u8() {
-            0
-        } else {
-            dealer + 1
-        };
+        let mut stats: [PlayerStats; MAX_PLAYERS as usize] = <_>::default();
+        for stats in &mut stats[..player_count.usize()] {
+            stats.start_hand();
+        }
 
         // TODO handle case where the pot has all the money in it!
         Self {
             seats: Seats {
+                standings,
                 moneys,
                 personalities,
-                skip: <_>::default(),
+                stats,
+                betting_structure,
+                rules,
+                levels: Levels::new(level_schedule),
                 ante,
+                ..<_>::default()
             },
             state: TableState::FirstRound {
-                bundle: StateBundle {
-                    deck,
-                    hands,
-                    dealer,
-                    current,
-                    pot,
-                    player_count,
-                    selection: <_>::default(),
-                },
+                bundle,
             },
         }
     }
 }
 
+/// Collect the antes, and deal out a new hand.
+fn deal_bundle(
+    rng: &mut Xs,
+    player_count: PlayerCount,
+    moneys: &mut [Money; MAX_PLAYERS as usize],
+    ante: NonZeroMoneyInner,
+    wilds: WildCards,
+) -> StateBundle {
+    let mut pot: Pot = Pot::with_capacity(player_count.u8(), 16);
+
+    for i in 0..player_count.u8() {
+        pot.push_bet_of_kind(
+            i, 
+            PotAction::Bet(
+                moneys[usize::from(i)]
+                    .take(ante.get())
+            ),
+            BetKind::Ante,
+        );
+    }
+
+    let (hands, deck) = deal(rng, player_count, wilds);
+
+    let selected = gen_hand_index(rng, player_count);
+
+    let dealer = if moneys[usize::from(selected)] == 0 {
+        let mut index = selected + 1;
+        while {
+            if index >= player_count.u8() {
+                index = 0;
+            }
+
+            index != selected
+            && moneys[usize::from(index)] == 0
+        } {
+            index += 1;
+        }
+
+        index
+    } else {
+        selected
+    };
+
+    let current = dealers_left(dealer, player_count);
+
+    StateBundle {
+        deck,
+        hands,
+        dealer,
+        current,
+        pot,
+        player_count,
+        selection: <_>::default(),
+        opener: None,
+        last_aggressor: None,
+    }
+}
+
 pub struct State<'state> {
     pub rng: &'state mut Xs,
     pub ctx: &'state mut ui::Context,
@@ -378,7 +821,7 @@ pub fn update_and_render(
     let mut cmd = ModeCmd::NoOp;
 
     macro_rules! do_five_card_draw {
-        ($group: ident $(,)? $bundle: ident, $drawing_state_opt: expr) => ({
+        ($group: ident $(,)? $bundle: ident, $drawing_state_opt: expr, $is_first_round: expr) => ({
             let group = $group;
             let hands = &$bundle.hands;
             let current = $bundle.current;
@@ -386,6 +829,12 @@ pub fn update_and_render(
             let player_count = $bundle.player_count;
             let pot = &mut $bundle.pot;
             let drawing_state_opt: Option<&mut DrawingState> = $drawing_state_opt;
+            let is_drawing = drawing_state_opt.is_some();
+            // While drawing, the player drawing is not the player to bet next.
+            let acting_i = drawing_state_opt.as_ref()
+                .and_then(|drawing_state| drawing_state.current)
+                .map(usize::from)
+                .unwrap_or(current_i);
 
             use platform_types::unscaled::xy;
             // TODO Avoid overlapping hands
@@ -422,7 +871,7 @@ pub fn update_and_render(
                 for _ in 0..hands_len {
                     let at = coords[i];
 
-                    if current_i == i {
+                    if acting_i == i {
                         group.commands.draw_five_card_hand_underlight(
                             at.x,
                             at.y
@@ -433,6 +882,12 @@ pub fn update_and_render(
                 }
             }
 
+            // Only the human who is acting, and so has the device, gets to see
+            // their own cards.
+            let acting_human = state.table.seats.personalities[acting_i]
+                .is_none()
+                .then_some(acting_i as HandIndex);
+
             {
                 let mut i: HandIndex = 0;
                 for hand in &hands[0..(hands_len as usize)] {
@@ -442,11 +897,10 @@ pub fn update_and_render(
                         FiveCardDrawHand(index) => index == i,
                         FiveCardDrawMenu(_) => true,
                         _ => false,
-                    } && current == i;
+                    } && acting_human == Some(i);
 
                     use gfx::FiveCardFacing;
-                    let facing = if show_if_player_owned
-                    && state.table.seats.personalities[current_i].is_none() {
+                    let facing = if show_if_player_owned {
                         FiveCardFacing::Up(*hand)
                     } else {
                         FiveCardFacing::Down
@@ -462,7 +916,9 @@ pub fn update_and_render(
                 }
             }
 
-            {
+            // While drawing, the `FiveCardDrawHand` ids refer to cards in the
+            // drawing player's hand, so there are no hands to select.
+            if !is_drawing {
                 let mut i = 0;
                 for _ in hands.iter() {
                     let at = coords[i];
@@ -539,6 +995,13 @@ pub fn update_and_render(
                 // which would be some function of MAX_PLAYERS. Exactly MAX_PLAYERS?
             }
 
+            ui::draw_levels(
+                group.commands,
+                &state.table.seats.levels,
+                POT_BASE_X + SPACING_W,
+                POT_BASE_Y,
+            );
+
             const ACTION_KIND: ui::FiveCardDrawMenuId = 0;
             const MONEY_AMOUNT: ui::FiveCardDrawMenuId = 1;
             const SUBMIT: ui::FiveCardDrawMenuId = 2;
@@ -546,7 +1009,6 @@ pub fn update_and_render(
 
             // The total bet needed to call
             let call_amount = pot.call_amount();
-            let minimum_raise_total = call_amount + MIN_MONEY_UNIT.get();
             // The amount extra needed to call
             let call_remainder = call_amount.saturating_sub(
                 pot.amount_for(current)
@@ -556,17 +1018,20 @@ pub fn update_and_render(
                 .as_inner()
                 .checked_sub(call_remainder);
 
-            let allowed_kind_mode =
-                if call_remainder > 0 {
-                    AllowedKindMode::All
-                } else if call_leftover.unwrap_or(0) > 0 {
-                    AllowedKindMode::NoFolding
-                } else {
-                    AllowedKindMode::AllIn
-                };
+            let (raise_range, allowed_kind_mode) = state.table.seats.betting_options(
+                pot,
+                current,
+                hands[current_i],
+                $is_first_round,
+                $bundle.opener.is_some(),
+            );
 
             let mut i = 0;
             for _ in 0..hands_len {
+                if is_drawing {
+                    break
+                }
+
                 match group.ctx.hot {
                     FiveCardDrawHand(mut index) if usize::from(index) == i => {
                         stack_money_text!(money_text = state.table.seats.moneys[i]);
@@ -625,12 +1090,8 @@ pub fn update_and_render(
                 i += 1;
             }
 
-            if $bundle.selection.bet < minimum_raise_total {
-                $bundle.selection.bet = minimum_raise_total;
-            }
-            if $bundle.selection.bet > state.table.seats.moneys[current_i] {
-                $bundle.selection.bet = state.table.seats.moneys[current_i]
-                    .as_inner();
+            if let Some(range) = raise_range {
+                $bundle.selection.bet = range.clamp($bundle.selection.bet);
             }
 
             if let Some(drawing_state) = drawing_state_opt {
@@ -641,25 +1102,132 @@ pub fn update_and_render(
                 && drawing_state.animations_done() {
                     RoundOutcome::AdvanceToNext
                 } else {
-                    enum DrawAction {
-                        Zero,
-                        One,
-                        Two,
-                        Three,
-                        Four,
-                    }
-
-                    let action_opt: Option<()> = match drawing_state.current {
+                    let action_opt: Option<DrawAction> = match drawing_state.current {
                         Some(c) if drawing_state.ready_to_ask_next() => {
-                            match &state.table.seats.personalities[usize::from(c)] {
-                                Some(_) => {
-                                    // TODO Base choice of action off of personality
+                            let c_i = usize::from(c);
+                            if pot.has_folded(c) {
+                                Some(DrawAction::StandPat)
+                            } else {
+                                match &state.table.seats.personalities[c_i] {
+                                    Some(strategy) => {
+                                        Some(cpu_draw_action(
+                                            rng,
+                                            &**strategy,
+                                            hands[c_i],
+                                            state.table.seats.rules.wilds,
+                                        ))
+                                    },
+                                    None => {
+                                        const DRAW_BASE_X: unscaled::X = unscaled::x_const_add_w(
+                                            HAND_DESC_RECT.x,
+                                            SPACING_W,
+                                        );
+                                        const DRAW_Y: unscaled::Y = unscaled::y_const_add_h(
+                                            HAND_DESC_RECT.y,
+                                            unscaled::h_const_div(
+                                                unscaled::h_const_sub(
+                                                    HAND_DESC_RECT.h,
+                                                    gfx::card::HEIGHT,
+                                                ),
+                                                2
+                                            ),
+                                        );
+                                        const CARD_ADVANCE: unscaled::W = unscaled::w_const_add(
+                                            gfx::card::WIDTH,
+                                            SPACING_W,
+                                        );
 
-                                    todo!()
-                                    //Some()
-                                },
-                                None => {
-                                    todo!()
+                                        group.commands.draw_nine_slice(
+                                            gfx::NineSlice::Button,
+                                            HAND_DESC_RECT
+                                        );
+
+                                        let mut x = DRAW_BASE_X;
+                                        for (card_i, card) in hands[c_i].iter().enumerate() {
+                                            let card_i = card_i as CardIndex;
+
+                                            if drawing_state.selection.contains(card_i) {
+                                                group.commands.draw_card_back(x, DRAW_Y);
+                                            } else {
+                                                group.commands.draw_card(*card, x, DRAW_Y);
+                                            }
+
+                                            if group.ctx.hot == FiveCardDrawHand(card_i) {
+                                                group.commands.draw_selected(
+                                                    x + (gfx::card::WIDTH / 2),
+                                                    DRAW_Y,
+                                                );
+                                            }
+
+                                            x += CARD_ADVANCE;
+                                        }
+
+                                        let submit_text: &[u8] = if drawing_state.selection.len() == 0 {
+                                            b"stand pat"
+                                        } else {
+                                            b"draw"
+                                        };
+
+                                        let draw_action_opt = if do_button(
+                                            group,
+                                            ButtonSpec {
+                                                id: FiveCardDrawMenu(SUBMIT),
+                                                rect: unscaled::Rect {
+                                                    x: x + SPACING_W,
+                                                    y: HAND_DESC_RECT.y + SPACING_H,
+                                                    w: unscaled::W(70),
+                                                    h: HAND_DESC_RECT.h - SPACING_H * 2,
+                                                },
+                                                text: submit_text,
+                                            }
+                                        ) {
+                                            Some(if drawing_state.selection.len() == 0 {
+                                                DrawAction::StandPat
+                                            } else {
+                                                DrawAction::Discard(drawing_state.selection)
+                                            })
+                                        } else {
+                                            None
+                                        };
+
+                                        match group.ctx.hot {
+                                            FiveCardDrawHand(card_i) => {
+                                                if group.input.pressed_this_frame(Button::LEFT) {
+                                                    match card_i.checked_sub(1) {
+                                                        Some(new_i) => {
+                                                            group.ctx.set_next_hot(FiveCardDrawHand(new_i));
+                                                        },
+                                                        None => {
+                                                            group.ctx.set_next_hot(FiveCardDrawMenu(SUBMIT));
+                                                        },
+                                                    }
+                                                } else if group.input.pressed_this_frame(Button::RIGHT) {
+                                                    if card_i + 1 >= HAND_LEN {
+                                                        group.ctx.set_next_hot(FiveCardDrawMenu(SUBMIT));
+                                                    } else {
+                                                        group.ctx.set_next_hot(FiveCardDrawHand(card_i + 1));
+                                                    }
+                                                } else {
+                                                    if group.input.pressed_this_frame(Button::A) {
+                                                        drawing_state.selection.toggle(card_i);
+                                                    }
+                                                    group.ctx.set_next_hot(FiveCardDrawHand(card_i));
+                                                }
+                                            },
+                                            FiveCardDrawMenu(SUBMIT) => {
+                                                if group.input.pressed_this_frame(Button::LEFT) {
+                                                    group.ctx.set_next_hot(FiveCardDrawHand(HAND_LEN - 1));
+                                                } else if group.input.pressed_this_frame(Button::RIGHT) {
+                                                    group.ctx.set_next_hot(FiveCardDrawHand(0));
+                                                }
+                                            },
+                                            _ => {
+                                                group.ctx.set_next_hot(FiveCardDrawHand(0));
+                                            },
+                                        }
+
+                                        draw_action_opt
+                                    }
                                 }
                             }
                         }
@@ -669,14 +1237,19 @@ pub fn update_and_render(
                             None
                         }
                     };
-                    // TODO
-                    //drawing_state.render(group);
-                    // Advance animations
-                    //{
-                        //
-                    //}
+
+                    if let (Some(action), Some(c)) = (action_opt, drawing_state.current) {
+                        drawing_state.perform(
+                            rng,
+                            &mut $bundle.deck,
+                            &mut $bundle.hands[usize::from(c)],
+                            action,
+                        );
+                    }
 
                     if drawing_state.ready_to_advance_to_next_player() {
+                        drawing_state.has_drawn = false;
+                        drawing_state.selection = <_>::default();
                         drawing_state.current = match drawing_state.current {
                             Some(c) => {
                                 let mut next_index = c + 1;
@@ -696,8 +1269,16 @@ pub fn update_and_render(
                     RoundOutcome::Undetermined
                 }
             } else {
-                if let Zero = group.ctx.hot {
-                    group.ctx.set_next_hot(FiveCardDrawHand(0));
+                match group.ctx.hot {
+                    Zero => {
+                        group.ctx.set_next_hot(FiveCardDrawHand(0));
+                    },
+                    // This can happen after the drawing round, since the ids were
+                    // used for cards then.
+                    FiveCardDrawHand(index) if index >= hands_len => {
+                        group.ctx.set_next_hot(FiveCardDrawHand(0));
+                    },
+                    _ => {},
                 }
 
                 let action_opt = match (
@@ -705,61 +1286,29 @@ pub fn update_and_render(
                     &state.table.seats.personalities[current_i]
                 ) {
                     (true, _) => Some(Action::Fold),
-                    (false, Some(_personality)) => {
-                        // TODO Base choice of action off of personality
-    
+                    (false, Some(strategy)) => {
                         let hand = hands.get(current_i)
                                     .map(|&h| h)
                                     .unwrap_or_default();
-    
-                        let probability = hand_win_probability(hand);
-                        let mut action = if probability >= SEVENTY_FIVE_PERCENT {
-                            let multiple = MoneyInner::from(xs::range(rng, 6..12));
-                            Action::Raise(
-                                minimum_raise_total 
-                                + state.table.seats.ante.get().saturating_mul(multiple)
-                            )
-                        } else if probability >= FIFTY_PERCENT {
-                            if xs::range(rng, 0..5) == 0 {
-                                // Don't be perfectly predictable!
-                                gen_action(
-                                    rng,
-                                    ActionSpec {
-                                        one_past_max_money: NonZeroMoneyInner::MIN
-                                            .saturating_add(
-                                                state.table.seats.moneys[current_i]
-                                                .as_inner()
-                                            ),
-                                        min_money_unit: MIN_MONEY_UNIT,
-                                        minimum_raise_total,
-                                    }
-                                )
-                            } else {
-                                Action::Call
-                            }
-                        } else {
-                            Action::Fold
+
+                        let stacks: [MoneyInner; MAX_PLAYERS as usize] = core::array::from_fn(
+                            |i| state.table.seats.moneys[i].as_inner()
+                        );
+
+                        let view = BettingView {
+                            cards: Cards::FiveCardDraw { hand, wilds: state.table.seats.rules.wilds },
+                            position: current,
+                            dealer: $bundle.dealer,
+                            stacks: &stacks[..player_count.usize()],
+                            stats: &state.table.seats.stats[..player_count.usize()],
+                            pot,
+                            call_remainder,
+                            raise_range,
+                            raise_unit: state.table.seats.ante,
+                            allowed_kind_mode,
                         };
-    
-                        match action {
-                            Action::Fold => {
-                                if call_remainder == 0 {
-                                    action = Action::Call;
-                                }
-                            },
-                            Action::Call => {},
-                            Action::Raise(raise_amount) => {
-                                let inner = state.table.seats.moneys[current_i]
-                                    .as_inner();
-                                if inner
-                                    .checked_sub(raise_amount)
-                                    .is_none() {
-                                    action = Action::Raise(inner);
-                                }
-                            },
-                        }
-    
-                        Some(action)
+
+                        Some(view.legal_action(strategy.betting_action(rng, &view)))
                     },
                     (false, None) => {
                         match group.ctx.hot {
@@ -837,7 +1386,8 @@ pub fn update_and_render(
                                         ActionKind::Call => {
                                             match allowed_kind_mode {
                                                 AllowedKindMode::All
-                                                | AllowedKindMode::NoFolding => {
+                                                | AllowedKindMode::NoFolding
+                                                | AllowedKindMode::NoRaising => {
                                                     draw_money_in_rect!(group, call_remainder, money_rect);
                                                 },
                                                 AllowedKindMode::AllIn => {
@@ -928,6 +1478,10 @@ pub fn update_and_render(
                                             } else if group.input.pressed_this_frame(Button::DOWN) {
                                                 $bundle.selection.bet = $bundle.selection.bet.saturating_sub(MIN_MONEY_UNIT.get());
                                             }
+                                            // Keep the chevrons within the amounts that can be raised.
+                                            if let Some(range) = raise_range {
+                                                $bundle.selection.bet = range.clamp($bundle.selection.bet);
+                                            }
                                         }
                                         _ => {}
                                     }
@@ -943,6 +1497,24 @@ pub fn update_and_render(
                 };
     
                 if let Some(action) = action_opt {
+                    // A raise can be left selected in the menu from an earlier turn,
+                    // when raising is no longer allowed.
+                    let action = match (action, raise_range) {
+                        (Action::Raise(_), None) => Action::Call,
+                        (Action::Raise(raise_amount), Some(range)) => {
+                            Action::Raise(range.clamp(raise_amount))
+                        },
+                        (action, _) => action,
+                    };
+
+                    if !pot.has_folded(current) {
+                        state.table.seats.stats[current_i].record_action(
+                            &action,
+                            call_remainder,
+                            $is_first_round,
+                        );
+                    }
+
                     let bet = match action {
                         Action::Fold => PotAction::Fold,
                         Action::Call => {
@@ -1011,6 +1583,13 @@ pub fn update_and_render(
                         },
                     };
     
+                    if let Action::Raise(_) = action {
+                        if $is_first_round && $bundle.opener.is_none() {
+                            $bundle.opener = Some(current);
+                        }
+                        $bundle.last_aggressor = Some(current);
+                    }
+
                     pot.push_bet($bundle.current, bet);
     
                     $bundle.current += 1;
@@ -1027,69 +1606,232 @@ pub fn update_and_render(
     }
 
     macro_rules! next_bundle {
-        ($bundle: ident =
-            $hands: expr,
-            $deck: expr,
-            $dealer: expr,
-            $player_count: expr,
-            $pot: expr
-        ) => {
-            let hands = $hands;
-            let deck = $deck;
-            let dealer = $dealer;
-            let player_count = $player_count;
-            let mut pot = $pot;
+        ($bundle: ident = $old_bundle: expr, $current: expr) => {
+            let old_bundle: &StateBundle = $old_bundle;
+            let current = $current;
 
+            let mut pot = old_bundle.pot.clone();
             pot.reset_for_new_round();
-    
-            // TODO Add options for selecting the next player to go in 
-            // different wys since apparently multiple options are in use.
-            // * Dealer's left always
-            // * Last aggresive player (Last to raise)
-            // * The player who opened the betting on the first round
-            //     Could call this first aggresive player
-            let current = {
-                let mut index = dealer + 1;
-                if index >= player_count.u8() {
-                    index = 0;
-                }
-                index
-            };
 
             let $bundle = StateBundle {
-                deck,
-                hands,
-                dealer,
+                deck: old_bundle.deck.clone(),
+                hands: old_bundle.hands.clone(),
+                dealer: old_bundle.dealer,
                 current,
-                pot: $pot,
-                player_count,
+                pot,
+                player_count: old_bundle.player_count,
                 selection: <_>::default(),
+                opener: old_bundle.opener,
+                last_aggressor: old_bundle.last_aggressor,
             };
         }
     }
 
-    //const MENU_H: unscaled::H = unscaled::h_const_div(
-        //command::HEIGHT_H,
-        //6
-    //);
+    macro_rules! stack_eval_text {
+        ($text:ident = $eval: expr) => {
+            let mut eval_text = [0u8; 64];
+            let _cant_actually_fail = write!(
+                &mut eval_text[..],
+                "{}",
+                $eval
+            );
 
-    //const MENU_RECT: unscaled::Rect = unscaled::Rect {
-        //x: unscaled::X(0),
-        //y: unscaled::y_const_add_h(
-            //unscaled::Y(0),
-            //unscaled::h_const_sub(
-                //command::HEIGHT_H,
-                //MENU_H
-            //)
-        //),
-        //w: command::WIDTH_W,
-        //h: MENU_H,
-    //};
+            // Lowercase the first letter, since upper case is weird in the font
+            // right now
+            eval_text[0].make_ascii_lowercase();
 
-    match &mut state.table.state {
-        Undealt {
+            let $text = eval_text;
+        }
+    }
+
+    macro_rules! finish_round {
+        () => {
+            state.table.seats.hands_played = state.table.seats.hands_played.saturating_add(1);
+            state.table.seats.levels.hand_played();
+            state.table.seats.standings.record_stacks(
+                state.table.seats.hands_played,
+                state.table.seats.starting_seats.iter()
+                    .zip(state.table.seats.moneys.iter())
+                    .map(|(&seat, money)| (seat, money.as_inner())),
+            );
+            state.table.seats.update_ante();
+
+            // Condense players down
+            {
+                let mut pairs: [(Money, Personality, PlayerStats, StartingSeat); MAX_PLAYERS as usize]
+                    = <_>::default();
+
+                let mut pair_index = 0;
+                for i in 0..state.table.seats.moneys.len() {
+                    if state.table.seats.moneys[i] == 0 {
+                        continue
+                    }
+                    pairs[pair_index] = (
+                        state.table.seats.moneys[i].take_all(),
+                        state.table.seats.personalities[i].take(),
+                        core::mem::take(&mut state.table.seats.stats[i]),
+                        state.table.seats.starting_seats[i],
+                    );
+                    pair_index += 1;
+                }
+
+                for i in 0..state.table.seats.moneys.len() {
+                    state.table.seats.personalities[i] = None;
+                    state.table.seats.stats[i] = PlayerStats::default();
+                }
+
+                for i in 0..pair_index {
+                    MoneyMove {
+                        from: &mut pairs[i].0,
+                        to: &mut state.table.seats.moneys[i],
+                        amount: NonZeroMoneyInner::MAX,
+                    }.perform();
+                    state.table.seats.personalities[i] = pairs[i].1.take();
+                    state.table.seats.stats[i] = pairs[i].2;
+                    state.table.seats.starting_seats[i] = pairs[i].3;
+                }
+            }
+
+            let remaining_player_count = state.table.seats.moneys
+                .iter()
+                .take_while(|money| **money != 0)
+                .count();
+
+            debug_assert!(remaining_player_count > 0);
+
+            // Assumes we just condensed the players
+            let only_cpus_left = state.table.seats.personalities[..remaining_player_count]
+                .iter()
+                .all(|personality| personality.is_some());
+
+            let hand_limit_reached = state.table.seats.hand_limit
+                .is_some_and(|limit| state.table.seats.hands_played >= limit);
+
+            state.table.seats.skip.simulate_if_only_cpus_left(only_cpus_left);
+
+            if hand_limit_reached {
+                speaker.request_sfx(SFX::CardPlace);
+                state.table.state = <_>::default();
+                cmd = ModeCmd::FinishedRound;
+            } else {
+                match PlayerCount::try_from(remaining_player_count as u8) {
+                    Ok(player_count) => {
+                        let bundle = deal_bundle(
+                            rng,
+                            player_count,
+                            &mut state.table.seats.moneys,
+                            state.table.seats.ante,
+                            state.table.seats.rules.wilds,
+                        );
+
+                        for stats in &mut state.table.seats.stats[..player_count.usize()] {
+                            stats.start_hand();
+                        }
+
+                        speaker.request_sfx(SFX::CardPlace);
+                        state.table.state = FirstRound {
+                            bundle,
+                        };
+                    },
+                    Err(_) => {
+                        speaker.request_sfx(SFX::CardPlace);
+                        state.table.state = Finished;
+                        cmd = ModeCmd::FinishedRound;
+                    },
+                }
+            }
+        }
+    }
+
+    macro_rules! award_now {
+        ($hand_index: ident, $pot: expr) => {
+            let i = usize::from($hand_index);
+            state.table.seats.standings.record_pot_won(
+                state.table.seats.starting_seats[i],
+                $pot.total(),
+            );
+            $pot.award(&mut state.table.seats.moneys[i]);
+
+            finish_round!();
+        }
+    }
+
+    const FULLSCREEN_MODAL_RECT: unscaled::Rect = unscaled::Rect {
+        x: unscaled::X(0),
+        y: unscaled::Y(0),
+        w: command::WIDTH_W,
+        h: command::HEIGHT_H,
+    };
+
+    //const MENU_H: unscaled::H = unscaled::h_const_div(
+        //command::HEIGHT_H,
+        //6
+    //);
+
+    //const MENU_RECT: unscaled::Rect = unscaled::Rect {
+        //x: unscaled::X(0),
+        //y: unscaled::y_const_add_h(
+            //unscaled::Y(0),
+            //unscaled::h_const_sub(
+                //command::HEIGHT_H,
+                //MENU_H
+            //)
+        //),
+        //w: command::WIDTH_W,
+        //h: MENU_H,
+    //};
+
+    if !matches!(state.table.state, Undealt { .. }) {
+        state.table.seats.levels.frame_passed();
+    }
+
+    match &mut state.table.state {
+        Finished => {
+            match ui::do_standings(new_group!(), &state.table.seats.standings) {
+                StandingsCmd::NoOp => {},
+                StandingsCmd::PlayAgain => {
+                    let standings = &state.table.seats.standings;
+
+                    let mut moneys = [0; MAX_PLAYERS as usize];
+                    for (money, standing) in moneys.iter_mut().zip(standings.seats.iter()) {
+                        *money = standing.starting_money;
+                    }
+
+                    if let Ok(player_count) = PlayerCount::try_from(standings.player_count) {
+                        speaker.request_sfx(SFX::CardPlace);
+
+                        *state.table = Table::selected(
+                            rng,
+                            player_count,
+                            Money::array_from_inner_array(moneys),
+                            &standings.setups,
+                            state.table.seats.betting_structure,
+                            state.table.seats.rules,
+                            state.table.seats.levels.schedule,
+                        );
+                    }
+                },
+                StandingsCmd::BackToTitleScreen => {
+                    cmd = ModeCmd::BackToTitleScreen;
+                },
+            }
+        },
+        Undealt {
+            ref player_count,
+            ref mut seats,
+            ref mut seat_editor,
+            ..
+        } if seat_editor.is_some() => {
+            ui::do_seat_editor(new_group!(), seats, player_count.u8(), seat_editor);
+        },
+        Undealt {
             ref mut player_count,
             ref mut starting_money,
+            ref mut seats,
+            ref mut seat_editor,
+            ref mut betting_structure,
+            ref mut rules,
+            ref mut level_schedule,
         } => {
             let group = new_group!();
 
@@ -1110,7 +1852,7 @@ pub fn update_and_render(
             }
 
             let player_count_rect = unscaled::Rect {
-                x: unscaled::X(100),
+                x: unscaled::X(50),
                 y: unscaled::Y(100),
                 w: unscaled::W(50),
                 h: unscaled::H(100),
@@ -1152,8 +1894,24 @@ pub fn update_and_render(
                 PlayerCountSelect,
             );
 
+            if do_button(
+                group,
+                ButtonSpec {
+                    id: EditSeats,
+                    rect: unscaled::Rect {
+                        x: player_count_rect.x,
+                        y: player_count_rect.y + player_count_rect.h,
+                        w: unscaled::W(50),
+                        h: unscaled::H(50),
+                    },
+                    text: b"seats",
+                }
+            ) {
+                *seat_editor = Some(SeatEditor::default());
+            }
+
             let starting_money_rect = unscaled::Rect {
-                x: unscaled::X(150),
+                x: unscaled::X(100),
                 y: unscaled::Y(100),
                 w: unscaled::W(50),
                 h: unscaled::H(100),
@@ -1167,12 +1925,203 @@ pub fn update_and_render(
                 StartingMoneySelect,
             );
 
+            let betting_structure_rect = unscaled::Rect {
+                x: starting_money_rect.x + starting_money_rect.w,
+                y: unscaled::Y(100),
+                w: unscaled::W(50),
+                h: unscaled::H(100),
+            };
+
+            {
+                let betting_structure_text = betting_structure.text();
+
+                let xy = gfx::center_line_in_rect(
+                    betting_structure_text.len() as _,
+                    betting_structure_rect,
+                );
+                group.commands.print_chars(
+                    betting_structure_text,
+                    xy.x,
+                    xy.y,
+                    TEXT
+                );
+            }
+            {
+                let limit_label = b"limit";
+
+                let xy = gfx::center_line_in_rect(
+                    limit_label.len() as _,
+                    betting_structure_rect,
+                );
+
+                group.commands.print_chars(
+                    limit_label,
+                    xy.x,
+                    xy.y + gfx::CHAR_H,
+                    TEXT
+                );
+            }
+
+            ui::draw_quick_select(
+                group,
+                betting_structure_rect,
+                BettingStructureSelect,
+            );
+
+            let wilds_rect = unscaled::Rect {
+                x: betting_structure_rect.x + betting_structure_rect.w,
+                y: unscaled::Y(100),
+                w: unscaled::W(50),
+                h: unscaled::H(100),
+            };
+
+            {
+                let wilds_text = rules.wilds.text();
+
+                let xy = gfx::center_line_in_rect(
+                    wilds_text.len() as _,
+                    wilds_rect,
+                );
+                group.commands.print_chars(
+                    wilds_text,
+                    xy.x,
+                    xy.y,
+                    TEXT
+                );
+            }
+            {
+                let wild_label = b"wild";
+
+                let xy = gfx::center_line_in_rect(
+                    wild_label.len() as _,
+                    wilds_rect,
+                );
+
+                group.commands.print_chars(
+                    wild_label,
+                    xy.x,
+                    xy.y + gfx::CHAR_H,
+                    TEXT
+                );
+            }
+
+            ui::draw_quick_select(
+                group,
+                wilds_rect,
+                WildCardsSelect,
+            );
+
+            let opening_rect = unscaled::Rect {
+                x: wilds_rect.x + wilds_rect.w,
+                y: unscaled::Y(100),
+                w: unscaled::W(50),
+                h: unscaled::H(100),
+            };
+
+            {
+                let opening_text = rules.opening.text();
+
+                let xy = gfx::center_line_in_rect(
+                    opening_text.len() as _,
+                    opening_rect,
+                );
+                group.commands.print_chars(
+                    opening_text,
+                    xy.x,
+                    xy.y,
+                    TEXT
+                );
+            }
+            {
+                let opening_label = b"to open";
+
+                let xy = gfx::center_line_in_rect(
+                    opening_label.len() as _,
+                    opening_rect,
+                );
+
+                group.commands.print_chars(
+                    opening_label,
+                    xy.x,
+                    xy.y + gfx::CHAR_H,
+                    TEXT
+                );
+            }
+
+            ui::draw_quick_select(
+                group,
+                opening_rect,
+                OpeningRequirementSelect,
+            );
+
+            let second_round_start_rect = unscaled::Rect {
+                x: opening_rect.x + opening_rect.w,
+                y: unscaled::Y(100),
+                w: unscaled::W(50),
+                h: unscaled::H(100),
+            };
+
+            {
+                let second_round_start_text = rules.second_round_start.text();
+
+                let xy = gfx::center_line_in_rect(
+                    second_round_start_text.len() as _,
+                    second_round_start_rect,
+                );
+                group.commands.print_chars(
+                    second_round_start_text,
+                    xy.x,
+                    xy.y,
+                    TEXT
+                );
+            }
+            {
+                let second_round_start_label = b"bets 2nd";
+
+                let xy = gfx::center_line_in_rect(
+                    second_round_start_label.len() as _,
+                    second_round_start_rect,
+                );
+
+                group.commands.print_chars(
+                    second_round_start_label,
+                    xy.x,
+                    xy.y + gfx::CHAR_H,
+                    TEXT
+                );
+            }
+
+            ui::draw_quick_select(
+                group,
+                second_round_start_rect,
+                SecondRoundStartSelect,
+            );
+
+            let level_schedule_rect = unscaled::Rect {
+                x: second_round_start_rect.x + second_round_start_rect.w,
+                y: unscaled::Y(100),
+                w: unscaled::W(50),
+                h: unscaled::H(100),
+            };
+
+            ui::draw_level_schedule_in_rect(
+                group.commands,
+                *level_schedule,
+                level_schedule_rect,
+            );
+
+            ui::draw_quick_select(
+                group,
+                level_schedule_rect,
+                LevelScheduleSelect,
+            );
+
             if do_button(
                 group,
                 ButtonSpec {
                     id: Submit,
                     rect: unscaled::Rect {
-                        x: starting_money_rect.x + starting_money_rect.w,
+                        x: level_schedule_rect.x + level_schedule_rect.w,
                         y: unscaled::Y(100),
                         w: unscaled::W(50),
                         h: unscaled::H(100),
@@ -1186,7 +2135,7 @@ pub fn update_and_render(
 
                 let mut moneys = [0; MAX_PLAYERS as usize];
                 for i in 0..player_count.usize() {
-                    moneys[i] = *starting_money;
+                    moneys[i] = seats[i].starting_money;
                 }
                 let moneys = Money::array_from_inner_array(moneys);
 
@@ -1194,9 +2143,13 @@ pub fn update_and_render(
                     rng,
                     player_count,
                     moneys,
+                    &seats[..],
+                    *betting_structure,
+                    *rules,
+                    *level_schedule,
                 );
             } else {
-                let menu = [BackToTitleScreen, PlayerCountSelect, StartingMoneySelect, Submit];
+                let menu = [BackToTitleScreen, PlayerCountSelect, EditSeats, StartingMoneySelect, BettingStructureSelect, WildCardsSelect, OpeningRequirementSelect, SecondRoundStartSelect, LevelScheduleSelect, Submit];
 
                 match group.ctx.hot {
                     BackToTitleScreen => {
@@ -1211,16 +2164,110 @@ pub fn update_and_render(
                         }
                     }
                     StartingMoneySelect => {
-                        let menu_i = 2;
+                        let menu_i = 3;
                         match input.dir_pressed_this_frame() {
                             Some(Dir::Up) => {
+                                let previous = *starting_money;
                                 *starting_money = starting_money.saturating_add(MIN_MONEY_UNIT.get());
+                                set_unedited_starting_money(seats, previous, *starting_money);
                             },
                             Some(Dir::Down) => {
+                                let previous = *starting_money;
                                 *starting_money = starting_money.saturating_sub(MIN_MONEY_UNIT.get());
                                 if *starting_money == 0 {
                                     *starting_money = MIN_MONEY_UNIT.get();
                                 }
+                                set_unedited_starting_money(seats, previous, *starting_money);
+                            },
+                            Some(Dir::Left) => {
+                                group.ctx.set_next_hot(menu[menu_i - 1]);
+                            }
+                            Some(Dir::Right) => {
+                                group.ctx.set_next_hot(menu[menu_i + 1]);
+                            }
+                            None => {}
+                        }
+                    }
+                    BettingStructureSelect => {
+                        let menu_i = 4;
+                        match input.dir_pressed_this_frame() {
+                            Some(Dir::Up) => {
+                                *betting_structure = betting_structure.next_up();
+                            },
+                            Some(Dir::Down) => {
+                                *betting_structure = betting_structure.next_down();
+                            },
+                            Some(Dir::Left) => {
+                                group.ctx.set_next_hot(menu[menu_i - 1]);
+                            }
+                            Some(Dir::Right) => {
+                                group.ctx.set_next_hot(menu[menu_i + 1]);
+                            }
+                            None => {}
+                        }
+                    }
+                    WildCardsSelect => {
+                        let menu_i = 5;
+                        match input.dir_pressed_this_frame() {
+                            Some(Dir::Up) => {
+                                rules.wilds = rules.wilds.next_up();
+                            },
+                            Some(Dir::Down) => {
+                                rules.wilds = rules.wilds.next_down();
+                            },
+                            Some(Dir::Left) => {
+                                group.ctx.set_next_hot(menu[menu_i - 1]);
+                            }
+                            Some(Dir::Right) => {
+                                group.ctx.set_next_hot(menu[menu_i + 1]);
+                            }
+                            None => {}
+                        }
+                    }
+                    OpeningRequirementSelect => {
+                        let menu_i = 6;
+                        match input.dir_pressed_this_frame() {
+                            Some(Dir::Up) => {
+                                rules.opening = rules.opening.next_up();
+                            },
+                            Some(Dir::Down) => {
+                                rules.opening = rules.opening.next_down();
+                            },
+                            Some(Dir::Left) => {
+                                group.ctx.set_next_hot(menu[menu_i - 1]);
+                            }
+                            Some(Dir::Right) => {
+                                group.ctx.set_next_hot(menu[menu_i + 1]);
+                            }
+                            None => {}
+                        }
+                    }
+                    SecondRoundStartSelect => {
+                        let menu_i = 7;
+                        match input.dir_pressed_this_frame() {
+                            Some(Dir::Up) => {
+                                rules.second_round_start = rules.second_round_start.next_up();
+                            },
+                            Some(Dir::Down) => {
+                                rules.second_round_start = rules.second_round_start.next_down();
+                            },
+                            Some(Dir::Left) => {
+                                group.ctx.set_next_hot(menu[menu_i - 1]);
+                            }
+                            Some(Dir::Right) => {
+                                group.ctx.set_next_hot(menu[menu_i + 1]);
+                            }
+                            None => {}
+                        }
+                    }
+                    LevelScheduleSelect => {
+                        let menu_i = 8;
+                        match input.dir_pressed_this_frame() {
+                            Some(Dir::Up) => {
+                                *level_schedule = level_schedule.next_up();
+                            },
+                            Some(Dir::Down) => {
+                                *level_schedule = level_schedule.next_down();
                             },
                             Some(Dir::Left) => {
                                 group.ctx.set_next_hot(menu[menu_i - 1]);
@@ -1249,6 +2296,18 @@ pub fn update_and_render(
                             None => {}
                         }
                     }
+                    EditSeats => {
+                        let menu_i = 2;
+                        match input.dir_pressed_this_frame() {
+                            Some(Dir::Left) => {
+                                group.ctx.set_next_hot(menu[menu_i - 1]);
+                            }
+                            Some(Dir::Right) => {
+                                group.ctx.set_next_hot(menu[menu_i + 1]);
+                            }
+                            _ => {}
+                        }
+                    }
                     Submit => {
                         let menu_i = menu.len() - 1;
                         match input.dir_pressed_this_frame() {
@@ -1270,18 +2329,43 @@ pub fn update_and_render(
             bundle,
         } => {
             let group = new_group!();
-            let outcome = do_five_card_draw!(group, bundle, None);
+            let outcome = if !bundle.pot.has_folded(bundle.current)
+            && ui::do_pass_device_if_needed(
+                group,
+                bundle.current,
+                state.table.seats.starting_seats[usize::from(bundle.current)],
+                &state.table.seats.personalities[..usize::from(bundle.player_count.u8())],
+                &mut state.table.seats.hot_seat,
+                &state.table.seats.standings.setups,
+            ) {
+                RoundOutcome::Undetermined
+            } else {
+                do_five_card_draw!(group, bundle, None, true)
+            };
 
             match outcome {
                 RoundOutcome::Undetermined => {},
+                RoundOutcome::AdvanceToNext
+                if bundle.opener.is_none()
+                && state.table.seats.rules.opening != OpeningRequirement::Any => {
+                    // Nobody opened, so deal again, leaving the antes in the pot.
+                    let (hands, deck) = deal(
+                        rng,
+                        bundle.player_count,
+                        state.table.seats.rules.wilds,
+                    );
+                    bundle.hands = hands;
+                    bundle.deck = deck;
+                    bundle.pot.restart_hand();
+                    bundle.current = dealers_left(bundle.dealer, bundle.player_count);
+                    bundle.selection = <_>::default();
+                    speaker.request_sfx(SFX::CardPlace);
+                },
                 RoundOutcome::AdvanceToNext => {
+                    // The draw always starts at the dealer's left.
                     next_bundle!(
-                        new_bundle =
-                            bundle.hands.clone(),
-                            bundle.deck.clone(),
-                            bundle.current,
-                            bundle.player_count,
-                            bundle.pot.clone()
+                        new_bundle = bundle,
+                        dealers_left(bundle.dealer, bundle.player_count)
                     );
                     speaker.request_sfx(SFX::CardPlace);
                     state.table.state = Drawing {
@@ -1289,8 +2373,8 @@ pub fn update_and_render(
                         bundle: new_bundle,
                     };
                 },
-                RoundOutcome::AwardNow(_) => {
-                    todo!("AwardNow(_) from FirstRound");
+                RoundOutcome::AwardNow(hand_index) => {
+                    award_now!(hand_index, bundle.pot);
                 },
             }
         }
@@ -1299,15 +2383,34 @@ pub fn update_and_render(
             drawing_state,
         } => {
             let group = new_group!();
-            let outcome = do_five_card_draw!(group, bundle, Some(drawing_state));
+            let drawing = drawing_state.current;
+            let outcome = match drawing {
+                Some(c) if !bundle.pot.has_folded(c)
+                && ui::do_pass_device_if_needed(
+                    group,
+                    c,
+                    state.table.seats.starting_seats[usize::from(c)],
+                    &state.table.seats.personalities[..usize::from(bundle.player_count.u8())],
+                    &mut state.table.seats.hot_seat,
+                    &state.table.seats.standings.setups,
+                ) => RoundOutcome::Undetermined,
+                _ => do_five_card_draw!(group, bundle, Some(drawing_state), false),
+            };
 
             match outcome {
                 RoundOutcome::Undetermined => {},
                 RoundOutcome::AdvanceToNext => {
-                    todo!("AdvanceToNext from Drawing");
+                    next_bundle!(
+                        new_bundle = bundle,
+                        state.table.seats.rules.second_round_start.first_to_act(bundle)
+                    );
+                    speaker.request_sfx(SFX::CardPlace);
+                    state.table.state = SecondRound {
+                        bundle: new_bundle,
+                    };
                 },
-                RoundOutcome::AwardNow(_) => {
-                    todo!("AwardNow(_) from Drawing");
+                RoundOutcome::AwardNow(hand_index) => {
+                    award_now!(hand_index, bundle.pot);
                 },
             }
         }
@@ -1315,35 +2418,506 @@ pub fn update_and_render(
             bundle,
         } => {
             let group = new_group!();
-            let outcome = do_five_card_draw!(group, bundle, None);
+            let outcome = if !bundle.pot.has_folded(bundle.current)
+            && ui::do_pass_device_if_needed(
+                group,
+                bundle.current,
+                state.table.seats.starting_seats[usize::from(bundle.current)],
+                &state.table.seats.personalities[..usize::from(bundle.player_count.u8())],
+                &mut state.table.seats.hot_seat,
+                &state.table.seats.standings.setups,
+            ) {
+                RoundOutcome::Undetermined
+            } else {
+                do_five_card_draw!(group, bundle, None, false)
+            };
 
             match outcome {
                 RoundOutcome::Undetermined => {},
                 RoundOutcome::AdvanceToNext => {
-                    todo!("AdvanceToNext from SecondRound");
+                    speaker.request_sfx(SFX::CardPlace);
+                    state.table.state = Showdown {
+                        bundle: bundle.clone(),
+                    };
                 },
-                RoundOutcome::AwardNow(_) => {
-                    todo!("AwardNow(_) from SecondRound");
+                RoundOutcome::AwardNow(hand_index) => {
+                    award_now!(hand_index, bundle.pot);
                 },
             }
         }
         Showdown {
             bundle,
         } => {
+            debug_assert!(bundle.pot.total() > 0);
+
             let group = new_group!();
-            let outcome = do_five_card_draw!(group, bundle, None);
 
-            match outcome {
-                RoundOutcome::Undetermined => {},
-                RoundOutcome::AdvanceToNext => {
-                    todo!("AdvanceToNext from Showdown");
-                },
-                RoundOutcome::AwardNow(_) => {
-                    todo!("AwardNow(_) from Showdown");
-                },
+            group.commands.draw_nine_slice(
+                gfx::NineSlice::Window,
+                FULLSCREEN_MODAL_RECT
+            );
+
+            #[derive(Debug, Default)]
+            struct Award {
+                amount: MoneyInner,
+                eval: evaluate::Eval,
+            }
+            type Awards = [[Award; MAX_PLAYERS as usize]; MAX_PLAYERS as usize];
+
+            let awards: Awards = {
+                let mut awards = Awards::default();
+
+                let wilds = state.table.seats.rules.wilds;
+                let eval_for = |player: HandIndex| match bundle.hands.get(usize::from(player)) {
+                    Some(hand) => evaluate::five_card_hand_wild(*hand, wilds),
+                    None => {
+                        debug_assert!(false, "Hand not found for {player}");
+                        evaluate::Eval::WORST
+                    }
+                };
+
+                let splits = bundle.pot.best_hand_splits(
+                    &state.table.seats.moneys,
+                    eval_for,
+                    bundle.current,
+                );
+
+                for split in splits {
+                    for (winner_index, &amount) in split.shares.iter().enumerate() {
+                        if amount == 0 {
+                            continue
+                        }
+
+                        // Push an award on
+                        for award in &mut awards[winner_index] {
+                            if award.amount == 0 {
+                                *award = Award {
+                                    amount,
+                                    eval: eval_for(winner_index as HandIndex),
+                                };
+                                break
+                            }
+                        }
+                    }
+                }
+
+                debug_assert_eq!(
+                    {
+                        let mut total: MoneyInner = 0;
+
+                        for award_array in awards.iter() {
+                            for Award { amount, .. } in award_array {
+                                total = total.saturating_add(*amount);
+                            }
+                        }
+
+                        total
+                    },
+                    bundle.pot.total(),
+                    "Awarded total did not match pot total!"
+                );
+
+                awards
+            };
+
+            {
+                const AWARDS_BASE_X: unscaled::X = unscaled::X(SPACING_W.get());
+
+                let mut y = unscaled::Y(gfx::CHAR_LINE_ADVANCE.get());
+                for (i, award_array) in awards.iter().enumerate() {
+                    if award_array.iter().all(|award| award.amount == 0) {
+                        continue
+                    }
+
+                    group.commands.draw_five_card_hand(
+                        gfx::FiveCardFacing::Up(bundle.hands[i]),
+                        AWARDS_BASE_X,
+                        y,
+                    );
+
+                    let text_x = AWARDS_BASE_X
+                        + gfx::Commands::FIVE_CARD_HAND_WIDTH
+                        + SPACING_W;
+
+                    let mut text_y = y;
+
+                    let mut player_text = [0u8; 20];
+                    let _cant_actually_fail = write!(
+                        &mut player_text[..],
+                        "{}",
+                        state.table.seats.standings.name(
+                            state.table.seats.starting_seats[i]
+                        ),
+                    );
+
+                    group.commands.print_chars(
+                        &player_text,
+                        text_x,
+                        text_y,
+                        TEXT
+                    );
+
+                    text_y += gfx::CHAR_LINE_ADVANCE;
+
+                    for Award { amount, eval } in award_array {
+                        if *amount == 0 {
+                            break
+                        }
+
+                        stack_money_text!(amount_text = amount);
+
+                        group.commands.print_chars(
+                            &amount_text,
+                            text_x,
+                            text_y,
+                            TEXT
+                        );
+
+                        stack_eval_text!(eval_text = eval);
+
+                        group.commands.print_chars(
+                            &eval_text,
+                            text_x + (pre_nul_len(&amount_text) + 1) * gfx::CHAR_ADVANCE,
+                            text_y,
+                            TEXT
+                        );
+
+                        text_y += gfx::CHAR_LINE_ADVANCE;
+                    }
+
+                    y += gfx::card::HEIGHT + SPACING_H;
+                }
+            }
+
+            let w = unscaled::W(50);
+            let h = unscaled::H(20);
+
+            let submit_rect = unscaled::Rect {
+                x: unscaled::X(0) + ((command::WIDTH_W/2) - (w/2)),
+                y: unscaled::Y(0) + (command::HEIGHT_H - (h + SPACING_H)),
+                w,
+                h,
+            };
+
+            {
+                let skip_rect = unscaled::Rect {
+                    x: submit_rect.x + (w * 2),
+                    y: submit_rect.y - submit_rect.h,
+                    h: submit_rect.h * 2,
+                    ..submit_rect
+                };
+
+                let skip_text: &[u8] = match state.table.seats.skip {
+                    SkipState::Skip | SkipState::Simulating => b"skip cpu only",
+                    SkipState::Watch => b"watch cpu only",
+                };
+
+                {
+                    let xy = gfx::center_line_in_rect(
+                        skip_text.len() as _,
+                        skip_rect,
+                    );
+                    group.commands.print_chars(
+                        skip_text,
+                        xy.x,
+                        xy.y,
+                        TEXT
+                    );
+                }
+
+                ui::draw_quick_select(
+                    group,
+                    skip_rect,
+                    SkipRemainderOfGameSelect,
+                );
+            }
+
+            if do_button(
+                group,
+                ButtonSpec {
+                    id: ShowdownSubmit,
+                    rect: submit_rect,
+                    text: b"submit",
+                }
+            ) {
+                for i in 0..bundle.player_count.u8() {
+                    if !bundle.pot.has_folded(i) {
+                        state.table.seats.stats[usize::from(i)].record_showdown();
+                    }
+                }
+
+                for (i, pots) in awards.iter().enumerate() {
+                    state.table.seats.standings.record_pot_won(
+                        state.table.seats.starting_seats[i],
+                        pots.iter().map(|award| award.amount).sum(),
+                    );
+                }
+
+                bundle.pot.award_multiple(
+                    &mut state.table.seats.moneys,
+                    awards.iter()
+                        .enumerate()
+                        .flat_map(|(i, pots)| {
+                            let i: HandIndex = i as HandIndex;
+                            pots.iter().map(move |award| (i, award.amount))
+                        })
+                );
+
+                finish_round!();
+            } else {
+                match group.ctx.hot {
+                    SkipRemainderOfGameSelect => {
+                        match input.dir_pressed_this_frame() {
+                            Some(Dir::Up | Dir::Down) => {
+                                state.table.seats.skip =
+                                    match state.table.seats.skip {
+                                        SkipState::Skip | SkipState::Simulating => SkipState::Watch,
+                                        SkipState::Watch => SkipState::Skip,
+                                    };
+                            },
+                            Some(Dir::Left | Dir::Right) => {
+                                group.ctx.set_next_hot(ShowdownSubmit);
+                            }
+                            None => {}
+                        }
+                    }
+                    ShowdownSubmit => {
+                         match input.dir_pressed_this_frame() {
+                            Some(Dir::Left | Dir::Right) => {
+                                group.ctx.set_next_hot(SkipRemainderOfGameSelect);
+                            }
+                            Some(Dir::Up | Dir::Down) | None => {}
+                        }
+                    }
+                    _ => {
+                        group.ctx.set_next_hot(ShowdownSubmit);
+                    }
+                }
             }
         }
     }
 
     cmd
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+const TEST_STARTING_MONEY: MoneyInner = 1000;
+
+/// A table with two CPU players, in the first betting round.
+#[cfg(test)]
+fn cpu_table(rng: &mut Xs, rules: Rules) -> Table {
+    let mut setups = default_seat_setups(TEST_STARTING_MONEY);
+    for setup in &mut setups {
+        setup.is_human = false;
+    }
+
+    let mut moneys = [0; MAX_PLAYERS as usize];
+    moneys[0] = TEST_STARTING_MONEY;
+    moneys[1] = TEST_STARTING_MONEY;
+
+    Table::selected(
+        rng,
+        PlayerCount::Two,
+        Money::array_from_inner_array(moneys),
+        &setups[..],
+        <_>::default(),
+        rules,
+        <_>::default(),
+    )
+}
+
+/// Runs frames, pressing A every other one to get past any buttons, until
+/// `done` returns true.
+#[cfg(test)]
+fn run_until(rng: &mut Xs, table: &mut Table, mut done: impl FnMut(&Table) -> bool) {
+    let mut ctx = ui::Context::default();
+    let mut commands = Commands::default();
+    let mut speaker = Speaker::default();
+
+    for frame in 0..10_000 {
+        if done(table) {
+            return
+        }
+
+        ctx.frame_init();
+        commands.clear();
+        speaker.clear();
+
+        let input = if frame % 2 == 0 {
+            Input { gamepad: Button::A, previous_gamepad: <_>::default() }
+        } else {
+            Input { gamepad: <_>::default(), previous_gamepad: Button::A }
+        };
+
+        update_and_render(
+            &mut commands,
+            State {
+                rng,
+                ctx: &mut ctx,
+                table,
+            },
+            input,
+            &mut speaker,
+        );
+    }
+
+    panic!("The table never got to the expected state");
+}
+
+/// Sets up a showdown between the given hands, submits it, and returns what
+/// each player has once they have anted for the next hand.
+#[cfg(test)]
+fn moneys_after_showdown(wilds: WildCards, hands: [Hand; 2]) -> [MoneyInner; 2] {
+    let mut rng = xs::from_seed([7; 16]);
+
+    let mut table = cpu_table(&mut rng, Rules { wilds, ..<_>::default() });
+
+    let TableState::FirstRound { ref bundle } = table.state else {
+        panic!("The table should start in the first round");
+    };
+    let mut bundle = bundle.clone();
+    bundle.hands[0] = hands[0];
+    bundle.hands[1] = hands[1];
+    table.state = TableState::Showdown { bundle };
+
+    run_until(&mut rng, &mut table, |table| !matches!(table.state, TableState::Showdown { .. }));
+
+    [table.seats.moneys[0].as_inner(), table.seats.moneys[1].as_inner()]
+}
+
+/// What the winner and the loser of a showdown over just the antes have once
+/// they have anted for the next hand.
+#[cfg(test)]
+const WON: MoneyInner = TEST_STARTING_MONEY;
+#[cfg(test)]
+const LOST: MoneyInner = TEST_STARTING_MONEY - BASE_ANTE.get() * 2;
+
+#[test]
+fn a_joker_makes_three_of_a_kind_that_beats_two_pair_at_the_showdown() {
+    const JOKER: Card = models::JOKER;
+    // A pair of kings, and a joker.
+    let kings = [12, 25, 28, 45, JOKER];
+    // Queens and fives.
+    let two_pair = [11, 24, 4, 17, 34];
+
+    assert_eq!(moneys_after_showdown(WildCards::Jokers, [kings, two_pair]), [WON, LOST]);
+    // Without the joker being wild, the kings are only one pair.
+    assert_eq!(moneys_after_showdown(WildCards::None, [kings, two_pair]), [LOST, WON]);
+}
+
+#[test]
+fn a_deuce_makes_a_straight_that_beats_three_of_a_kind_at_the_showdown() {
+    // Three, four, five and seven, and a deuce to fill in the six.
+    let straight = [2, 16, 30, 45, 1];
+    // Three queens.
+    let trips = [11, 24, 37, 4, 34];
+
+    assert_eq!(moneys_after_showdown(WildCards::Deuces, [straight, trips]), [WON, LOST]);
+    // Without deuces being wild, it's only seven high.
+    assert_eq!(moneys_after_showdown(WildCards::None, [straight, trips]), [LOST, WON]);
+}
+
+#[test]
+fn only_hands_that_can_open_may_bet_before_anyone_opens() {
+    let mut rng = xs::from_seed([19; 16]);
+
+    let table = cpu_table(
+        &mut rng,
+        Rules { opening: OpeningRequirement::JacksOrBetter, ..<_>::default() },
+    );
+
+    let TableState::FirstRound { ref bundle } = table.state else {
+        panic!("The table should start in the first round");
+    };
+
+    // A pair of tens.
+    let tens = [9, 22, 1, 15, 30];
+    // A pair of jacks.
+    let jacks = [10, 23, 1, 15, 30];
+
+    let options = |hand, is_first_round, has_opener| table.seats.betting_options(
+        &bundle.pot,
+        bundle.current,
+        hand,
+        is_first_round,
+        has_opener,
+    );
+
+    let (raise_range, allowed_kind_mode) = options(tens, true, false);
+    assert!(raise_range.is_none());
+    assert_eq!(allowed_kind_mode, AllowedKindMode::NoRaising);
+
+    let (raise_range, allowed_kind_mode) = options(jacks, true, false);
+    assert!(raise_range.is_some());
+    assert_eq!(allowed_kind_mode, AllowedKindMode::NoFolding);
+
+    // Once somebody has opened, or after the draw, anybody can bet.
+    let (raise_range, allowed_kind_mode) = options(tens, true, true);
+    assert!(raise_range.is_some());
+    assert_eq!(allowed_kind_mode, AllowedKindMode::NoFolding);
+
+    let (raise_range, allowed_kind_mode) = options(tens, false, false);
+    assert!(raise_range.is_some());
+    assert_eq!(allowed_kind_mode, AllowedKindMode::NoFolding);
+}
+
+#[test]
+fn nobody_opening_deals_again_with_the_antes_left_in_the_pot() {
+    let mut rng = xs::from_seed([19; 16]);
+
+    let mut table = cpu_table(
+        &mut rng,
+        Rules { opening: OpeningRequirement::JacksOrBetter, ..<_>::default() },
+    );
+
+    // A pair of tens, and eight high.
+    let unopenable = [[9, 22, 1, 15, 30], [2, 16, 31, 45, 7]];
+
+    let TableState::FirstRound { ref mut bundle } = table.state else {
+        panic!("The table should start in the first round");
+    };
+    bundle.hands[0] = unopenable[0];
+    bundle.hands[1] = unopenable[1];
+    let antes = bundle.pot.total();
+    let moneys = table.seats.moneys.clone();
+
+    // Neither player can bet, and checking can't be turned into folding, so
+    // the round ends with everyone checking.
+    run_until(&mut rng, &mut table, |table| match table.state {
+        TableState::FirstRound { ref bundle } => bundle.hands[..2] != unopenable,
+        _ => true,
+    });
+
+    let TableState::FirstRound { ref bundle } = table.state else {
+        panic!("The hand should have been dealt again");
+    };
+    assert_eq!(bundle.pot.total(), antes);
+    assert_eq!(table.seats.moneys, moneys);
+    assert!(!bundle.pot.has_folded(0));
+    assert!(!bundle.pot.has_folded(1));
+    assert_eq!(bundle.opener, None);
+    assert_eq!(bundle.current, dealers_left(bundle.dealer, bundle.player_count));
+}
+
+#[test]
+fn the_second_round_starts_with_the_chosen_player() {
+    use SecondRoundStart::*;
+
+    let mut rng = xs::from_seed([23; 16]);
+
+    let mut moneys = Money::array_from_inner_array([TEST_STARTING_MONEY; MAX_PLAYERS as usize]);
+    let mut bundle = deal_bundle(&mut rng, PlayerCount::Four, &mut moneys, BASE_ANTE, WildCards::None);
+    bundle.dealer = 3;
+
+    // Nobody bet, so everybody falls back to the dealer's left.
+    for start in SecondRoundStart::ALL {
+        assert_eq!(start.first_to_act(&bundle), 0, "{start:?}");
+    }
+
+    bundle.opener = Some(1);
+    bundle.last_aggressor = Some(2);
+
+    assert_eq!(DealersLeft.first_to_act(&bundle), 0);
+    assert_eq!(LastAggressor.first_to_act(&bundle), 2);
+    assert_eq!(Opener.first_to_act(&bundle), 1);
+}
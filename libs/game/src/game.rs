@@ -17,32 +17,1427 @@ macro_rules! compile_time_assert {
 
 // TODO? should this just be in models?
 mod shared_game_types {
-    use models::NonZeroMoneyInner;
+    use models::{Action, Money, MoneyInner, NonZeroMoneyInner, PerPlayer, PlayerCount, MIN_MONEY_UNIT};
+    use probability::{EvalCount, Probability, TWENTY_FIVE_PERCENT, FIFTY_PERCENT, SEVENTY_FIVE_PERCENT, ONE};
+    use xs::Xs;
+
+    /// `None` for a human player.
+    pub type Personality = Option<Box<dyn crate::strategy::Strategy>>;
+
+    /// The index of the seat a player started the game in. This stays with the
+    /// player when the seats are condensed down after other players run out of
+    /// money, so players can be told apart for the whole game.
+    pub type StartingSeat = u8;
+
+    /// Which of the `CpuPersonality::PRESETS` a CPU seat plays as.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub enum CpuPreset {
+        /// A preset picked at random when the game starts.
+        #[default]
+        Random,
+        Rock,
+        Maniac,
+        CallingStation,
+        Solid,
+    }
+
+    impl CpuPreset {
+        pub const ALL: [Self; 5] = [
+            Self::Random,
+            Self::Rock,
+            Self::Maniac,
+            Self::CallingStation,
+            Self::Solid,
+        ];
+
+        pub fn text(self) -> &'static [u8] {
+            match self {
+                Self::Random => b"random",
+                Self::Rock => b"rock",
+                Self::Maniac => b"maniac",
+                Self::CallingStation => b"caller",
+                Self::Solid => b"solid",
+            }
+        }
+
+        fn index(self) -> usize {
+            Self::ALL.iter()
+                .position(|&preset| preset == self)
+                .unwrap_or(0)
+        }
+
+        pub fn next_up(self) -> Self {
+            let index = self.index();
+            Self::ALL[if index == 0 { Self::ALL.len() - 1 } else { index - 1 }]
+        }
+
+        pub fn next_down(self) -> Self {
+            Self::ALL[(self.index() + 1) % Self::ALL.len()]
+        }
+
+        pub fn personality(self, rng: &mut Xs) -> CpuPersonality {
+            match self {
+                Self::Random => CpuPersonality::gen(rng),
+                Self::Rock => CpuPersonality::ROCK,
+                Self::Maniac => CpuPersonality::MANIAC,
+                Self::CallingStation => CpuPersonality::CALLING_STATION,
+                Self::Solid => CpuPersonality::SOLID,
+            }
+        }
+    }
+
+    /// A short name for a seat, picked one character at a time on the seat
+    /// setup screen. Unused characters are spaces.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct SeatName([u8; SeatName::LEN]);
+
+    impl SeatName {
+        pub const LEN: usize = 8;
+
+        /// The characters that can be picked, in the order they are cycled
+        /// through.
+        const CHARS: &'static [u8] = b" abcdefghijklmnopqrstuvwxyz0123456789";
+
+        /// The default name for a seat, like "p0" for the first one.
+        pub fn for_seat(seat: StartingSeat) -> Self {
+            use std::io::Write;
+
+            let mut name = [b' '; Self::LEN];
+            let _cant_actually_fail = write!(&mut name[..], "p{seat}");
+            Self(name)
+        }
+
+        /// The name, without any trailing spaces.
+        pub fn as_bytes(&self) -> &[u8] {
+            let len = self.0.iter()
+                .rposition(|&c| c != b' ')
+                .map_or(0, |i| i + 1);
+            &self.0[..len]
+        }
+
+        /// All of the characters, including the trailing spaces, for editing.
+        pub fn chars(&self) -> &[u8; Self::LEN] {
+            &self.0
+        }
+
+        fn char_index(&self, i: usize) -> usize {
+            Self::CHARS.iter()
+                .position(|&c| c == self.0[i])
+                .unwrap_or(0)
+        }
+
+        /// Changes the character at `i` to the previous one that can be picked.
+        pub fn char_up(&mut self, i: usize) {
+            if i >= Self::LEN { return }
+            let index = self.char_index(i);
+            self.0[i] = Self::CHARS[if index == 0 { Self::CHARS.len() - 1 } else { index - 1 }];
+        }
+
+        /// Changes the character at `i` to the next one that can be picked.
+        pub fn char_down(&mut self, i: usize) {
+            if i >= Self::LEN { return }
+            self.0[i] = Self::CHARS[(self.char_index(i) + 1) % Self::CHARS.len()];
+        }
+    }
+
+    impl core::fmt::Display for SeatName {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            // All of the `CHARS` are ASCII.
+            f.pad(core::str::from_utf8(self.as_bytes()).unwrap_or_default())
+        }
+    }
+
+    /// How a seat is set up before a game starts.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct SeatSetup {
+        pub is_human: bool,
+        /// Only used when the seat is not human.
+        pub preset: CpuPreset,
+        pub name: SeatName,
+        pub starting_money: MoneyInner,
+    }
+
+    impl SeatSetup {
+        /// `None` for a human player, as with `Personality`.
+        pub fn personality(&self, rng: &mut Xs) -> Personality {
+            if self.is_human {
+                None
+            } else {
+                Some(Box::new(self.preset.personality(rng)))
+            }
+        }
+    }
+
+    pub type SeatSetups = PerPlayer<SeatSetup>;
+
+    /// Where the seat setup screen is at, while it is open.
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct SeatEditor {
+        /// The seat being edited.
+        pub seat: StartingSeat,
+        /// The index of the character of the name being edited.
+        pub name_cursor: u8,
+    }
+
+    /// The seats the setup screens start with: the first seat is human, the
+    /// rest are CPU players with random presets, and everyone starts with
+    /// `starting_money`.
+    pub fn default_seat_setups(starting_money: MoneyInner) -> SeatSetups {
+        core::array::from_fn(|i| SeatSetup {
+            is_human: i == 0,
+            preset: CpuPreset::Random,
+            name: SeatName::for_seat(i as StartingSeat),
+            starting_money,
+        })
+    }
+
+    /// Sets every seat that starts with the `previous` amount that all the
+    /// stacks were set to at once to start with `starting_money` instead.
+    /// Seats whose stacks were changed on the seat setup screen keep them.
+    pub fn set_unedited_starting_money(
+        setups: &mut SeatSetups,
+        previous: MoneyInner,
+        starting_money: MoneyInner,
+    ) {
+        for setup in setups.iter_mut() {
+            if setup.starting_money == previous {
+                setup.starting_money = starting_money;
+            }
+        }
+    }
+
+    /// Sets each of the given seats up with the personality from the setup of
+    /// the same index.
+    pub fn gen_personalities(
+        rng: &mut Xs,
+        personalities: &mut [Personality],
+        setups: &[SeatSetup],
+    ) {
+        for (personality, setup) in personalities.iter_mut().zip(setups.iter()) {
+            *personality = setup.personality(rng);
+        }
+    }
+
+    #[test]
+    fn changing_the_shared_starting_money_keeps_edited_stacks() {
+        let mut setups = default_seat_setups(500);
+        setups[1].starting_money = 2000;
+
+        set_unedited_starting_money(&mut setups, 500, 505);
+
+        assert_eq!(setups[0].starting_money, 505);
+        assert_eq!(setups[1].starting_money, 2000);
+        assert!(setups[2..].iter().all(|setup| setup.starting_money == 505));
+    }
+
+    #[test]
+    fn seat_names_can_be_edited_a_character_at_a_time() {
+        let mut name = SeatName::for_seat(12);
+        assert_eq!(name.as_bytes(), b"p12");
+        assert_eq!(name.to_string(), "p12");
+
+        name.char_down(3);
+        assert_eq!(name.as_bytes(), b"p12a");
+        name.char_up(3);
+        name.char_up(3);
+        assert_eq!(name.as_bytes(), b"p129");
+
+        // Out of range indexes are ignored.
+        name.char_down(SeatName::LEN);
+        assert_eq!(name.as_bytes(), b"p129");
+
+        for i in 0..SeatName::LEN {
+            name.0[i] = b' ';
+        }
+        assert_eq!(name.as_bytes(), b"");
+    }
+
+    /// How many of the given seats are controlled by people.
+    pub fn human_count(personalities: &[Personality]) -> usize {
+        personalities.iter().filter(|p| p.is_none()).count()
+    }
+
+    /// Which human seat currently has the device, when several people are
+    /// taking turns on one device. The device is passed along before a
+    /// different human acts, so each player's cards are only shown to them.
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct HotSeat {
+        holder: Option<StartingSeat>,
+    }
+
+    impl HotSeat {
+        /// Whether the device needs to be passed to `seat` before they can
+        /// act, given how many humans are still in the game. With at most one
+        /// human, there is nobody to hide the cards from.
+        pub fn needs_pass(&self, seat: StartingSeat, human_count: usize) -> bool {
+            human_count > 1 && self.holder != Some(seat)
+        }
+
+        pub fn pass_to(&mut self, seat: StartingSeat) {
+            self.holder = Some(seat);
+        }
+    }
+
+    #[test]
+    fn the_device_is_only_passed_between_different_humans() {
+        let mut hot_seat = HotSeat::default();
+
+        assert!(!hot_seat.needs_pass(0, 1));
+        assert!(hot_seat.needs_pass(0, 2));
+
+        hot_seat.pass_to(0);
+        assert!(!hot_seat.needs_pass(0, 2));
+        assert!(hot_seat.needs_pass(2, 2));
+
+        hot_seat.pass_to(2);
+        assert!(hot_seat.needs_pass(0, 2));
+        // Once the other humans are out, there is no one left to pass to.
+        assert!(!hot_seat.needs_pass(0, 1));
+    }
+
+    pub type HandCount = u32;
+
+    pub type FrameCount = u32;
+
+    /// How many times per second `update_and_render` is expected to be called.
+    pub const FRAMES_PER_SECOND: FrameCount = 60;
+
+    pub type Level = u8;
+
+    /// How much the starting blinds and antes are multiplied by, at each level.
+    /// The last level lasts until the end of the game.
+    pub const LEVEL_MULTIPLIERS: [MoneyInner; 12] = [1, 2, 3, 4, 6, 8, 10, 15, 20, 30, 50, 100];
+
+    /// How the blinds and antes go up as the game goes on, so that games
+    /// eventually end.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub enum LevelSchedule {
+        /// The blinds and antes never go up.
+        #[default]
+        Flat,
+        /// Go up a level each time this many hands are played.
+        Hands(HandCount),
+        /// Go up a level each time this many frames go by.
+        Frames(FrameCount),
+    }
+
+    impl LevelSchedule {
+        pub const PRESETS: [Self; 5] = [
+            Self::Flat,
+            Self::Hands(10),
+            Self::Hands(25),
+            Self::Frames(5 * 60 * FRAMES_PER_SECOND),
+            Self::Frames(15 * 60 * FRAMES_PER_SECOND),
+        ];
+
+        fn preset_index(self) -> usize {
+            Self::PRESETS.iter()
+                .position(|&preset| preset == self)
+                .unwrap_or(0)
+        }
+
+        pub fn next_up(self) -> Self {
+            let index = self.preset_index();
+            Self::PRESETS[if index == 0 { Self::PRESETS.len() - 1 } else { index - 1 }]
+        }
+
+        pub fn next_down(self) -> Self {
+            Self::PRESETS[(self.preset_index() + 1) % Self::PRESETS.len()]
+        }
+    }
+
+    impl core::fmt::Display for LevelSchedule {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            match self {
+                Self::Flat => write!(f, "no"),
+                Self::Hands(hands) => write!(f, "{hands} hands"),
+                Self::Frames(frames) => write!(f, "{} min", frames / (60 * FRAMES_PER_SECOND)),
+            }
+        }
+    }
+
+    /// How long until the next level.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum UntilNextLevel {
+        Hands(HandCount),
+        Frames(FrameCount),
+    }
+
+    /// Where a table is in its `LevelSchedule`.
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct Levels {
+        pub schedule: LevelSchedule,
+        pub level: Level,
+        /// The amount of hands played, or frames gone by, during this level.
+        progress: u32,
+    }
+
+    impl Levels {
+        pub fn new(schedule: LevelSchedule) -> Self {
+            Self {
+                schedule,
+                ..<_>::default()
+            }
+        }
+
+        pub fn is_last(&self) -> bool {
+            usize::from(self.level) + 1 >= LEVEL_MULTIPLIERS.len()
+        }
+
+        fn advance(&mut self, per_level: u32) {
+            if self.is_last() {
+                return
+            }
+
+            self.progress = self.progress.saturating_add(1);
+
+            if self.progress >= per_level {
+                self.level += 1;
+                self.progress = 0;
+            }
+        }
+
+        /// Expected to be called once per frame while the game is being played.
+        pub fn frame_passed(&mut self) {
+            if let LevelSchedule::Frames(per_level) = self.schedule {
+                self.advance(per_level);
+            }
+        }
+
+        pub fn hand_played(&mut self) {
+            if let LevelSchedule::Hands(per_level) = self.schedule {
+                self.advance(per_level);
+            }
+        }
+
+        pub fn until_next(&self) -> Option<UntilNextLevel> {
+            if self.is_last() {
+                return None
+            }
+
+            match self.schedule {
+                LevelSchedule::Flat => None,
+                LevelSchedule::Hands(per_level) => Some(UntilNextLevel::Hands(
+                    per_level.saturating_sub(self.progress)
+                )),
+                LevelSchedule::Frames(per_level) => Some(UntilNextLevel::Frames(
+                    per_level.saturating_sub(self.progress)
+                )),
+            }
+        }
+
+        /// The given starting blind or ante amount, at the current level.
+        pub fn scale(&self, base: NonZeroMoneyInner) -> NonZeroMoneyInner {
+            let multiplier = LEVEL_MULTIPLIERS.get(usize::from(self.level))
+                .copied()
+                .and_then(NonZeroMoneyInner::new)
+                .unwrap_or(NonZeroMoneyInner::MIN);
+
+            base.saturating_mul(multiplier)
+        }
+    }
+
+    #[test]
+    fn levels_go_up_on_schedule_and_stop_at_the_last_one() {
+        let mut levels = Levels::new(LevelSchedule::Hands(3));
+
+        levels.frame_passed();
+        levels.hand_played();
+        levels.hand_played();
+        assert_eq!(levels.level, 0);
+        assert_eq!(levels.until_next(), Some(UntilNextLevel::Hands(1)));
+
+        levels.hand_played();
+        assert_eq!(levels.level, 1);
+        assert_eq!(levels.scale(MIN_MONEY_UNIT).get(), MIN_MONEY_UNIT.get() * 2);
+
+        for _ in 0..1000 {
+            levels.hand_played();
+        }
+        assert!(levels.is_last());
+        assert_eq!(levels.until_next(), None);
+        assert_eq!(
+            levels.scale(MIN_MONEY_UNIT).get(),
+            MIN_MONEY_UNIT.get() * LEVEL_MULTIPLIERS[LEVEL_MULTIPLIERS.len() - 1]
+        );
+
+        let mut flat = Levels::new(LevelSchedule::Flat);
+        for _ in 0..1000 {
+            flat.hand_played();
+            flat.frame_passed();
+        }
+        assert_eq!(flat.level, 0);
+        assert_eq!(flat.until_next(), None);
+    }
+
+    /// Each trait is a `Probability`, so 0 is the least of that trait, and
+    /// `probability::ONE` is the most.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct CpuPersonality {
+        /// How strong a hand needs to be before putting money in voluntarily.
+        pub tightness: Probability,
+        /// How often to bet or raise with a hand that is worth playing, instead
+        /// of just calling.
+        pub aggression: Probability,
+        /// How often to bet or raise with a hand that is not worth playing.
+        pub bluff_frequency: Probability,
+        /// How much of the stack is acceptable to put at risk on a hand that is
+        /// worth playing, but is not a strong hand.
+        pub risk_tolerance: Probability,
+    }
+
+    impl Default for CpuPersonality {
+        fn default() -> Self {
+            Self::SOLID
+        }
+    }
+
+    impl CpuPersonality {
+        /// Plays few hands, rarely raises, never bluffs, and doesn't like risk.
+        pub const ROCK: Self = Self {
+            tightness: 0b1110_0000,
+            aggression: 0b0010_0000,
+            bluff_frequency: 0,
+            risk_tolerance: 0b0010_0000,
+        };
+        /// Plays almost every hand, and bets and raises with almost all of them.
+        pub const MANIAC: Self = Self {
+            tightness: 0b0001_0000,
+            aggression: 0b1110_0000,
+            bluff_frequency: 0b1000_0000,
+            risk_tolerance: 0b1110_0000,
+        };
+        /// Plays almost every hand, calls nearly everything, and rarely raises.
+        pub const CALLING_STATION: Self = Self {
+            tightness: 0b0001_0000,
+            aggression: 0b0001_0000,
+            bluff_frequency: 0b0000_1000,
+            risk_tolerance: ONE,
+        };
+        /// A middle of the road player.
+        pub const SOLID: Self = Self {
+            tightness: 0b1000_0000,
+            aggression: 0b1000_0000,
+            bluff_frequency: 0b0001_0000,
+            risk_tolerance: 0b1000_0000,
+        };
+
+        pub const PRESETS: [Self; 4] = [
+            Self::ROCK,
+            Self::MANIAC,
+            Self::CALLING_STATION,
+            Self::SOLID,
+        ];
+
+        pub fn gen(rng: &mut Xs) -> Self {
+            Self::PRESETS[xs::range(rng, 0..Self::PRESETS.len() as u32) as usize]
+        }
+
+        /// Returns true with probability `chance`.
+        pub fn roll(rng: &mut Xs, chance: Probability) -> bool {
+            xs::range(rng, 0..u32::from(ONE)) < u32::from(chance)
+        }
+
+        /// The win probability a hand needs before it is worth playing.
+        /// Ranges from 25% for the loosest players to just under 75% for the
+        /// tightest.
+        pub fn play_threshold(&self) -> Probability {
+            TWENTY_FIVE_PERCENT.saturating_add(self.tightness / 2)
+        }
+
+        /// The win probability a hand needs before it is counted as strong, which
+        /// is always at least the `play_threshold`.
+        pub fn strong_threshold(&self) -> Probability {
+            core::cmp::max(
+                self.play_threshold(),
+                SEVENTY_FIVE_PERCENT.saturating_sub(self.aggression / 8)
+            )
+        }
+
+        /// The amount of money this personality is willing to put at risk on a
+        /// hand that is worth playing, but is not strong.
+        pub fn max_risk(&self, stack: MoneyInner) -> MoneyInner {
+            let amount = u64::from(stack) * u64::from(self.risk_tolerance)
+                / u64::from(ONE);
+
+            MoneyInner::try_from(amount).unwrap_or(stack)
+        }
+
+        /// Decide what to do in a poker betting round. The returned raise amounts
+        /// are totals that may be larger than the stack; capping them is left to
+        /// the caller.
+        pub fn decide_poker_action(
+            &self,
+            rng: &mut Xs,
+            PokerDecision {
+                win_probability,
+                call_remainder,
+                pot_odds,
+                stack,
+                minimum_raise_total,
+                raise_unit,
+                opponents,
+            }: PokerDecision,
+        ) -> Action {
+            let is_strong = win_probability >= self.strong_threshold();
+            let is_playable = win_probability >= if call_remainder > 0 {
+                self.call_threshold(opponents)
+            } else {
+                self.play_threshold()
+            };
+            let bluff_frequency = self.bluff_frequency_against(opponents);
+            // Calling is profitable in the long run, even if the hand is weak.
+            let has_odds = pot_odds > 0 && win_probability >= pot_odds;
+            let is_within_risk = call_remainder <= self.max_risk(stack);
+
+            let raise_by = |rng: &mut Xs, max_multiple: u32| {
+                let multiple = xs::range(rng, 1..max_multiple.saturating_add(2));
+                Action::Raise(
+                    minimum_raise_total
+                        .saturating_add(raise_unit.get().saturating_mul(multiple))
+                )
+            };
+
+            if is_strong {
+                if Self::roll(rng, self.aggression.saturating_add(FIFTY_PERCENT / 2)) {
+                    raise_by(rng, 2 + u32::from(self.aggression / 32))
+                } else {
+                    Action::Call
+                }
+            } else if is_playable {
+                if !is_within_risk && !has_odds {
+                    Action::Fold
+                } else if Self::roll(rng, self.aggression / 2) {
+                    raise_by(rng, 1 + u32::from(self.aggression / 64))
+                } else {
+                    Action::Call
+                }
+            } else if has_odds && is_within_risk {
+                Action::Call
+            } else if Self::roll(rng, bluff_frequency) {
+                raise_by(rng, 1 + u32::from(self.aggression / 64))
+            } else {
+                Action::Fold
+            }
+        }
+
+        /// The win probability a hand needs before it is worth calling a bet with.
+        /// This is lower than the `play_threshold` against loose or aggressive
+        /// opponents, since they bet with weaker hands, and higher against
+        /// passive opponents, since their rare bets tend to mean a strong hand.
+        pub fn call_threshold(&self, opponents: OpponentTendencies) -> Probability {
+            let lighter = (opponents.looseness.saturating_sub(TYPICAL_LOOSENESS) / 4)
+                .saturating_add(opponents.raise_rate.saturating_sub(TYPICAL_RAISE_RATE) / 4)
+                .saturating_add(opponents.aggression.saturating_sub(TYPICAL_AGGRESSION) / 8);
+            let tighter = TYPICAL_AGGRESSION.saturating_sub(opponents.aggression) / 8;
+
+            self.play_threshold()
+                .saturating_sub(lighter)
+                .saturating_add(tighter)
+        }
+
+        /// How often to bluff against the given opponents. Tight opponents, who
+        /// fold to bets often, get bluffed more, and opponents who rarely fold,
+        /// or who often see the hand through to the showdown, get bluffed less.
+        /// Personalities that never bluff still never do.
+        pub fn bluff_frequency_against(&self, opponents: OpponentTendencies) -> Probability {
+            let scaled = u32::from(self.bluff_frequency)
+                * u32::from(opponents.fold_to_bet)
+                / u32::from(TYPICAL_FOLD_TO_BET)
+                * u32::from(ONE - opponents.showdown_rate)
+                / u32::from(ONE - TYPICAL_SHOWDOWN_RATE);
+
+            Probability::try_from(scaled).unwrap_or(ONE)
+        }
+
+        /// Decide what to do in a betting round after some community cards have
+        /// been dealt. Bets and raises are sized relative to the pot, and capped by
+        /// the stack.
+        pub fn decide_postflop_action(
+            &self,
+            rng: &mut Xs,
+            decision: PokerDecision,
+            PostflopDecision {
+                pot_total,
+                has_draw,
+                has_gone_this_round,
+            }: PostflopDecision,
+        ) -> Action {
+            let PokerDecision {
+                win_probability,
+                call_remainder,
+                pot_odds,
+                stack,
+                ..
+            } = decision;
+
+            let is_strong = win_probability >= self.strong_threshold();
+            let has_odds = pot_odds > 0 && win_probability >= pot_odds;
+            let is_within_risk = call_remainder <= self.max_risk(stack);
+
+            let pot_sized = |fraction: Probability| {
+                pot_sized_raise(decision, pot_total, fraction)
+            };
+
+            if is_strong {
+                if call_remainder == 0 {
+                    if !has_gone_this_round
+                    && Self::roll(rng, self.aggression / 4) {
+                        // Check, planning to raise if someone else bets.
+                        Action::Call
+                    } else if Self::roll(rng, self.aggression.saturating_add(FIFTY_PERCENT / 2)) {
+                        // Value bet
+                        pot_sized(FIFTY_PERCENT.saturating_add(self.aggression / 4))
+                    } else {
+                        Action::Call
+                    }
+                } else if has_gone_this_round
+                && Self::roll(rng, self.aggression.saturating_add(FIFTY_PERCENT / 2)) {
+                    // Check-raise, or re-raise
+                    pot_sized(ONE)
+                } else if Self::roll(rng, self.aggression / 2) {
+                    pot_sized(SEVENTY_FIVE_PERCENT)
+                } else {
+                    Action::Call
+                }
+            } else if has_draw
+            && (is_within_risk || has_odds)
+            && Self::roll(rng, self.aggression) {
+                // Semi-bluff
+                pot_sized(FIFTY_PERCENT)
+            } else {
+                match self.decide_poker_action(rng, decision) {
+                    // Keep any other raises, including bluffs, smaller than the pot.
+                    Action::Raise(_) => pot_sized(TWENTY_FIVE_PERCENT + self.aggression / 8),
+                    action => action,
+                }
+            }
+        }
+
+        /// The amount to bet in a game where each bet is placed against the pot
+        /// rather than other players, like acey-deucey. Bets that are expected to
+        /// win are sized with a Kelly-style fraction of the stack, scaled between
+        /// half and full Kelly by `risk_tolerance`. Returns `None` if the bet is
+        /// not worth making.
+        pub fn decide_pot_bet(
+            &self,
+            rng: &mut Xs,
+            win_probability: Probability,
+            stack: MoneyInner,
+            max_bet: MoneyInner,
+        ) -> Option<NonZeroMoneyInner> {
+            let kelly = kelly_fraction(win_probability);
+
+            let amount = if kelly > 0 && win_probability >= self.play_threshold() {
+                let scale = FIFTY_PERCENT.saturating_add(self.risk_tolerance / 2);
+
+                let amount = u64::from(stack)
+                    * u64::from(kelly)
+                    * u64::from(scale)
+                    / (u64::from(ONE) * u64::from(ONE));
+
+                MoneyInner::try_from(amount).unwrap_or(stack)
+            } else if Self::roll(rng, self.bluff_frequency) {
+                // A long shot, so keep it small.
+                MIN_MONEY_UNIT.get()
+            } else {
+                return None
+            };
+
+            // Round down to a multiple of the minimum unit, but bet at least that
+            // unit, since the decision to bet was already made.
+            let amount = amount - (amount % MIN_MONEY_UNIT.get());
+
+            NonZeroMoneyInner::new(core::cmp::min(amount, max_bet))
+                .or_else(|| NonZeroMoneyInner::new(core::cmp::min(MIN_MONEY_UNIT.get(), max_bet)))
+        }
+    }
+
+    /// The fraction of the stack that the Kelly criterion says to bet on an even
+    /// money bet that wins with `win_probability`. That is, `2p - 1`, or 0 if
+    /// the bet is not expected to win. `ONE` is odd, so `FIFTY_PERCENT` is
+    /// slightly over a half, and has to be checked for separately.
+    pub fn kelly_fraction(win_probability: Probability) -> Probability {
+        if win_probability <= FIFTY_PERCENT {
+            0
+        } else {
+            win_probability - (ONE - win_probability)
+        }
+    }
+
+    #[derive(Clone, Copy, Debug)]
+    pub struct PokerDecision {
+        pub win_probability: Probability,
+        /// The amount extra needed to call.
+        pub call_remainder: MoneyInner,
+        /// The win probability above which calling is profitable in the long run.
+        pub pot_odds: Probability,
+        pub stack: MoneyInner,
+        pub minimum_raise_total: MoneyInner,
+        /// The typical amount to raise by, for example the big blind.
+        pub raise_unit: NonZeroMoneyInner,
+        pub opponents: OpponentTendencies,
+    }
+
+    pub type StatCount = u32;
+
+    /// The amount of hands a player needs to have been dealt before their
+    /// stats are considered meaningful.
+    pub const MIN_STAT_HANDS: StatCount = 8;
+
+    /// Counts of what a player has done across hands, so that CPU players can
+    /// adapt to how the other players play.
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct PlayerStats {
+        pub hands: StatCount,
+        /// Hands where money was voluntarily put in during the first betting
+        /// round. That is, not counting blinds or antes.
+        pub voluntarily_put_in: StatCount,
+        /// Hands where there was a raise during the first betting round.
+        pub first_round_raised: StatCount,
+        pub bets_and_raises: StatCount,
+        pub calls: StatCount,
+        pub faced_bets: StatCount,
+        pub folds_to_bets: StatCount,
+        pub showdowns: StatCount,
+        has_put_in_this_hand: bool,
+        has_raised_this_hand: bool,
+    }
+
+    impl PlayerStats {
+        pub fn start_hand(&mut self) {
+            self.hands = self.hands.saturating_add(1);
+            self.has_put_in_this_hand = false;
+            self.has_raised_this_hand = false;
+        }
+
+        /// Record an action taken while `call_remainder` was needed to call.
+        pub fn record_action(
+            &mut self,
+            action: &Action,
+            call_remainder: MoneyInner,
+            is_first_round: bool,
+        ) {
+            fn inc(count: &mut StatCount) {
+                *count = count.saturating_add(1);
+            }
+
+            if call_remainder > 0 {
+                inc(&mut self.faced_bets);
+            }
+
+            match action {
+                Action::Fold => {
+                    if call_remainder > 0 {
+                        inc(&mut self.folds_to_bets);
+                    }
+                    return
+                },
+                // Checking
+                Action::Call if call_remainder == 0 => return,
+                Action::Call => inc(&mut self.calls),
+                Action::Raise(_) => {
+                    inc(&mut self.bets_and_raises);
+
+                    if is_first_round && !self.has_raised_this_hand {
+                        self.has_raised_this_hand = true;
+                        inc(&mut self.first_round_raised);
+                    }
+                },
+            }
+
+            if is_first_round && !self.has_put_in_this_hand {
+                self.has_put_in_this_hand = true;
+                inc(&mut self.voluntarily_put_in);
+            }
+        }
+
+        pub fn record_showdown(&mut self) {
+            self.showdowns = self.showdowns.saturating_add(1);
+        }
+
+        fn rate(&self, count: StatCount, total: StatCount) -> Option<Probability> {
+            if self.hands < MIN_STAT_HANDS || total == 0 {
+                return None
+            }
+
+            Some(EvalCount { win_count: count, total }.probability())
+        }
+
+        /// Voluntarily put money in pot: the fraction of hands where money was
+        /// put in during the first betting round without being forced to.
+        pub fn vpip(&self) -> Option<Probability> {
+            self.rate(self.voluntarily_put_in, self.hands)
+        }
+
+        /// Pre-flop raise: the fraction of hands where there was a raise during
+        /// the first betting round.
+        pub fn pfr(&self) -> Option<Probability> {
+            self.rate(self.first_round_raised, self.hands)
+        }
+
+        /// The aggression factor is usually given as bets and raises divided by
+        /// calls. This is the fraction of bets, raises and calls that were bets
+        /// or raises instead, which has the same ordering, but fits in a
+        /// `Probability`.
+        pub fn aggression_factor(&self) -> Option<Probability> {
+            self.rate(
+                self.bets_and_raises,
+                self.bets_and_raises.saturating_add(self.calls)
+            )
+        }
+
+        pub fn fold_to_bet(&self) -> Option<Probability> {
+            self.rate(self.folds_to_bets, self.faced_bets)
+        }
+
+        pub fn showdown_rate(&self) -> Option<Probability> {
+            self.rate(self.showdowns, self.hands)
+        }
+    }
+
+    /// A rough guess at how often a player puts money in voluntarily, used when
+    /// there isn't enough information to know better.
+    pub const TYPICAL_LOOSENESS: Probability = 0b0110_0000;
+    /// A rough guess at how often a player raises in the first betting round,
+    /// used when there isn't enough information to know better.
+    pub const TYPICAL_RAISE_RATE: Probability = TWENTY_FIVE_PERCENT;
+    /// A rough guess at a player's `aggression_factor`, used when there isn't
+    /// enough information to know better.
+    pub const TYPICAL_AGGRESSION: Probability = FIFTY_PERCENT;
+    /// A rough guess at how often a player folds to a bet, used when there isn't
+    /// enough information to know better.
+    pub const TYPICAL_FOLD_TO_BET: Probability = FIFTY_PERCENT;
+    /// A rough guess at how often a player goes to the showdown, used when
+    /// there isn't enough information to know better.
+    pub const TYPICAL_SHOWDOWN_RATE: Probability = TWENTY_FIVE_PERCENT;
+
+    /// A summary of how the opponents in a hand tend to play.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct OpponentTendencies {
+        /// The average `vpip` of the opponents.
+        pub looseness: Probability,
+        /// The average `pfr` of the opponents.
+        pub raise_rate: Probability,
+        /// The average `aggression_factor` of the opponents.
+        pub aggression: Probability,
+        /// The average `fold_to_bet` of the opponents.
+        pub fold_to_bet: Probability,
+        /// The average `showdown_rate` of the opponents.
+        pub showdown_rate: Probability,
+    }
+
+    impl Default for OpponentTendencies {
+        fn default() -> Self {
+            Self {
+                looseness: TYPICAL_LOOSENESS,
+                raise_rate: TYPICAL_RAISE_RATE,
+                aggression: TYPICAL_AGGRESSION,
+                fold_to_bet: TYPICAL_FOLD_TO_BET,
+                showdown_rate: TYPICAL_SHOWDOWN_RATE,
+            }
+        }
+    }
+
+    impl OpponentTendencies {
+        /// Summarize the stats of the given opponents, using typical values for
+        /// any stats that there is not enough information on yet.
+        pub fn from_stats<'stats>(
+            opponents: impl IntoIterator<Item = &'stats PlayerStats>
+        ) -> Self {
+            let mut count: u32 = 0;
+            let mut looseness_sum: u32 = 0;
+            let mut raise_rate_sum: u32 = 0;
+            let mut aggression_sum: u32 = 0;
+            let mut fold_to_bet_sum: u32 = 0;
+            let mut showdown_rate_sum: u32 = 0;
+
+            for stats in opponents {
+                count += 1;
+                looseness_sum += u32::from(
+                    stats.vpip().unwrap_or(TYPICAL_LOOSENESS)
+                );
+                raise_rate_sum += u32::from(
+                    stats.pfr().unwrap_or(TYPICAL_RAISE_RATE)
+                );
+                aggression_sum += u32::from(
+                    stats.aggression_factor().unwrap_or(TYPICAL_AGGRESSION)
+                );
+                fold_to_bet_sum += u32::from(
+                    stats.fold_to_bet().unwrap_or(TYPICAL_FOLD_TO_BET)
+                );
+                showdown_rate_sum += u32::from(
+                    stats.showdown_rate().unwrap_or(TYPICAL_SHOWDOWN_RATE)
+                );
+            }
+
+            if count == 0 {
+                return Self::default()
+            }
+
+            Self {
+                looseness: (looseness_sum / count) as Probability,
+                raise_rate: (raise_rate_sum / count) as Probability,
+                aggression: (aggression_sum / count) as Probability,
+                fold_to_bet: (fold_to_bet_sum / count) as Probability,
+                showdown_rate: (showdown_rate_sum / count) as Probability,
+            }
+        }
+    }
+
+    #[derive(Clone, Copy, Debug)]
+    pub struct PostflopDecision {
+        /// The total of all the bets so far, including previous rounds.
+        pub pot_total: MoneyInner,
+        /// Whether one more card could complete a strong hand.
+        pub has_draw: bool,
+        /// Whether this player has already acted in the current round.
+        pub has_gone_this_round: bool,
+    }
+
+    /// A raise of `fraction` of the size the pot would be after a call, rounded
+    /// down to a multiple of the minimum unit, and capped by the stack.
+    fn pot_sized_raise(
+        PokerDecision {
+            call_remainder,
+            stack,
+            minimum_raise_total,
+            ..
+        }: PokerDecision,
+        pot_total: MoneyInner,
+        fraction: Probability,
+    ) -> Action {
+        let pot_after_call = pot_total.saturating_add(call_remainder);
+
+        let size = u64::from(pot_after_call) * u64::from(fraction) / u64::from(ONE);
+        let size = MoneyInner::try_from(size).unwrap_or(MoneyInner::MAX);
+        let size = size - (size % MIN_MONEY_UNIT.get());
+
+        Action::Raise(
+            core::cmp::min(
+                core::cmp::max(
+                    minimum_raise_total,
+                    call_remainder.saturating_add(size),
+                ),
+                stack
+            )
+        )
+    }
+
+    #[test]
+    fn postflop_raises_are_sized_by_the_pot_and_capped_by_the_stack() {
+        let decision = PokerDecision {
+            win_probability: ONE,
+            call_remainder: 100,
+            pot_odds: TWENTY_FIVE_PERCENT,
+            stack: 1000,
+            minimum_raise_total: 105,
+            raise_unit: MIN_MONEY_UNIT,
+            opponents: OpponentTendencies::default(),
+        };
+
+        assert!(matches!(
+            pot_sized_raise(decision, 300, FIFTY_PERCENT),
+            Action::Raise(300)
+        ));
+        assert!(matches!(
+            pot_sized_raise(decision, 3000, ONE),
+            Action::Raise(1000)
+        ));
+        assert!(matches!(
+            pot_sized_raise(decision, 0, FIFTY_PERCENT),
+            Action::Raise(150)
+        ));
+
+        let mut rng = xs::from_seed([2; 16]);
+
+        for _ in 0..16 {
+            let action = CpuPersonality::MANIAC.decide_postflop_action(
+                &mut rng,
+                decision,
+                PostflopDecision {
+                    pot_total: 3000,
+                    has_draw: false,
+                    has_gone_this_round: true,
+                },
+            );
+
+            match action {
+                Action::Raise(amount) => assert!(amount <= decision.stack),
+                Action::Call => {},
+                Action::Fold => panic!("Folded the nuts!"),
+            }
+        }
+    }
+
+    #[test]
+    fn personalities_disagree_on_a_marginal_hand_facing_a_big_bet() {
+        let decision = PokerDecision {
+            win_probability: FIFTY_PERCENT,
+            call_remainder: 400,
+            pot_odds: SEVENTY_FIVE_PERCENT,
+            stack: 500,
+            minimum_raise_total: 800,
+            raise_unit: MIN_MONEY_UNIT,
+            opponents: OpponentTendencies::default(),
+        };
+
+        let mut rng = xs::from_seed([1; 16]);
+
+        for _ in 0..16 {
+            assert!(matches!(
+                CpuPersonality::ROCK.decide_poker_action(&mut rng, decision),
+                Action::Fold
+            ));
+            assert!(!matches!(
+                CpuPersonality::CALLING_STATION.decide_poker_action(&mut rng, decision),
+                Action::Fold
+            ));
+        }
+    }
+
+    #[test]
+    fn pot_bets_grow_with_the_edge_and_the_risk_tolerance() {
+        let mut rng = xs::from_seed([3; 16]);
+
+        let bet = |rng: &mut Xs, personality: CpuPersonality, win_probability| {
+            personality.decide_pot_bet(rng, win_probability, 1000, 1000)
+                .map(|bet| bet.get())
+        };
+
+        assert_eq!(kelly_fraction(FIFTY_PERCENT), 0);
+        assert_eq!(kelly_fraction(FIFTY_PERCENT + 1), 3);
+        assert_eq!(kelly_fraction(TWENTY_FIVE_PERCENT), 0);
+        assert_eq!(kelly_fraction(ONE), ONE);
+
+        let rock_bet = bet(&mut rng, CpuPersonality::ROCK, ONE - 16).unwrap();
+        let maniac_bet = bet(&mut rng, CpuPersonality::MANIAC, ONE - 16).unwrap();
+        assert!(rock_bet < maniac_bet, "{rock_bet} >= {maniac_bet}");
+
+        let small_edge = bet(&mut rng, CpuPersonality::MANIAC, SEVENTY_FIVE_PERCENT).unwrap();
+        assert!(small_edge < maniac_bet, "{small_edge} >= {maniac_bet}");
+
+        for _ in 0..16 {
+            assert_eq!(bet(&mut rng, CpuPersonality::ROCK, TWENTY_FIVE_PERCENT), None);
+        }
+    }
+
+    #[test]
+    fn player_stats_count_each_hand_once() {
+        let mut stats = PlayerStats::default();
+
+        for _ in 0..MIN_STAT_HANDS {
+            stats.start_hand();
+            // Limp, then call a raise, then get bet into on a later round.
+            stats.record_action(&Action::Call, 10, true);
+            stats.record_action(&Action::Call, 20, true);
+            stats.record_action(&Action::Fold, 50, false);
+        }
+
+        assert_eq!(stats.vpip(), Some(ONE));
+        assert_eq!(stats.pfr(), Some(0));
+        assert_eq!(stats.aggression_factor(), Some(0));
+        assert_eq!(
+            stats.fold_to_bet(),
+            Some(EvalCount { win_count: 1, total: 3 }.probability())
+        );
+        assert_eq!(stats.showdown_rate(), Some(0));
+
+        let mut few_hands = PlayerStats::default();
+        few_hands.start_hand();
+        few_hands.record_action(&Action::Raise(100), 10, true);
+        assert_eq!(few_hands.vpip(), None);
+    }
+
+    #[test]
+    fn loose_opponents_get_called_lighter_and_tight_ones_get_bluffed_more() {
+        let mut loose = PlayerStats::default();
+        let mut tight = PlayerStats::default();
+        for _ in 0..MIN_STAT_HANDS {
+            loose.start_hand();
+            loose.record_action(&Action::Raise(100), 10, true);
+
+            tight.start_hand();
+            tight.record_action(&Action::Fold, 10, true);
+        }
+
+        let against_loose = OpponentTendencies::from_stats([&loose]);
+        let against_tight = OpponentTendencies::from_stats([&tight]);
+        let unknown = OpponentTendencies::from_stats([]);
+
+        let personality = CpuPersonality::SOLID;
+
+        assert!(personality.call_threshold(against_loose) < personality.call_threshold(unknown));
+        assert_eq!(personality.call_threshold(unknown), personality.play_threshold());
+        assert!(
+            personality.bluff_frequency_against(against_tight)
+            > personality.bluff_frequency_against(unknown)
+        );
+        assert_eq!(CpuPersonality::ROCK.bluff_frequency_against(against_tight), 0);
+    }
+
+    #[test]
+    fn passive_opponents_get_called_tighter_and_bluffed_less() {
+        // Only calls bets after the first round, and sees every hand through.
+        let mut passive = PlayerStats::default();
+        for _ in 0..MIN_STAT_HANDS {
+            passive.start_hand();
+            passive.record_action(&Action::Call, 10, false);
+            passive.record_showdown();
+        }
+
+        let against_passive = OpponentTendencies::from_stats([&passive]);
+        let unknown = OpponentTendencies::from_stats([]);
+
+        assert_eq!(against_passive.aggression, 0);
+        assert_eq!(against_passive.showdown_rate, ONE);
+
+        let personality = CpuPersonality::SOLID;
+
+        assert!(personality.call_threshold(against_passive) > personality.call_threshold(unknown));
+        assert!(
+            personality.call_threshold(OpponentTendencies { raise_rate: ONE, ..unknown })
+            < personality.call_threshold(unknown)
+        );
+        assert!(
+            personality.call_threshold(OpponentTendencies { aggression: ONE, ..unknown })
+            < personality.call_threshold(unknown)
+        );
+
+        assert!(
+            personality.bluff_frequency_against(OpponentTendencies { showdown_rate: ONE - 16, ..unknown })
+            < personality.bluff_frequency_against(unknown)
+        );
+        assert_eq!(
+            personality.bluff_frequency_against(OpponentTendencies { showdown_rate: ONE, ..unknown }),
+            0
+        );
+    }
+
+    #[derive(Clone, Copy, Default, PartialEq)]
+    pub enum SkipState {
+        #[default]
+        Watch,
+        Skip,
+        /// Skip was chosen and only CPU players are left, so the rest of the
+        /// game is being played out headlessly, a batch of frames at a time.
+        Simulating,
+    }
+
+    impl SkipState {
+        /// Starts simulating once skip was chosen and there are only CPU
+        /// players left to play.
+        pub fn simulate_if_only_cpus_left(&mut self, only_cpus_left: bool) {
+            if only_cpus_left && *self == SkipState::Skip {
+                // Nobody is left to watch, so play the rest of the game out
+                // without showing it.
+                *self = SkipState::Simulating;
+            }
+        }
+    }
+
+    #[derive(Clone, Copy, Default, PartialEq, Eq)]
+    pub enum ModeCmd {
+        #[default]
+        NoOp,
+        BackToTitleScreen,
+        // Relevant for dealer's choice mode
+        FinishedRound,
+    }
+
+    /// How one player did over a whole game.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct SeatStanding {
+        pub is_user: bool,
+        pub starting_money: MoneyInner,
+        /// How much the player had after the last hand they were in.
+        pub money: MoneyInner,
+        /// The most the player took from a single pot.
+        pub biggest_pot: MoneyInner,
+        /// The amount of hands that had been played when the player ran out of
+        /// money, if they did.
+        pub out_on_hand: Option<HandCount>,
+        /// How much the player had going into the hand they ran out of money
+        /// on, for ordering players who ran out on the same hand.
+        pub money_before_out: MoneyInner,
+    }
+
+    impl SeatStanding {
+        pub fn net(&self) -> i64 {
+            i64::from(self.money) - i64::from(self.starting_money)
+        }
+    }
+
+    /// How each player did over a whole game, indexed by `StartingSeat`, for
+    /// the results screen at the end of it.
+    #[derive(Clone, Debug)]
+    pub struct Standings {
+        pub player_count: PlayerCount,
+        pub seats: PerPlayer<SeatStanding>,
+        /// How each seat was set up, so the same game can be set up again.
+        pub setups: SeatSetups,
+    }
+
+    impl Default for Standings {
+        fn default() -> Self {
+            Self {
+                player_count: <_>::default(),
+                seats: <_>::default(),
+                setups: default_seat_setups(0),
+            }
+        }
+    }
 
-    pub type Personality = Option<CpuPersonality>;
+    impl Standings {
+        /// Expected to be called with the seats as they are before any blinds
+        /// or antes are collected.
+        pub fn new(moneys: &[Money], setups: &[SeatSetup]) -> Self {
+            let mut standings = Self::default();
+
+            for (i, (money, setup)) in moneys.iter()
+                .zip(setups.iter())
+                .take(standings.seats.len())
+                .enumerate() {
+                if *money == 0 {
+                    continue
+                }
 
-    #[derive(Clone, Debug)]
-    pub struct CpuPersonality {
-        // TODO
-    }
+                standings.player_count = i as PlayerCount + 1;
+                standings.setups[i] = *setup;
+                standings.seats[i] = SeatStanding {
+                    is_user: setup.is_human,
+                    starting_money: money.as_inner(),
+                    money: money.as_inner(),
+                    ..<_>::default()
+                };
+            }
 
-    #[derive(Clone, Default, PartialEq)]
-    pub enum SkipState {
-        #[default]
-        Watch,
-        Skip,
+            standings
+        }
+
+        /// The name of the player in `seat`, to show in place of their seat
+        /// number.
+        pub fn name(&self, seat: StartingSeat) -> SeatName {
+            self.setups.get(usize::from(seat))
+                .map_or(SeatName::for_seat(seat), |setup| setup.name)
+        }
+
+        pub fn record_pot_won(&mut self, seat: StartingSeat, amount: MoneyInner) {
+            if let Some(standing) = self.seats.get_mut(usize::from(seat)) {
+                standing.biggest_pot = core::cmp::max(standing.biggest_pot, amount);
+            }
+        }
+
+        /// Records how much each player has after `hands_played` hands, given
+        /// the seat and stack of each player. Players that are not included,
+        /// or that have no money, are counted as out of the game.
+        pub fn record_stacks(
+            &mut self,
+            hands_played: HandCount,
+            stacks: impl Iterator<Item = (StartingSeat, MoneyInner)>,
+        ) {
+            let mut still_in = PerPlayer::<bool>::default();
+            for (seat, money) in stacks {
+                let i = usize::from(seat);
+                if money == 0 || i >= self.seats.len() {
+                    continue
+                }
+
+                still_in[i] = true;
+                self.seats[i].money = money;
+            }
+
+            for (i, standing) in self.seats[..usize::from(self.player_count)]
+                .iter_mut()
+                .enumerate() {
+                if still_in[i] || standing.starting_money == 0 {
+                    continue
+                }
+
+                if standing.out_on_hand.is_none() {
+                    standing.out_on_hand = Some(hands_played);
+                    standing.money_before_out = standing.money;
+                }
+                standing.money = 0;
+            }
+        }
+
+        /// The seats from first place to last, and how many there are. Players
+        /// who never ran out of money place above those who did, ordered by how
+        /// much they have. The rest are ordered by how long they lasted, and
+        /// then by how much they had going into the hand they ran out on.
+        pub fn finishing_order(&self) -> (PerPlayer<StartingSeat>, usize) {
+            let mut order = PerPlayer::<StartingSeat>::default();
+            let mut len = 0;
+            for (i, standing) in self.seats[..usize::from(self.player_count)]
+                .iter()
+                .enumerate() {
+                if standing.starting_money == 0 {
+                    continue
+                }
+                order[len] = i as StartingSeat;
+                len += 1;
+            }
+
+            order[..len].sort_by_key(|&seat| {
+                let standing = &self.seats[usize::from(seat)];
+                core::cmp::Reverse((
+                    standing.out_on_hand.unwrap_or(HandCount::MAX),
+                    standing.money,
+                    standing.money_before_out,
+                ))
+            });
+
+            (order, len)
+        }
     }
 
-    #[derive(Clone, Copy, Default, PartialEq, Eq)]
-    pub enum ModeCmd {
-        #[default]
-        NoOp,
-        BackToTitleScreen,
-        // Relevant for dealer's choice mode
-        FinishedRound,
+    #[test]
+    fn standings_are_ordered_by_money_then_by_how_long_players_lasted() {
+        let moneys = Money::array_from_inner_array([100, 100, 100, 100]);
+        let mut setups = default_seat_setups(100);
+        setups[2].name.char_down(2);
+
+        let mut standings = Standings::new(&moneys, &setups[..4]);
+        assert_eq!(standings.player_count, 4);
+        assert!(standings.seats[0].is_user);
+        assert!(!standings.seats[1].is_user);
+        assert_eq!(standings.setups[2].name.as_bytes(), b"p2a");
+        assert_eq!(standings.name(2).as_bytes(), b"p2a");
+        assert_eq!(standings.name(3).as_bytes(), b"p3");
+
+        standings.record_pot_won(2, 50);
+        standings.record_pot_won(2, 30);
+        standings.record_stacks(3, [(0, 150), (2, 150), (3, 100)].into_iter());
+        assert_eq!(standings.seats[1].out_on_hand, Some(3));
+        assert_eq!(standings.seats[2].biggest_pot, 50);
+
+        standings.record_stacks(7, [(2, 400)].into_iter());
+        assert_eq!(standings.seats[1].out_on_hand, Some(3));
+        assert_eq!(standings.seats[0].out_on_hand, Some(7));
+        assert_eq!(standings.seats[2].net(), 300);
+        assert_eq!(standings.seats[0].net(), -100);
+
+        let (order, len) = standings.finishing_order();
+        assert_eq!(len, 4);
+        assert_eq!(order[0], 2);
+        assert_eq!(order[3], 1);
+
+        // Seats 0 and 1 both go out on the same hand, but seat 1 had more
+        // going into it, so places higher.
+        let mut standings = Standings::new(&moneys, &setups[..4]);
+        standings.record_stacks(1, [(0, 50), (1, 150), (2, 100), (3, 100)].into_iter());
+        standings.record_stacks(2, [(2, 200), (3, 200)].into_iter());
+        assert_eq!(standings.seats[0].money_before_out, 50);
+        assert_eq!(standings.seats[1].money_before_out, 150);
+
+        let (order, len) = standings.finishing_order();
+        assert_eq!(len, 4);
+        assert_eq!(&order[2..4], &[1, 0]);
     }
 }
 use shared_game_types::{ModeCmd};
+pub use shared_game_types::{FRAMES_PER_SECOND, LevelSchedule};
 
 mod dealers_choice;
 
@@ -51,6 +1446,15 @@ mod holdem;
 mod acey_deucey;
 
 mod five_card_draw;
+pub use five_card_draw::{OpeningRequirement, SecondRoundStart};
+
+mod omaha;
+
+mod seven_card_stud;
+
+pub mod headless;
+
+pub mod strategy;
 
 macro_rules! all_up_down_impl {
     ($item_name: ident) => {
@@ -75,7 +1479,17 @@ macro_rules! all_up_down_impl {
 
                 Self::ALL[index]
             }
+        }
+
+        index_of_impl!{
+            $item_name
+        }
+    }
+}
 
+macro_rules! index_of_impl {
+    ($item_name: ident) => {
+        impl $item_name {
             fn index_of(self) -> usize {
                 let mut i = 0;
                 for game in Self::ALL {
@@ -161,6 +1575,12 @@ macro_rules! mode_def {
                     $mode_name::FiveCardDraw => {
                         $mode::FiveCardDraw(<_>::default())
                     },
+                    $mode_name::Omaha => {
+                        $mode::Omaha(<_>::default())
+                    },
+                    $mode_name::SevenCardStud => {
+                        $mode::SevenCardStud(<_>::default())
+                    },
                 }
             }
         }
@@ -220,7 +1640,7 @@ macro_rules! mode_def {
             ];
         }
 
-        all_up_down_impl!{
+        index_of_impl!{
             $sub_game
         }
 
@@ -255,6 +1675,8 @@ mode_def!{
         Holdem => ("texas hold'em", holdem),
         AceyDeucey => ("acey-deucey", acey_deucey),
         FiveCardDraw => ("five-card draw", five_card_draw),
+        Omaha => ("omaha", omaha),
+        SevenCardStud => ("seven-card stud", seven_card_stud),
     ]
 }
 
@@ -387,6 +1809,9 @@ impl State {
 
 mod ui {
     use super::*;
+    use crate::shared_game_types::{HotSeat, Levels, Personality, SeatEditor, SeatName, SeatSetups, Standings, StartingSeat, UntilNextLevel, human_count};
+    use models::MIN_MONEY_UNIT;
+    use platform_types::YELLOW_INDEX;
 
     /// A group of things that are used together to render UI. Naming suggestions
     /// welcome!
@@ -409,8 +1834,28 @@ mod ui {
         GameSelect,
         BackToTitleScreen,
         Submit,
+        PlayAgain,
         PlayerCountSelect,
+        EditSeats,
+        SeatSelect,
+        SeatKindSelect,
+        CpuPresetSelect,
+        SeatNameSelect,
+        SeatMoneySelect,
+        SeatsDone,
         StartingMoneySelect,
+        BettingStructureSelect,
+        DeckCompositionSelect,
+        WildCardsSelect,
+        OpeningRequirementSelect,
+        SecondRoundStartSelect,
+        LevelScheduleSelect,
+        RotationSelect,
+        MaxBetSelect,
+        PairRuleSelect,
+        PairBurnSelect,
+        ConnectorsBurnSelect,
+        ReAnteSelect,
         HoldemHand(holdem::HandIndex),
         HoldemMenu(HoldemMenuId),
         HoldemChartButton,
@@ -422,9 +1867,11 @@ mod ui {
         HighLowSelect,
         HighLowSubmit,
         AcknowledgeCPUPass,
+        PassDevice,
         FiveCardDrawHand(five_card_draw::HandIndex),
         FiveCardDrawMenu(FiveCardDrawMenuId),
         SubGameCheckbox(SubGame),
+        SubGameChoice(SubGame),
     }
 
     #[derive(Copy, Clone, Default, Debug)]
@@ -611,9 +2058,559 @@ mod ui {
     }
 
     pub use _draw_money_in_rect as draw_money_in_rect;
+
+    /// Draws the current level, and how long until the next one, as two lines
+    /// starting at the given position. Draws nothing if the levels never change.
+    pub(crate) fn draw_levels(
+        commands: &mut Commands,
+        levels: &Levels,
+        x: unscaled::X,
+        y: unscaled::Y,
+    ) {
+        use std::io::Write;
+
+        if let LevelSchedule::Flat = levels.schedule {
+            return
+        }
+
+        let mut level_text = [0u8; 16];
+        let _cant_actually_fail = write!(
+            &mut level_text[..],
+            "level {}",
+            u16::from(levels.level) + 1,
+        );
+
+        commands.print_chars(&level_text, x, y, TEXT);
+
+        let mut until_text = [0u8; 24];
+        let _cant_actually_fail = match levels.until_next() {
+            None => return,
+            Some(UntilNextLevel::Hands(hands)) => write!(
+                &mut until_text[..],
+                "next in {hands} hands",
+            ),
+            Some(UntilNextLevel::Frames(frames)) => {
+                let seconds = frames / FRAMES_PER_SECOND;
+                write!(
+                    &mut until_text[..],
+                    "next in {}:{:02}",
+                    seconds / 60,
+                    seconds % 60,
+                )
+            },
+        };
+
+        commands.print_chars(&until_text, x, y + gfx::CHAR_LINE_ADVANCE, TEXT);
+    }
+
+    /// Draws an editor for how each of the first `player_count` seats is set
+    /// up, over the whole screen: who controls the seat, the CPU preset, the
+    /// name and the starting stack. Sets `editor_opt` to `None` once done.
+    pub(crate) fn do_seat_editor<'commands, 'ctx, 'speaker>(
+        group: &mut Group<'commands, 'ctx, 'speaker>,
+        setups: &mut SeatSetups,
+        player_count: PlayerCount,
+        editor_opt: &mut Option<SeatEditor>,
+    ) {
+        use std::io::Write;
+
+        let editor = match editor_opt {
+            Some(editor) => editor,
+            None => return,
+        };
+
+        const SCREEN_RECT: unscaled::Rect = unscaled::Rect {
+            x: unscaled::X(0),
+            y: unscaled::Y(0),
+            w: command::WIDTH_W,
+            h: command::HEIGHT_H,
+        };
+
+        const COLUMN_XS: [unscaled::X; 2] = [unscaled::X(16), unscaled::X(248)];
+        const ROWS_PER_COLUMN: usize = (OVERALL_MAX_PLAYER_COUNT as usize).div_ceil(2);
+        const NAME_OFFSET: unscaled::W = unscaled::W(20);
+        const KIND_OFFSET: unscaled::W = unscaled::W(60);
+        const MONEY_OFFSET: unscaled::W = unscaled::W(92);
+
+        let player_count = core::cmp::max(player_count, 1);
+        if editor.seat >= player_count {
+            *editor = SeatEditor::default();
+        }
+
+        group.commands.draw_nine_slice(gfx::NineSlice::Window, SCREEN_RECT);
+
+        let list_y = unscaled::Y(0) + SPACING_H * 2;
+        group.commands.print_chars(b"seats", COLUMN_XS[0], list_y, TEXT);
+
+        for (i, setup) in setups[..usize::from(player_count)].iter().enumerate() {
+            let x = COLUMN_XS[(i / ROWS_PER_COLUMN) % COLUMN_XS.len()];
+            let y = list_y + gfx::CHAR_LINE_ADVANCE * (2 + (i % ROWS_PER_COLUMN)) as unscaled::Inner;
+
+            let colour = if i == usize::from(editor.seat) {
+                YELLOW_INDEX
+            } else {
+                TEXT
+            };
+
+            let mut seat_text = [0u8; 4];
+            let _cant_actually_fail = write!(&mut seat_text[..], "p{i}");
+            group.commands.print_chars(&seat_text, x, y, colour);
+
+            group.commands.print_chars(setup.name.as_bytes(), x + NAME_OFFSET, y, colour);
+
+            let kind_text = if setup.is_human {
+                b"human"
+            } else {
+                setup.preset.text()
+            };
+            group.commands.print_chars(kind_text, x + KIND_OFFSET, y, colour);
+
+            stack_money_text!(money_text = setup.starting_money);
+            group.commands.print_chars(&money_text, x + MONEY_OFFSET, y, colour);
+        }
+
+        let seat_i = usize::from(editor.seat);
+        let setup = &mut setups[seat_i];
+
+        let seat_rect = unscaled::Rect {
+            x: unscaled::X(40),
+            y: unscaled::Y(170),
+            w: unscaled::W(50),
+            h: unscaled::H(100),
+        };
+
+        macro_rules! draw_labeled {
+            ($text: expr, $label: literal, $rect: expr) => ({
+                let text: &[u8] = $text;
+                let rect = $rect;
+
+                let xy = gfx::center_line_in_rect(
+                    gfx::pre_nul_len(text),
+                    rect,
+                );
+                group.commands.print_chars(text, xy.x, xy.y, TEXT);
+
+                let label: &[u8] = $label;
+                let label_xy = gfx::center_line_in_rect(
+                    label.len() as _,
+                    rect,
+                );
+                group.commands.print_chars(label, label_xy.x, label_xy.y + gfx::CHAR_H, TEXT);
+
+                xy
+            })
+        }
+
+        let mut seat_text = [0u8; 4];
+        let _cant_actually_fail = write!(&mut seat_text[..], "p{seat_i}");
+        draw_labeled!(&seat_text, b"seat", seat_rect);
+        draw_quick_select(group, seat_rect, SeatSelect);
+
+        let kind_rect = unscaled::Rect {
+            x: seat_rect.x + seat_rect.w,
+            ..seat_rect
+        };
+        draw_labeled!(if setup.is_human { b"human" } else { b"cpu" }, b"player", kind_rect);
+        draw_quick_select(group, kind_rect, SeatKindSelect);
+
+        let preset_rect = unscaled::Rect {
+            x: kind_rect.x + kind_rect.w,
+            ..kind_rect
+        };
+        draw_labeled!(if setup.is_human { b"-" } else { setup.preset.text() }, b"style", preset_rect);
+        draw_quick_select(group, preset_rect, CpuPresetSelect);
+
+        let name_rect = unscaled::Rect {
+            x: preset_rect.x + preset_rect.w,
+            ..preset_rect
+        };
+        let name_xy = draw_labeled!(setup.name.chars(), b"name", name_rect);
+        draw_quick_select(group, name_rect, SeatNameSelect);
+        if group.ctx.hot == SeatNameSelect {
+            group.commands.print_chars(
+                b"_",
+                name_xy.x + gfx::CHAR_ADVANCE * unscaled::Inner::from(editor.name_cursor),
+                name_xy.y,
+                YELLOW_INDEX,
+            );
+        }
+
+        let money_rect = unscaled::Rect {
+            x: name_rect.x + name_rect.w,
+            ..name_rect
+        };
+        stack_money_text!(money_text = setup.starting_money);
+        draw_labeled!(&money_text, b"stack", money_rect);
+        draw_quick_select(group, money_rect, SeatMoneySelect);
+
+        if do_button(
+            group,
+            ButtonSpec {
+                id: SeatsDone,
+                rect: unscaled::Rect {
+                    x: money_rect.x + money_rect.w,
+                    ..money_rect
+                },
+                text: b"done",
+            }
+        ) {
+            *editor_opt = None;
+            group.ctx.set_next_hot(EditSeats);
+            return
+        }
+
+        let menu = [SeatSelect, SeatKindSelect, CpuPresetSelect, SeatNameSelect, SeatMoneySelect, SeatsDone];
+
+        let menu_i = match menu.iter().position(|&id| id == group.ctx.hot) {
+            Some(menu_i) => menu_i,
+            None => {
+                group.ctx.set_next_hot(SeatSelect);
+                return
+            }
+        };
+
+        match group.input.dir_pressed_this_frame() {
+            Some(Dir::Left) => {
+                if menu_i > 0 {
+                    group.ctx.set_next_hot(menu[menu_i - 1]);
+                }
+                return
+            }
+            Some(Dir::Right) => {
+                if menu_i + 1 < menu.len() {
+                    group.ctx.set_next_hot(menu[menu_i + 1]);
+                }
+                return
+            }
+            _ => {}
+        }
+
+        let dir = group.input.dir_pressed_this_frame();
+
+        match menu[menu_i] {
+            SeatSelect => match dir {
+                Some(Dir::Up) => {
+                    editor.seat = (editor.seat + 1) % player_count;
+                    editor.name_cursor = 0;
+                },
+                Some(Dir::Down) => {
+                    editor.seat = editor.seat.checked_sub(1).unwrap_or(player_count - 1);
+                    editor.name_cursor = 0;
+                },
+                _ => {}
+            },
+            SeatKindSelect => {
+                if let Some(Dir::Up | Dir::Down) = dir {
+                    setup.is_human = !setup.is_human;
+                }
+            },
+            CpuPresetSelect => match dir {
+                Some(Dir::Up) => {
+                    setup.preset = setup.preset.next_up();
+                },
+                Some(Dir::Down) => {
+                    setup.preset = setup.preset.next_down();
+                },
+                _ => {}
+            },
+            SeatNameSelect => {
+                let cursor = usize::from(editor.name_cursor);
+                match dir {
+                    Some(Dir::Up) => {
+                        setup.name.char_up(cursor);
+                    },
+                    Some(Dir::Down) => {
+                        setup.name.char_down(cursor);
+                    },
+                    _ => {}
+                }
+
+                // A and B move along the name, since left and right move
+                // between the options.
+                if group.input.pressed_this_frame(Button::A) {
+                    editor.name_cursor = ((cursor + 1) % SeatName::LEN) as u8;
+                } else if group.input.pressed_this_frame(Button::B) {
+                    editor.name_cursor = cursor.checked_sub(1)
+                        .unwrap_or(SeatName::LEN - 1) as u8;
+                }
+            },
+            SeatMoneySelect => match dir {
+                Some(Dir::Up) => {
+                    setup.starting_money = setup.starting_money.saturating_add(MIN_MONEY_UNIT.get());
+                },
+                Some(Dir::Down) => {
+                    setup.starting_money = core::cmp::max(
+                        setup.starting_money.saturating_sub(MIN_MONEY_UNIT.get()),
+                        MIN_MONEY_UNIT.get(),
+                    );
+                },
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+
+    /// Covers the whole screen, so the table can't be seen, and asks for the
+    /// device to be passed to the player with the given name. Returns whether
+    /// that player has confirmed that they have it.
+    pub(crate) fn do_pass_device<'commands, 'ctx, 'speaker>(
+        group: &mut Group<'commands, 'ctx, 'speaker>,
+        name: &SeatName,
+    ) -> bool {
+        use std::io::Write;
+
+        const SCREEN_RECT: unscaled::Rect = unscaled::Rect {
+            x: unscaled::X(0),
+            y: unscaled::Y(0),
+            w: command::WIDTH_W,
+            h: command::HEIGHT_H,
+        };
+
+        group.commands.draw_nine_slice(gfx::NineSlice::Window, SCREEN_RECT);
+
+        let mut pass_text = [0u8; 32];
+        let _cant_actually_fail = write!(&mut pass_text[..], "pass the device to {name}");
+
+        let xy = gfx::center_line_in_rect(
+            gfx::pre_nul_len(&pass_text),
+            SCREEN_RECT,
+        );
+        group.commands.print_chars(&pass_text, xy.x, xy.y - gfx::CHAR_LINE_ADVANCE, TEXT);
+
+        let w = unscaled::W(70);
+        let h = unscaled::H(20);
+
+        group.ctx.set_next_hot(PassDevice);
+
+        let passed = do_button(
+            group,
+            ButtonSpec {
+                id: PassDevice,
+                rect: unscaled::Rect {
+                    x: unscaled::X(0) + ((command::WIDTH_W / 2) - (w / 2)),
+                    y: xy.y + gfx::CHAR_LINE_ADVANCE,
+                    w,
+                    h,
+                },
+                text: b"ready",
+            }
+        );
+
+        if passed {
+            group.ctx.set_next_hot(Zero);
+        }
+
+        passed
+    }
+
+    /// Shows the screen asking for the device to be passed to the human
+    /// about to act, in place of the table, if someone else had it. `acting`
+    /// indexes `personalities`, which should only cover the hands being
+    /// played, and sits in `acting_seat`. Returns whether that screen was
+    /// shown.
+    pub(crate) fn do_pass_device_if_needed<'commands, 'ctx, 'speaker>(
+        group: &mut Group<'commands, 'ctx, 'speaker>,
+        acting: HandIndex,
+        acting_seat: StartingSeat,
+        personalities: &[Personality],
+        hot_seat: &mut HotSeat,
+        setups: &SeatSetups,
+    ) -> bool {
+        if personalities[usize::from(acting)].is_some()
+        || !hot_seat.needs_pass(acting_seat, human_count(personalities)) {
+            return false
+        }
+
+        if do_pass_device(group, &setups[usize::from(acting_seat)].name) {
+            hot_seat.pass_to(acting_seat);
+        }
+
+        true
+    }
+
+    /// Draws the level schedule as an option that can be selected between,
+    /// in the style of the other options on the setup screens.
+    pub(crate) fn draw_level_schedule_in_rect(
+        commands: &mut Commands,
+        schedule: LevelSchedule,
+        rect: unscaled::Rect,
+    ) {
+        use std::io::Write;
+
+        let mut schedule_text = [0u8; 16];
+        let _cant_actually_fail = write!(&mut schedule_text[..], "{schedule}");
+
+        let xy = gfx::center_line_in_rect(
+            gfx::pre_nul_len(&schedule_text),
+            rect,
+        );
+        commands.print_chars(&schedule_text, xy.x, xy.y, TEXT);
+
+        let label: &[u8] = match schedule {
+            LevelSchedule::Flat => b"levels",
+            LevelSchedule::Hands(_)
+            | LevelSchedule::Frames(_) => b"per level",
+        };
+
+        let xy = gfx::center_line_in_rect(
+            label.len() as _,
+            rect,
+        );
+        commands.print_chars(label, xy.x, xy.y + gfx::CHAR_H, TEXT);
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub(crate) enum StandingsCmd {
+        NoOp,
+        PlayAgain,
+        BackToTitleScreen,
+    }
+
+    /// Draws how each player did over a whole game, from first place to last,
+    /// over the whole screen, with buttons to play again with the same settings,
+    /// or to go back to the title screen.
+    pub(crate) fn do_standings<'commands, 'ctx, 'speaker>(
+        group: &mut Group<'commands, 'ctx, 'speaker>,
+        standings: &Standings,
+    ) -> StandingsCmd {
+        use std::io::Write;
+
+        const SCREEN_RECT: unscaled::Rect = unscaled::Rect {
+            x: unscaled::X(0),
+            y: unscaled::Y(0),
+            w: command::WIDTH_W,
+            h: command::HEIGHT_H,
+        };
+
+        const PLACE_X: unscaled::X = unscaled::X(16);
+        const PLAYER_X: unscaled::X = unscaled::X(56);
+        const OUT_ON_X: unscaled::X = unscaled::X(136);
+        const BIGGEST_POT_X: unscaled::X = unscaled::X(236);
+        const NET_X: unscaled::X = unscaled::X(336);
+
+        group.commands.draw_nine_slice(gfx::NineSlice::Window, SCREEN_RECT);
+
+        let mut y = unscaled::Y(0) + SPACING_H * 2;
+
+        group.commands.print_chars(b"final standings", PLACE_X, y, TEXT);
+        y += gfx::CHAR_LINE_ADVANCE * 2;
+
+        group.commands.print_chars(b"place", PLACE_X, y, TEXT);
+        group.commands.print_chars(b"player", PLAYER_X, y, TEXT);
+        group.commands.print_chars(b"out on hand", OUT_ON_X, y, TEXT);
+        group.commands.print_chars(b"biggest pot", BIGGEST_POT_X, y, TEXT);
+        group.commands.print_chars(b"net", NET_X, y, TEXT);
+        y += gfx::CHAR_LINE_ADVANCE;
+
+        let human_count = standings.seats.iter()
+            .filter(|standing| standing.is_user)
+            .count();
+
+        let (order, len) = standings.finishing_order();
+        for (place_i, &seat) in order[..len].iter().enumerate() {
+            let standing = &standings.seats[usize::from(seat)];
+
+            let mut place_text = [0u8; 8];
+            let _cant_actually_fail = write!(&mut place_text[..], "{}", place_i + 1);
+            group.commands.print_chars(&place_text, PLACE_X, y, TEXT);
+
+            let name = &standings.setups[usize::from(seat)].name;
+
+            let mut player_text = [0u8; 24];
+            let _cant_actually_fail = if standing.is_user && human_count > 1 {
+                write!(&mut player_text[..], "{name} (human)")
+            } else if standing.is_user {
+                write!(&mut player_text[..], "{name} (you)")
+            } else {
+                write!(&mut player_text[..], "{name}")
+            };
+            group.commands.print_chars(&player_text, PLAYER_X, y, TEXT);
+
+            let mut out_on_text = [0u8; 16];
+            let _cant_actually_fail = match standing.out_on_hand {
+                Some(hand) => write!(&mut out_on_text[..], "{hand}"),
+                None => write!(&mut out_on_text[..], "-"),
+            };
+            group.commands.print_chars(&out_on_text, OUT_ON_X, y, TEXT);
+
+            if standing.biggest_pot == 0 {
+                group.commands.print_chars(b"-", BIGGEST_POT_X, y, TEXT);
+            } else {
+                stack_money_text!(biggest_pot_text = standing.biggest_pot);
+                group.commands.print_chars(&biggest_pot_text, BIGGEST_POT_X, y, TEXT);
+            }
+
+            let net = standing.net();
+            let mut net_text = [0u8; 24];
+            let _cant_actually_fail = match net.cmp(&0) {
+                core::cmp::Ordering::Greater => write!(&mut net_text[..], "+${net}"),
+                core::cmp::Ordering::Equal => write!(&mut net_text[..], "$0"),
+                core::cmp::Ordering::Less => write!(&mut net_text[..], "-${}", net.unsigned_abs()),
+            };
+            group.commands.print_chars(&net_text, NET_X, y, TEXT);
+
+            y += gfx::CHAR_LINE_ADVANCE;
+        }
+
+        let w = unscaled::W(70);
+        let h = unscaled::H(20);
+        let button_y = unscaled::Y(0) + (command::HEIGHT_H - (h + SPACING_H));
+
+        let play_again_rect = unscaled::Rect {
+            x: unscaled::X(0) + ((command::WIDTH_W / 2) - (w + gfx::SPACING_W)),
+            y: button_y,
+            w,
+            h,
+        };
+        let back_rect = unscaled::Rect {
+            x: unscaled::X(0) + ((command::WIDTH_W / 2) + gfx::SPACING_W),
+            ..play_again_rect
+        };
+
+        if do_button(
+            group,
+            ButtonSpec {
+                id: PlayAgain,
+                rect: play_again_rect,
+                text: b"play again",
+            }
+        ) {
+            return StandingsCmd::PlayAgain
+        }
+
+        if do_button(
+            group,
+            ButtonSpec {
+                id: BackToTitleScreen,
+                rect: back_rect,
+                text: b"back to title",
+            }
+        ) {
+            return StandingsCmd::BackToTitleScreen
+        }
+
+        match group.ctx.hot {
+            PlayAgain => {
+                if let Some(Dir::Right) = group.input.dir_pressed_this_frame() {
+                    group.ctx.set_next_hot(BackToTitleScreen);
+                }
+            }
+            BackToTitleScreen => {
+                if let Some(Dir::Left) = group.input.dir_pressed_this_frame() {
+                    group.ctx.set_next_hot(PlayAgain);
+                }
+            }
+            _ => {
+                group.ctx.set_next_hot(PlayAgain);
+            }
+        }
+
+        StandingsCmd::NoOp
+    }
 }
 
 use ui::{ButtonSpec, Id::*, do_button};
+use headless::CpuTable;
 
 pub fn update_and_render(
     commands: &mut Commands,
@@ -658,37 +2655,51 @@ pub fn update_and_render(
             );
         }
         Mode::Holdem(table) => {
-            cmd = holdem::update_and_render(
+            cmd = headless::update_or_simulate(
                 commands,
-                holdem::State {
-                    rng: &mut state.rng,
-                    ctx: &mut state.ctx,
-                    table,
-                },
+                &mut state.rng,
+                &mut state.ctx,
+                table,
                 input,
                 speaker,
             );
         }
         Mode::AceyDeucey(table) => {
-            cmd = acey_deucey::update_and_render(
+            cmd = headless::update_or_simulate(
                 commands,
-                acey_deucey::State {
-                    rng: &mut state.rng,
-                    ctx: &mut state.ctx,
-                    table,
-                },
+                &mut state.rng,
+                &mut state.ctx,
+                table,
                 input,
                 speaker,
             );
         }
         Mode::FiveCardDraw(table) => {
-            cmd = five_card_draw::update_and_render(
+            cmd = headless::update_or_simulate(
                 commands,
-                five_card_draw::State {
-                    rng: &mut state.rng,
-                    ctx: &mut state.ctx,
-                    table,
-                },
+                &mut state.rng,
+                &mut state.ctx,
+                table,
+                input,
+                speaker,
+            );
+        }
+        Mode::Omaha(table) => {
+            cmd = headless::update_or_simulate(
+                commands,
+                &mut state.rng,
+                &mut state.ctx,
+                table,
+                input,
+                speaker,
+            );
+        }
+        Mode::SevenCardStud(table) => {
+            cmd = headless::update_or_simulate(
+                commands,
+                &mut state.rng,
+                &mut state.ctx,
+                table,
                 input,
                 speaker,
             );
@@ -902,6 +2913,12 @@ fn title_update_and_render(
                             "five-card-draw" => {
                                 cmd = TitleCmd::StartMode(ModeName::FiveCardDraw);
                             }
+                            "omaha" => {
+                                cmd = TitleCmd::StartMode(ModeName::Omaha);
+                            }
+                            "seven-card-stud" => {
+                                cmd = TitleCmd::StartMode(ModeName::SevenCardStud);
+                            }
                             "dealers-choice" => {
                                 cmd = TitleCmd::StartMode(ModeName::DealersChoice);
                             }
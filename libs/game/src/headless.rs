@@ -0,0 +1,727 @@
+//! Running tables where every seat is a CPU player, without showing anything,
+//! so the game logic can be exercised in bulk, and the CPU players tuned.
+
+use gfx::Commands;
+use models::{BettingStructure, DeckComposition, Money, MoneyInner, PerPlayer, PlayerCount, WildCards};
+use platform_types::{Button, Input, Speaker, command, unscaled, TEXT};
+
+use xs::{Seed, Xs};
+
+use crate::{acey_deucey, five_card_draw, holdem, omaha, seven_card_stud, OpeningRequirement, SecondRoundStart, SubGame};
+use crate::shared_game_types::{HandCount, LevelSchedule, ModeCmd, SkipState, StartingSeat, default_seat_setups};
+use crate::ui;
+
+/// If this many frames go by without a hand finishing, then the game is
+/// considered stuck.
+pub const MAX_FRAMES_PER_HAND: u32 = 4096;
+
+/// The most games `simulate` will play, whether or not the hand count has been
+/// reached by then.
+pub const MAX_GAMES: GameIndex = 1 << 16;
+
+pub const DEFAULT_STARTING_MONEY: MoneyInner = 500;
+
+/// How many frames of a game that only has CPU players left to play out each
+/// time `update_simulating` is called. The CPU players can take a while to
+/// decide, so this is kept low enough that the UI stays responsive.
+pub const SIMULATED_FRAMES_PER_FRAME: u32 = 8;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Spec {
+    pub seed: Seed,
+    pub sub_game: SubGame,
+    pub player_count: PlayerCount,
+    /// The total amount of hands to play, across as many games as that takes.
+    pub hand_count: HandCount,
+    pub starting_money: MoneyInner,
+    /// Ignored by games without betting rounds, like acey-deucey, and games
+    /// that only have one betting structure, like fixed-limit seven-card stud.
+    pub betting_structure: BettingStructure,
+    /// Only used by hold'em and Omaha.
+    pub deck_composition: DeckComposition,
+    /// Only used by five-card draw.
+    pub wilds: WildCards,
+    /// Only used by five-card draw.
+    pub opening: OpeningRequirement,
+    /// Only used by five-card draw.
+    pub second_round_start: SecondRoundStart,
+    /// Only used by acey-deucey.
+    pub acey_deucey_rules: acey_deucey::Rules,
+    /// Ignored by games without blinds or antes, like acey-deucey.
+    pub level_schedule: LevelSchedule,
+}
+
+impl Default for Spec {
+    fn default() -> Self {
+        Self {
+            seed: <_>::default(),
+            sub_game: SubGame::Holdem,
+            player_count: 2,
+            hand_count: 16,
+            starting_money: DEFAULT_STARTING_MONEY,
+            betting_structure: <_>::default(),
+            deck_composition: <_>::default(),
+            wilds: <_>::default(),
+            opening: <_>::default(),
+            second_round_start: <_>::default(),
+            acey_deucey_rules: <_>::default(),
+            level_schedule: <_>::default(),
+        }
+    }
+}
+
+pub type GameIndex = u32;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ViolationKind {
+    MoneyNotConserved { expected: MoneyInner, actual: MoneyInner },
+    Stuck,
+    Panicked,
+}
+
+/// Something that should never happen, and where it happened.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Violation {
+    pub game: GameIndex,
+    /// The amount of hands that had been played in the game so far.
+    pub hand: HandCount,
+    pub kind: ViolationKind,
+}
+
+impl core::fmt::Display for Violation {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "game {} hand {}: ", self.game, self.hand)?;
+
+        match self.kind {
+            ViolationKind::MoneyNotConserved { expected, actual } => write!(
+                f,
+                "expected ${expected} total but found ${actual}"
+            ),
+            ViolationKind::Stuck => write!(
+                f,
+                "no hand finished in {MAX_FRAMES_PER_HAND} frames"
+            ),
+            ViolationKind::Panicked => write!(f, "panicked"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Report {
+    pub hands: HandCount,
+    pub games_finished: GameIndex,
+    /// Games that were still going when the hand count was reached, or that
+    /// were stopped because of a violation.
+    pub games_unfinished: GameIndex,
+    /// How many games were won by the player that started in each seat.
+    pub wins: PerPlayer<GameIndex>,
+    pub pot_sum: u64,
+    pub pot_count: u64,
+    pub hands_to_elimination_sum: u64,
+    pub elimination_count: u64,
+    pub violations: Vec<Violation>,
+}
+
+impl Report {
+    /// The size of each pot when the hand finished, averaged over all hands.
+    pub fn average_pot(&self) -> Option<u64> {
+        self.pot_sum.checked_div(self.pot_count)
+    }
+
+    /// The amount of hands into a game that each player ran out of money,
+    /// averaged over all the players that did.
+    pub fn average_hands_to_elimination(&self) -> Option<u64> {
+        self.hands_to_elimination_sum.checked_div(self.elimination_count)
+    }
+}
+
+/// The parts of each game's table that are needed to run it without a human.
+pub(crate) trait CpuTable: Sized {
+    fn new(rng: &mut Xs, spec: &Spec) -> Option<Self>;
+
+    fn update(
+        &mut self,
+        rng: &mut Xs,
+        ctx: &mut ui::Context,
+        commands: &mut Commands,
+        input: Input,
+        speaker: &mut Speaker,
+    ) -> ModeCmd;
+
+    fn is_undealt(&self) -> bool;
+
+    fn is_finished(&self) -> bool;
+
+    fn pot_total(&self) -> MoneyInner;
+
+    fn hands_played(&self) -> HandCount;
+
+    /// Each player who has money, and how much.
+    fn stacks(&self) -> impl Iterator<Item = (StartingSeat, MoneyInner)>;
+
+    fn skip(&self) -> SkipState;
+
+    /// Whether the rest of the game should be played out by
+    /// `update_simulating`, instead of being shown.
+    fn is_simulating(&self) -> bool {
+        self.skip() == SkipState::Simulating
+        && !self.is_undealt()
+        && !self.is_finished()
+    }
+}
+
+macro_rules! cpu_table_new {
+    ($module: ident, $rng: ident, $spec: ident $(, $extra_arg: expr)* $(,)?) => ({
+        let player_count = $module::PlayerCount::try_from($spec.player_count).ok()?;
+
+        let mut moneys = [0; $module::MAX_PLAYERS as usize];
+        for money in &mut moneys[..player_count.usize()] {
+            *money = $spec.starting_money;
+        }
+
+        let mut setups = default_seat_setups($spec.starting_money);
+        for setup in setups.iter_mut() {
+            setup.is_human = false;
+        }
+
+        let mut table = $module::Table::selected(
+            $rng,
+            player_count,
+            Money::array_from_inner_array(moneys),
+            &setups[..],
+            $($extra_arg,)*
+        );
+
+        // Keep playing after there are no humans left.
+        table.seats.skip = SkipState::Watch;
+
+        Some(table)
+    })
+}
+
+macro_rules! cpu_table_update {
+    ($module: ident, $table: ident, $rng: ident, $ctx: ident, $commands: ident, $input: ident, $speaker: ident) => {
+        $module::update_and_render(
+            $commands,
+            $module::State {
+                rng: $rng,
+                ctx: $ctx,
+                table: $table,
+            },
+            $input,
+            $speaker,
+        )
+    }
+}
+
+impl <H: holdem::Variant> CpuTable for holdem::Table<H> {
+    fn new(rng: &mut Xs, spec: &Spec) -> Option<Self> {
+        if spec.player_count > H::max_players(spec.deck_composition) {
+            return None
+        }
+
+        cpu_table_new!(
+            holdem,
+            rng,
+            spec,
+            spec.betting_structure,
+            spec.deck_composition,
+            spec.level_schedule,
+        )
+    }
+
+    fn update(
+        &mut self,
+        rng: &mut Xs,
+        ctx: &mut ui::Context,
+        commands: &mut Commands,
+        input: Input,
+        speaker: &mut Speaker,
+    ) -> ModeCmd {
+        cpu_table_update!(holdem, self, rng, ctx, commands, input, speaker)
+    }
+
+    fn is_undealt(&self) -> bool {
+        matches!(self.state, holdem::TableState::Undealt { .. })
+    }
+
+    fn is_finished(&self) -> bool {
+        matches!(self.state, holdem::TableState::Finished)
+    }
+
+    fn pot_total(&self) -> MoneyInner {
+        use holdem::TableState::*;
+        match &self.state {
+            Undealt { .. } | Finished => 0,
+            Betting { bundle, .. }
+            | Showdown { bundle, .. } => bundle.pot.total(),
+        }
+    }
+
+    fn hands_played(&self) -> HandCount {
+        self.seats.hands_played
+    }
+
+    fn skip(&self) -> SkipState {
+        self.seats.skip
+    }
+
+    fn stacks(&self) -> impl Iterator<Item = (StartingSeat, MoneyInner)> {
+        self.seats.starting_seats.iter()
+            .zip(self.seats.moneys.iter())
+            .map(|(&seat, money)| (seat, money.as_inner()))
+            .filter(|&(_, money)| money > 0)
+    }
+}
+
+impl CpuTable for five_card_draw::Table {
+    fn new(rng: &mut Xs, spec: &Spec) -> Option<Self> {
+        cpu_table_new!(
+            five_card_draw,
+            rng,
+            spec,
+            spec.betting_structure,
+            five_card_draw::Rules {
+                wilds: spec.wilds,
+                opening: spec.opening,
+                second_round_start: spec.second_round_start,
+            },
+            spec.level_schedule,
+        )
+    }
+
+    fn update(
+        &mut self,
+        rng: &mut Xs,
+        ctx: &mut ui::Context,
+        commands: &mut Commands,
+        input: Input,
+        speaker: &mut Speaker,
+    ) -> ModeCmd {
+        cpu_table_update!(five_card_draw, self, rng, ctx, commands, input, speaker)
+    }
+
+    fn is_undealt(&self) -> bool {
+        matches!(self.state, five_card_draw::TableState::Undealt { .. })
+    }
+
+    fn is_finished(&self) -> bool {
+        matches!(self.state, five_card_draw::TableState::Finished)
+    }
+
+    fn pot_total(&self) -> MoneyInner {
+        use five_card_draw::TableState::*;
+        match &self.state {
+            Undealt { .. } | Finished => 0,
+            FirstRound { bundle }
+            | Drawing { bundle, .. }
+            | SecondRound { bundle }
+            | Showdown { bundle } => bundle.pot.total(),
+        }
+    }
+
+    fn hands_played(&self) -> HandCount {
+        self.seats.hands_played
+    }
+
+    fn skip(&self) -> SkipState {
+        self.seats.skip
+    }
+
+    fn stacks(&self) -> impl Iterator<Item = (StartingSeat, MoneyInner)> {
+        self.seats.starting_seats.iter()
+            .zip(self.seats.moneys.iter())
+            .map(|(&seat, money)| (seat, money.as_inner()))
+            .filter(|&(_, money)| money > 0)
+    }
+}
+
+impl CpuTable for acey_deucey::Table {
+    fn new(rng: &mut Xs, spec: &Spec) -> Option<Self> {
+        cpu_table_new!(acey_deucey, rng, spec, spec.acey_deucey_rules)
+    }
+
+    fn update(
+        &mut self,
+        rng: &mut Xs,
+        ctx: &mut ui::Context,
+        commands: &mut Commands,
+        input: Input,
+        speaker: &mut Speaker,
+    ) -> ModeCmd {
+        cpu_table_update!(acey_deucey, self, rng, ctx, commands, input, speaker)
+    }
+
+    fn is_undealt(&self) -> bool {
+        matches!(self.state, acey_deucey::TableState::Undealt { .. })
+    }
+
+    fn is_finished(&self) -> bool {
+        matches!(self.state, acey_deucey::TableState::Finished)
+    }
+
+    fn pot_total(&self) -> MoneyInner {
+        use acey_deucey::TableState::*;
+        match &self.state {
+            Undealt { .. } | Finished => 0,
+            DealtPosts { bundle }
+            | Reveal { bundle, .. } => bundle.pot.as_inner(),
+        }
+    }
+
+    fn hands_played(&self) -> HandCount {
+        self.seats.hands_played
+    }
+
+    fn skip(&self) -> SkipState {
+        self.seats.skip
+    }
+
+    fn stacks(&self) -> impl Iterator<Item = (StartingSeat, MoneyInner)> {
+        // Acey-deucey does not condense the seats, so the seats never change.
+        self.seats.moneys.iter()
+            .enumerate()
+            .map(|(seat, money)| (seat as StartingSeat, money.as_inner()))
+            .filter(|&(_, money)| money > 0)
+    }
+}
+
+/// Play `spec.hand_count` hands of `spec.sub_game`, starting new games as
+/// needed, with every seat being a CPU player. Stops early after `MAX_GAMES`
+/// games, or after a game that did not finish any hands.
+pub fn simulate(spec: Spec) -> Report {
+    match spec.sub_game {
+        SubGame::Holdem => simulate_table::<holdem::Table>(spec),
+        SubGame::AceyDeucey => simulate_table::<acey_deucey::Table>(spec),
+        SubGame::FiveCardDraw => simulate_table::<five_card_draw::Table>(spec),
+        SubGame::Omaha => simulate_table::<omaha::Table>(spec),
+        SubGame::SevenCardStud => simulate_table::<seven_card_stud::Table>(spec),
+    }
+}
+
+fn simulate_table<T: CpuTable>(spec: Spec) -> Report {
+    let mut report = Report::default();
+
+    let mut rng = xs::from_seed(spec.seed);
+
+    let mut game: GameIndex = 0;
+
+    while report.hands < spec.hand_count && game < MAX_GAMES {
+        let Some(table) = T::new(&mut rng, &spec)
+            else { break };
+
+        let hands_before = report.hands;
+
+        let played = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            play_game(&mut rng, table, game, &spec, &mut report)
+        }));
+
+        let hands_this_game = report.hands - hands_before;
+
+        if played.is_err() {
+            report.violations.push(Violation {
+                game,
+                hand: hands_this_game,
+                kind: ViolationKind::Panicked,
+            });
+            report.games_unfinished += 1;
+        }
+
+        game += 1;
+
+        // A game that ends without finishing any hands will presumably do the
+        // same thing every time, so another one would never get us closer to
+        // the hand count.
+        if hands_this_game == 0 {
+            break
+        }
+    }
+
+    report
+}
+
+fn play_game<T: CpuTable>(
+    rng: &mut Xs,
+    mut table: T,
+    game: GameIndex,
+    spec: &Spec,
+    report: &mut Report,
+) {
+    let expected_total = table.stacks().map(|(_, money)| money).sum::<MoneyInner>()
+        + table.pot_total();
+
+    let mut ctx = ui::Context::default();
+    let mut commands = Commands::default();
+    let mut speaker = Speaker::default();
+
+    let mut alive: PerPlayer<bool> = <_>::default();
+    for (seat, _) in table.stacks() {
+        alive[usize::from(seat)] = true;
+    }
+
+    let mut hands_played = table.hands_played();
+    let mut last_pot_total = table.pot_total();
+    let mut frames_this_hand = 0;
+    let mut is_pressing_a = false;
+
+    loop {
+        ctx.frame_init();
+        commands.clear();
+        speaker.clear();
+
+        is_pressing_a = !is_pressing_a;
+        let input = cpu_only_input(is_pressing_a);
+
+        let cmd = table.update(rng, &mut ctx, &mut commands, input, &mut speaker);
+
+        let is_over = cmd != ModeCmd::NoOp || table.is_undealt() || table.is_finished();
+
+        let total = table.stacks().map(|(_, money)| money).sum::<MoneyInner>()
+            + table.pot_total();
+        if total != expected_total {
+            report.violations.push(Violation {
+                game,
+                hand: table.hands_played(),
+                kind: ViolationKind::MoneyNotConserved {
+                    expected: expected_total,
+                    actual: total,
+                },
+            });
+            report.games_unfinished += 1;
+            return
+        }
+
+        if table.hands_played() != hands_played {
+            hands_played = table.hands_played();
+            frames_this_hand = 0;
+            report.hands += 1;
+            report.pot_sum += u64::from(last_pot_total);
+            report.pot_count += 1;
+
+            let mut still_alive: PerPlayer<bool> = <_>::default();
+            for (seat, _) in table.stacks() {
+                still_alive[usize::from(seat)] = true;
+            }
+
+            for (was, is) in alive.iter().zip(still_alive.iter()) {
+                if *was && !*is {
+                    report.hands_to_elimination_sum += u64::from(hands_played);
+                    report.elimination_count += 1;
+                }
+            }
+
+            alive = still_alive;
+        } else {
+            frames_this_hand += 1;
+        }
+        last_pot_total = table.pot_total();
+
+        if is_over {
+            let winner = table.stacks()
+                .max_by_key(|&(_, money)| money)
+                .map(|(seat, _)| seat);
+
+            if let Some(winner) = winner {
+                report.wins[usize::from(winner)] += 1;
+            }
+            report.games_finished += 1;
+            return
+        }
+
+        if frames_this_hand > MAX_FRAMES_PER_HAND {
+            report.violations.push(Violation {
+                game,
+                hand: hands_played,
+                kind: ViolationKind::Stuck,
+            });
+            report.games_unfinished += 1;
+            return
+        }
+
+        if report.hands >= spec.hand_count {
+            report.games_unfinished += 1;
+            return
+        }
+    }
+}
+
+/// Press A every other frame, to get past any buttons that the CPU players
+/// wait on, like the one at showdown.
+fn cpu_only_input(is_pressing_a: bool) -> Input {
+    if is_pressing_a {
+        Input {
+            gamepad: Button::A,
+            previous_gamepad: <_>::default(),
+        }
+    } else {
+        Input {
+            gamepad: <_>::default(),
+            previous_gamepad: Button::A,
+        }
+    }
+}
+
+/// Plays up to `SIMULATED_FRAMES_PER_FRAME` frames of a game that only has CPU
+/// players left, without showing them, then draws a note saying so instead.
+/// Returns the first `ModeCmd` other than `NoOp` that the game does, so callers
+/// can handle the end of the game the same way as when it is shown. `ctx` is
+/// the same one the table is shown with, so nothing is built fresh each call.
+pub(crate) fn update_simulating<T: CpuTable>(
+    commands: &mut Commands,
+    rng: &mut Xs,
+    ctx: &mut ui::Context,
+    table: &mut T,
+) -> ModeCmd {
+    let mut hidden_commands = Commands::default();
+    let mut hidden_speaker = Speaker::default();
+
+    let mut cmd = ModeCmd::NoOp;
+
+    for frame in 0..SIMULATED_FRAMES_PER_FRAME {
+        ctx.frame_init();
+        hidden_commands.clear();
+        hidden_speaker.clear();
+
+        cmd = table.update(
+            rng,
+            ctx,
+            &mut hidden_commands,
+            cpu_only_input(frame % 2 == 0),
+            &mut hidden_speaker,
+        );
+
+        if cmd != ModeCmd::NoOp || table.is_undealt() || table.is_finished() {
+            break
+        }
+    }
+
+    const SCREEN_RECT: unscaled::Rect = unscaled::Rect {
+        x: unscaled::X(0),
+        y: unscaled::Y(0),
+        w: command::WIDTH_W,
+        h: command::HEIGHT_H,
+    };
+
+    commands.draw_nine_slice(gfx::NineSlice::Window, SCREEN_RECT);
+
+    let text: &[u8] = b"playing out the cpu only hands";
+    let xy = gfx::center_line_in_rect(text.len() as _, SCREEN_RECT);
+    commands.print_chars(text, xy.x, xy.y, TEXT);
+
+    let mut hands_text = [0u8; 32];
+    {
+        use std::io::Write;
+        let _cant_actually_fail = write!(
+            &mut hands_text[..],
+            "{} hands played",
+            table.hands_played(),
+        );
+    }
+    let xy = gfx::center_line_in_rect(gfx::pre_nul_len(&hands_text), SCREEN_RECT);
+    commands.print_chars(&hands_text, xy.x, xy.y + gfx::CHAR_LINE_ADVANCE, TEXT);
+
+    cmd
+}
+
+/// Plays `table` out with `update_simulating` once nobody is left to watch it,
+/// and otherwise updates and renders it as usual.
+pub(crate) fn update_or_simulate<T: CpuTable>(
+    commands: &mut Commands,
+    rng: &mut Xs,
+    ctx: &mut ui::Context,
+    table: &mut T,
+    input: Input,
+    speaker: &mut Speaker,
+) -> ModeCmd {
+    if table.is_simulating() {
+        update_simulating(commands, rng, ctx, table)
+    } else {
+        table.update(rng, ctx, commands, input, speaker)
+    }
+}
+
+#[test]
+fn every_sub_game_can_be_simulated_without_violations() {
+    for sub_game in SubGame::ALL {
+        for betting_structure in BettingStructure::ALL {
+            let report = simulate(Spec {
+                seed: [42; 16],
+                sub_game,
+                player_count: 3,
+                betting_structure,
+                // Short levels, so the later levels get played too.
+                level_schedule: LevelSchedule::Hands(4),
+                ..Spec::default()
+            });
+
+            assert_eq!(report.violations, [], "{sub_game:?} {betting_structure:?}");
+            assert_eq!(report.hands, 16, "{sub_game:?} {betting_structure:?}");
+        }
+    }
+}
+
+#[test]
+fn cpu_only_remainders_are_played_out_until_the_game_finishes() {
+    const MAX_CALLS: u32 = 4096;
+
+    fn play_out<T: CpuTable>(rng: &mut Xs, mut table: T, sub_game: SubGame) {
+        let expected_total = table.stacks().map(|(_, money)| money).sum::<MoneyInner>()
+            + table.pot_total();
+
+        let mut ctx = ui::Context::default();
+        let mut commands = Commands::default();
+
+        let mut calls = 0;
+        let cmd = loop {
+            assert!(table.is_simulating(), "{sub_game:?}");
+
+            let cmd = update_simulating(&mut commands, rng, &mut ctx, &mut table);
+            if cmd != ModeCmd::NoOp {
+                break cmd
+            }
+
+            calls += 1;
+            assert!(calls < MAX_CALLS, "{sub_game:?} did not finish");
+        };
+
+        assert!(cmd == ModeCmd::FinishedRound, "{sub_game:?}");
+        assert!(table.is_finished(), "{sub_game:?}");
+        assert!(!table.is_simulating(), "{sub_game:?}");
+        assert_eq!(
+            table.stacks().map(|(_, money)| money).sum::<MoneyInner>(),
+            expected_total,
+            "{sub_game:?}"
+        );
+    }
+
+    for sub_game in SubGame::ALL {
+        let spec = Spec {
+            seed: [22; 16],
+            sub_game,
+            player_count: 3,
+            hand_count: 0,
+            // Small stacks, so the games finish sooner.
+            starting_money: 100,
+            level_schedule: LevelSchedule::Hands(4),
+            ..Spec::default()
+        };
+
+        let mut rng = xs::from_seed(spec.seed);
+
+        macro_rules! play_out {
+            ($table: ty) => {{
+                let mut table = <$table>::new(&mut rng, &spec).unwrap();
+                table.seats.skip = SkipState::Simulating;
+                play_out(&mut rng, table, sub_game);
+            }}
+        }
+
+        match sub_game {
+            SubGame::Holdem => play_out!(holdem::Table),
+            SubGame::AceyDeucey => play_out!(acey_deucey::Table),
+            SubGame::FiveCardDraw => play_out!(five_card_draw::Table),
+            SubGame::Omaha => play_out!(omaha::Table),
+            SubGame::SevenCardStud => play_out!(seven_card_stud::Table),
+        }
+    }
+}
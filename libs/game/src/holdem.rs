@@ -1,42 +1,390 @@
 use gfx::{CHAR_SPACING_H, CHAR_SPACING_W, SPACING_H, SPACING_W, Commands, HoldemFacing, chart_block, pre_nul_len};
-use look_up::{holdem::{ALL_SORTED_HANDS, hand_win_probability}};
+use look_up::{holdem::{hand_win_probability}};
 pub use models::{MIN_MONEY_UNIT, Pot, PotAction, RoundOutcome, holdem::{PlayerIndex, MIN_PLAYERS, MAX_PLAYERS, HandIndex}};
-use models::{Action, ActionKind, AllowedKindMode, ActionSpec, BetKind, Deck, Money, MoneyInner, MoneyMove, NonZeroMoney, NonZeroMoneyInner, gen_action, holdem::{MAX_POTS, CommunityCards, FullBoard, Hand, HandLen, Hands, PerPlayer, gen_hand_index}};
+use models::{Action, ActionKind, BettingStructure, Card, RaiseRange, AllowedKindMode, ActionSpec, BetKind, Deck, DeckComposition, Money, MoneyInner, MoneyMove, NonZeroMoney, NonZeroMoneyInner, PerPlayerBitset, PotSplit, gen_action, holdem::{MAX_POTS, CommunityCards, FullBoard, Hand, HandLen, Hands, PerPlayer, PlayerAmount, gen_hand_index}};
 // TODO? Move Handlen into here, and rename it?
 pub use models::holdem::HandLen as PlayerCount;
-use platform_types::{Button, Dir, Input, PaletteIndex, Speaker, SFX, command, unscaled, TEXT};
+use platform_types::{Button, Dir, Input, PaletteIndex, Speaker, SFX, command, unscaled, GREY_INDEX, TEXT, YELLOW_INDEX};
 use probability::{FIFTY_PERCENT, SEVENTY_FIVE_PERCENT, EIGHTY_SEVEN_POINT_FIVE_PERCENT, Probability};
 
 use xs::Xs;
 
 use std::io::Write;
 
-use crate::shared_game_types::{CpuPersonality, Personality, ModeCmd, SkipState};
-use crate::ui::{self, ButtonSpec, Id::*, do_button};
+use crate::shared_game_types::{HandCount, SeatName, LevelSchedule, Levels, Personality, PlayerStats, ModeCmd, SkipState, Standings, StartingSeat, gen_personalities, HotSeat, SeatEditor, SeatSetup, SeatSetups, default_seat_setups, set_unedited_starting_money};
+use crate::strategy::{BettingView, Cards};
+use crate::ui::{self, StandingsCmd, ButtonSpec, Id::*, do_button};
+
+/// Where the community cards go, and so where the pots are listed, for the
+/// games that have community cards.
+const COMMUNITY_BASE_X: unscaled::X = unscaled::X(150);
+const COMMUNITY_BASE_Y: unscaled::Y = unscaled::Y(150);
+
+/// What comes after a betting round that more than one player is still in.
+pub enum Next<Round, Board> {
+    /// Another betting round.
+    Round(Round),
+    /// Every card is out, so the remaining hands are compared.
+    Showdown(Board),
+}
+
+/// A game that is bet the way hold'em is: forced bets, then a betting round
+/// each time more cards come out, then a showdown for each pot. Each game
+/// supplies its hands, how its cards come out, and who acts first.
+pub trait Variant: Copy + Default + AsRef<[Card]> {
+    /// How far along the hand is. The community cards for hold'em, or the
+    /// street for stud.
+    type Round: Copy + PartialEq;
+    /// The cards shared between all the hands at the showdown.
+    type Board: Copy;
+
+    const FIRST_ROUND: Self::Round;
+
+    /// The betting structure the game is played with unless another one is
+    /// selected.
+    const DEFAULT_BETTING_STRUCTURE: BettingStructure;
+
+    /// Whether the betting structure and deck composition can be selected.
+    /// When they can't, the defaults are always used.
+    const HAS_OPTIONS: bool;
+
+    /// Whether the two card starting hand chart applies.
+    const HAS_CHART: bool;
+
+    const HAND_WIDTH: unscaled::W;
+
+    /// The distance from the top of one row of hands to the top of the next.
+    const ROW_HEIGHT: unscaled::H;
+
+    /// The pots are listed right aligned to `POTS_X`, starting at `POTS_Y`.
+    const POTS_X: unscaled::X;
+    const POTS_Y: unscaled::Y;
+
+    fn max_players(deck_composition: DeckComposition) -> PlayerAmount;
+
+    fn deal(rng: &mut Xs, player_count: PlayerCount, deck_composition: DeckComposition) -> (Hands<Self>, Deck);
+
+    /// Takes the bets every hand starts with out of the players' stacks.
+    fn collect_forced_bets(hands: &Hands<Self>, dealer: HandIndex, pot: &mut Pot, seats: &mut Seats);
+
+    fn first_to_act(hands: &Hands<Self>, round: Self::Round, pot: &Pot, dealer: HandIndex) -> HandIndex;
+
+    /// Deals whatever comes out after `round`.
+    fn next_round(round: Self::Round, deck: &mut Deck) -> Next<Self::Round, Self::Board>;
+
+    /// The smallest bet allowed in the given round. Under fixed-limit, every
+    /// bet is this size.
+    fn bet_size(seats: &Seats, round: Self::Round) -> NonZeroMoneyInner;
+
+    /// The amount CPU players think of their raises in terms of.
+    fn raise_unit(seats: &Seats, round: Self::Round) -> NonZeroMoneyInner;
+
+    /// Draw the cards shared between all the hands, if any.
+    fn draw_round(commands: &mut Commands, round: Self::Round);
+
+    /// Draw the cards dealt so far, with the face down ones shown if `show`
+    /// is true.
+    fn draw(commands: &mut Commands, hand: Self, round: Self::Round, show: bool, x: unscaled::X, y: unscaled::Y);
+
+    fn draw_folded(commands: &mut Commands, hand: Self, round: Self::Round, show: bool, x: unscaled::X, y: unscaled::Y);
+
+    fn draw_underlight(commands: &mut Commands, x: unscaled::X, y: unscaled::Y);
+
+    fn draw_selected(commands: &mut Commands, x: unscaled::X, y: unscaled::Y);
+
+    /// The cards the CPU player at `current` can see.
+    fn cards(hands: &Hands<Self>, current: HandIndex, pot: &Pot, round: Self::Round, deck_composition: DeckComposition) -> Cards;
+
+    fn eval(self, deck_composition: DeckComposition, board: Self::Board) -> evaluate::Eval;
+
+    /// The five cards that make up the hand that `eval` evaluates, and what
+    /// kind of hand they are, for showing to the players.
+    fn best_five(self, deck_composition: DeckComposition, board: Self::Board) -> ([Card; 5], evaluate::Category);
+
+    /// The cards in `board`, in the first elements of the array.
+    fn board_cards(board: Self::Board) -> ([Card; 5], usize);
+}
+
+/// The cards dealt to each player in hold'em, or in a game that is played the
+/// same way apart from them, like Omaha. Everything else about the table is
+/// shared between those games.
+pub trait HoleCards: evaluate::HoleCards {
+    /// The betting structure the game is played with unless another one is
+    /// selected.
+    const DEFAULT_BETTING_STRUCTURE: BettingStructure;
+
+    const HAND_WIDTH: unscaled::W;
+
+    /// Draw the hand, face up if `facing` is `Some`, and face down otherwise.
+    fn draw(commands: &mut Commands, facing: Option<Self>, x: unscaled::X, y: unscaled::Y);
+
+    fn draw_folded(commands: &mut Commands, facing: Option<Self>, x: unscaled::X, y: unscaled::Y);
+
+    fn draw_underlight(commands: &mut Commands, x: unscaled::X, y: unscaled::Y);
+
+    fn draw_selected(commands: &mut Commands, x: unscaled::X, y: unscaled::Y);
+
+    /// The cards a CPU player with this hand can see.
+    fn cards(self, community_opt: Option<CommunityCards>, deck: DeckComposition) -> Cards;
+}
+
+impl <H: HoleCards> Variant for H {
+    /// `None` before the flop.
+    type Round = Option<CommunityCards>;
+    type Board = FullBoard;
+
+    const FIRST_ROUND: Self::Round = None;
+
+    const DEFAULT_BETTING_STRUCTURE: BettingStructure = <H as HoleCards>::DEFAULT_BETTING_STRUCTURE;
+
+    const HAS_OPTIONS: bool = true;
+
+    // The chart only covers two card starting hands.
+    const HAS_CHART: bool = H::LEN == models::holdem::HAND_LEN;
+
+    const HAND_WIDTH: unscaled::W = <H as HoleCards>::HAND_WIDTH;
+
+    const ROW_HEIGHT: unscaled::H = unscaled::H((gfx::card::HEIGHT.get() / 2) + 1);
+
+    const POTS_X: unscaled::X = COMMUNITY_BASE_X;
+    const POTS_Y: unscaled::Y = COMMUNITY_BASE_Y;
+
+    fn max_players(deck_composition: DeckComposition) -> PlayerAmount {
+        models::holdem::max_players_with(H::LEN, deck_composition)
+    }
+
+    fn deal(rng: &mut Xs, player_count: PlayerCount, deck_composition: DeckComposition) -> (Hands<Self>, Deck) {
+        models::holdem::deal(rng, player_count, deck_composition)
+    }
+
+    fn collect_forced_bets(hands: &Hands<Self>, dealer: HandIndex, pot: &mut Pot, seats: &mut Seats) {
+        let mut index = dealer;
+        if hands.len() == HandLen::Two {
+            // When head-to-head, the dealer posts the small blind
+            // and the other player posts the big blind, so don't
+            // advance.
+        } else {
+            index += 1;
+            if index >= hands.len().u8() {
+                index = 0;
+            }
+        };
+
+        pot.push_bet_of_kind(
+            index, 
+            PotAction::Bet(
+                seats.moneys[usize::from(index)]
+                    .take(seats.small_forced_bet.get())
+            ),
+            BetKind::Ante,
+        );
+
+        index += 1;
+        if index >= hands.len().u8() {
+            index = 0;
+        }
+
+        pot.push_bet_of_kind(
+            index, 
+            PotAction::Bet(
+                seats.moneys[usize::from(index)]
+                    .take(seats.large_forced_bet.get())
+            ),
+            BetKind::Ante,
+        );
+    }
+
+    fn first_to_act(hands: &Hands<Self>, _round: Self::Round, _pot: &Pot, dealer: HandIndex) -> HandIndex {
+        if hands.len() == HandLen::Two {
+            // When head-to-head, the dealer acts first.
+            dealer
+        } else {
+            // Normally, the player after the dealer acts first.
+            let mut index = dealer + 1;
+            if index >= hands.len().u8() {
+                index = 0;
+            }
+            index
+        }
+    }
+
+    fn next_round(round: Self::Round, deck: &mut Deck) -> Next<Self::Round, Self::Board> {
+        match round {
+            None => Next::Round(Some(
+                models::holdem::deal_community_cards(deck).expect("Deck ran out!?")
+            )),
+            Some(CommunityCards::River(flop, turn, river)) => Next::Showdown([
+                flop[0],
+                flop[1],
+                flop[2],
+                turn,
+                river
+            ]),
+            Some(mut community_cards) => {
+                models::holdem::deal_to_community_cards(deck, &mut community_cards);
+                Next::Round(Some(community_cards))
+            },
+        }
+    }
+
+    /// The turn and river use a big bet under fixed-limit, which is twice the
+    /// size of the small bet used before that.
+    fn bet_size(seats: &Seats, round: Self::Round) -> NonZeroMoneyInner {
+        match (seats.betting_structure, round) {
+            (
+                BettingStructure::FixedLimit,
+                Some(CommunityCards::Turn(..) | CommunityCards::River(..))
+            ) => seats.large_forced_bet.saturating_mul(NonZeroMoneyInner::MIN.saturating_add(1)),
+            _ => seats.large_forced_bet,
+        }
+    }
+
+    fn raise_unit(seats: &Seats, _round: Self::Round) -> NonZeroMoneyInner {
+        seats.large_forced_bet
+    }
+
+    fn draw_round(commands: &mut Commands, round: Self::Round) {
+        if let Some(community_cards) = round {
+            commands.draw_holdem_community_cards(
+                community_cards,
+                COMMUNITY_BASE_X,
+                COMMUNITY_BASE_Y,
+            );
+        }
+    }
+
+    fn draw(commands: &mut Commands, hand: Self, _round: Self::Round, show: bool, x: unscaled::X, y: unscaled::Y) {
+        <H as HoleCards>::draw(commands, show.then_some(hand), x, y);
+    }
+
+    fn draw_folded(commands: &mut Commands, hand: Self, _round: Self::Round, show: bool, x: unscaled::X, y: unscaled::Y) {
+        <H as HoleCards>::draw_folded(commands, show.then_some(hand), x, y);
+    }
+
+    fn draw_underlight(commands: &mut Commands, x: unscaled::X, y: unscaled::Y) {
+        <H as HoleCards>::draw_underlight(commands, x, y);
+    }
+
+    fn draw_selected(commands: &mut Commands, x: unscaled::X, y: unscaled::Y) {
+        <H as HoleCards>::draw_selected(commands, x, y);
+    }
+
+    fn cards(hands: &Hands<Self>, current: HandIndex, _pot: &Pot, round: Self::Round, deck_composition: DeckComposition) -> Cards {
+        let hand = hands.get(current)
+                    .copied()
+                    .unwrap_or_default();
+
+        <H as HoleCards>::cards(hand, round, deck_composition)
+    }
+
+    fn eval(self, deck_composition: DeckComposition, board: Self::Board) -> evaluate::Eval {
+        self.eval_with(deck_composition, CommunityCards::from(board))
+    }
+
+    fn best_five(self, deck_composition: DeckComposition, board: Self::Board) -> ([Card; 5], evaluate::Category) {
+        self.best_five_with(deck_composition, CommunityCards::from(board))
+    }
+
+    fn board_cards(board: Self::Board) -> ([Card; 5], usize) {
+        (board, board.len())
+    }
+}
+
+fn holdem_facing(facing: Option<Hand>) -> HoldemFacing {
+    match facing {
+        Some(hand) => HoldemFacing::Up(hand),
+        None => HoldemFacing::Down,
+    }
+}
+
+impl HoleCards for Hand {
+    const DEFAULT_BETTING_STRUCTURE: BettingStructure = BettingStructure::NoLimit;
+
+    const HAND_WIDTH: unscaled::W = Commands::HOLDEM_HAND_WIDTH;
+
+    fn draw(commands: &mut Commands, facing: Option<Self>, x: unscaled::X, y: unscaled::Y) {
+        commands.draw_holdem_hand(holdem_facing(facing), x, y);
+    }
+
+    fn draw_folded(commands: &mut Commands, facing: Option<Self>, x: unscaled::X, y: unscaled::Y) {
+        commands.draw_folded_holdem_hand(holdem_facing(facing), x, y);
+    }
+
+    fn draw_underlight(commands: &mut Commands, x: unscaled::X, y: unscaled::Y) {
+        commands.draw_holdem_hand_underlight(x, y);
+    }
+
+    fn draw_selected(commands: &mut Commands, x: unscaled::X, y: unscaled::Y) {
+        commands.draw_holdem_hand_selected(x, y);
+    }
+
+    fn cards(self, community_opt: Option<CommunityCards>, deck: DeckComposition) -> Cards {
+        Cards::Holdem {
+            hand: self,
+            community_opt,
+            deck,
+        }
+    }
+}
 
 #[derive(Clone)]
 pub struct Seats {
     pub moneys: [Money; MAX_PLAYERS as usize],
     pub personalities: [Personality; MAX_PLAYERS as usize],
+    pub stats: [PlayerStats; MAX_PLAYERS as usize],
+    pub starting_seats: [StartingSeat; MAX_PLAYERS as usize],
     pub skip: SkipState,
-    // TODO Increase these as the game goes on {
-    pub small_blind_amount: NonZeroMoneyInner,
-    pub large_blind_amount: NonZeroMoneyInner,
-    // }
+    pub hands_played: HandCount,
+    pub standings: Standings,
+    /// Which human has the device, for when more than one is playing.
+    pub hot_seat: HotSeat,
+    /// If set, the round finishes once this many hands have been played, even
+    /// if more than one player has money left. Dealer's choice uses this to
+    /// switch games.
+    pub hand_limit: Option<HandCount>,
+    pub betting_structure: BettingStructure,
+    pub deck_composition: DeckComposition,
+    pub levels: Levels,
+    /// The small and big blinds, or the ante and the bring-in in stud.
+    /// These are set from `levels` between hands, so they don't change in the
+    /// middle of one.
+    pub small_forced_bet: NonZeroMoneyInner,
+    pub large_forced_bet: NonZeroMoneyInner,
 }
 
+/// The forced bets at the first level.
+const BASE_SMALL_FORCED_BET: NonZeroMoneyInner = MIN_MONEY_UNIT;
+const BASE_LARGE_FORCED_BET: NonZeroMoneyInner = MIN_MONEY_UNIT.saturating_add(MIN_MONEY_UNIT.get());
+
 impl Default for Seats {
     fn default() -> Self {
         Self {
             moneys: <_>::default(),
             personalities: <_>::default(),
+            stats: <_>::default(),
+            starting_seats: core::array::from_fn(|i| i as StartingSeat),
             skip: <_>::default(),
-            small_blind_amount: MIN_MONEY_UNIT,
-            large_blind_amount: MIN_MONEY_UNIT.saturating_add(MIN_MONEY_UNIT.get()),
+            hands_played: 0,
+            standings: <_>::default(),
+            hot_seat: <_>::default(),
+            hand_limit: None,
+            betting_structure: <_>::default(),
+            deck_composition: <_>::default(),
+            levels: <_>::default(),
+            small_forced_bet: BASE_SMALL_FORCED_BET,
+            large_forced_bet: BASE_LARGE_FORCED_BET,
         }
     }
 }
 
+impl Seats {
+    /// Set the forced bets to the ones for the current level.
+    fn update_forced_bets(&mut self) {
+        self.small_forced_bet = self.levels.scale(BASE_SMALL_FORCED_BET);
+        self.large_forced_bet = self.levels.scale(BASE_LARGE_FORCED_BET);
+    }
+}
+
 #[derive(Clone, Default)]
 pub struct MenuSelection {
     pub action_kind: ActionKind,
@@ -51,9 +399,9 @@ pub enum Modal {
 }
 
 #[derive(Clone)]
-pub struct StateBundle {
+pub struct StateBundle<H = Hand> {
     pub deck: Deck,
-    pub hands: Hands,
+    pub hands: Hands<H>,
     pub dealer: HandIndex,
     pub current: HandIndex,
     pub pot: Pot,
@@ -62,73 +410,54 @@ pub struct StateBundle {
 }
 
 #[derive(Clone)]
-pub enum TableState {
-    Undealt { player_count: HandLen, starting_money: MoneyInner },
-    PreFlop {
-        bundle: StateBundle,
+pub enum TableState<H: Variant = Hand> {
+    Undealt {
+        player_count: HandLen,
+        starting_money: MoneyInner,
+        seats: SeatSetups,
+        /// Set while the seat setup screen is open.
+        seat_editor: Option<SeatEditor>,
+        betting_structure: BettingStructure,
+        deck_composition: DeckComposition,
+        level_schedule: LevelSchedule,
     },
-    PostFlop {
-        bundle: StateBundle,
-        community_cards: CommunityCards,
+    Betting {
+        bundle: StateBundle<H>,
+        round: H::Round,
     },
     Showdown {
-        bundle: StateBundle,
-        full_board: FullBoard,
+        bundle: StateBundle<H>,
+        board: H::Board,
+        /// The pot at the top of the list of pots, which can be scrolled
+        /// through when they don't all fit on the screen.
+        first_pot: u8,
     },
+    /// The game is over, and the final standings are being shown.
+    Finished,
 }
 
-impl Default for TableState {
+impl <H: Variant> Default for TableState<H> {
     fn default() -> Self {
         Self::Undealt {
             player_count: <_>::default(),
             starting_money: 500,
+            seats: default_seat_setups(500),
+            seat_editor: None,
+            betting_structure: H::DEFAULT_BETTING_STRUCTURE,
+            deck_composition: <_>::default(),
+            level_schedule: <_>::default(),
         }
     }
 }
 
-macro_rules! collect_blinds {
-    ($hands: ident $(,)? $player_count: ident $(,)? $dealer: ident $(,)? $pot: ident $(,)? $seats: ident) => {
-        let hands = &$hands;
-        let player_count = $player_count;
-        let dealer = $dealer;
-        let pot = &mut $pot;
-
-        {
-            let mut index = dealer;
-            if player_count == HandLen::Two {
-                // When head-to-head, the dealer posts the small blind
-                // and the other player posts the big blind, so don't
-                // advance.
-            } else {
-                index += 1;
-                if index >= hands.len().u8() {
-                    index = 0;
-                }
-            };
-
-            pot.push_bet_of_kind(
-                index, 
-                PotAction::Bet(
-                    $seats.moneys[usize::from(index)]
-                        .take($seats.small_blind_amount.get())
-                ),
-                BetKind::Ante,
-            );
-
-            index += 1;
-            if index >= hands.len().u8() {
-                index = 0;
-            }
-
-            pot.push_bet_of_kind(
-                index, 
-                PotAction::Bet(
-                    $seats.moneys[usize::from(index)]
-                        .take($seats.large_blind_amount.get())
-                ),
-                BetKind::Ante,
-            );
-        }
+/// Returns the largest player count that is at most `player_count`, and that
+/// can be dealt hands of `H` from a deck of the given composition.
+fn clamp_player_count<H: Variant>(player_count: PlayerCount, deck_composition: DeckComposition) -> PlayerCount {
+    let max = H::max_players(deck_composition);
+    if player_count.u8() > max {
+        PlayerCount::try_from(max).unwrap_or_default()
+    } else {
+        player_count
     }
 }
 
@@ -137,27 +466,17 @@ macro_rules! next_bundle {
         $hands: expr,
         $deck: expr,
         $dealer: expr,
-        $pot: expr
+        $pot: expr,
+        $round: expr
     ) => {
         let hands = $hands;
         let deck = $deck;
         let dealer = $dealer;
-        let player_count = hands.len();
         let mut pot = $pot;
 
         pot.reset_for_new_round();
 
-        let current = if player_count == HandLen::Two {
-            // When head-to-head, the dealer acts first.
-            dealer
-        } else {
-            // Normally, the player after the dealer acts first.
-            let mut index = dealer + 1;
-            if index >= hands.len().u8() {
-                index = 0;
-            }
-            index
-        };
+        let current = H::first_to_act(&hands, $round, &pot, dealer);
 
         let $bundle = StateBundle {
             hands,
@@ -171,28 +490,41 @@ macro_rules! next_bundle {
     }
 }
 
-#[derive(Clone, Default)]
-pub struct Table {
+#[derive(Clone)]
+pub struct Table<H: Variant = Hand> {
     pub seats: Seats,
-    pub state: TableState,
+    pub state: TableState<H>,
+}
+
+impl <H: Variant> Default for Table<H> {
+    fn default() -> Self {
+        Self {
+            seats: <_>::default(),
+            state: <_>::default(),
+        }
+    }
 }
 
-impl Table {
+impl <H: Variant> Table<H> {
     pub fn selected(
         rng: &mut Xs,
         player_count: PlayerCount,
         moneys: [Money; MAX_PLAYERS as usize],
+        setups: &[SeatSetup],
+        betting_structure: BettingStructure,
+        deck_composition: DeckComposition,
+        level_schedule: LevelSchedule,
     ) -> Self {
-        let mut personalities: [Personality; MAX_PLAYERS as usize] = <_>::default();
+        let (betting_structure, deck_composition) = if H::HAS_OPTIONS {
+            (betting_structure, deck_composition)
+        } else {
+            (H::DEFAULT_BETTING_STRUCTURE, DeckComposition::default())
+        };
 
-        personalities[0] = None;
-        // TODO Make each element of this array user selectable too.
-        // Start at 1 to make the first player user controlled
-        for i in 1..player_count.usize() {
-            personalities[i] = Some(CpuPersonality{});
-        }
+        let mut personalities: [Personality; MAX_PLAYERS as usize] = <_>::default();
+        gen_personalities(rng, &mut personalities[..player_count.usize()], setups);
 
-        let (hands, deck) = models::holdem::deal(rng, player_count);
+        let (hands, deck) = H::deal(rng, player_count, deck_composition);
 
         let selected = gen_hand_index(rng, player_count);
 
@@ -217,37 +549,42 @@ impl Table {
         let mut pot = Pot::with_capacity(player_count.u8(), 16);
 
         let mut seats = Seats {
+            standings: Standings::new(&moneys, setups),
             moneys,
             personalities,
+            betting_structure,
+            deck_composition,
+            levels: Levels::new(level_schedule),
             ..<_>::default()
         };
 
-        {
-            let seats_ref = &mut seats;
+        H::collect_forced_bets(&hands, dealer, &mut pot, &mut seats);
 
-            collect_blinds!(hands player_count dealer pot, seats_ref);
+        for stats in &mut seats.stats[..player_count.usize()] {
+            stats.start_hand();
         }
 
-        next_bundle!(bundle = hands, deck, dealer, pot);
+        next_bundle!(bundle = hands, deck, dealer, pot, H::FIRST_ROUND);
 
         Self {
             seats,
-            state: TableState::PreFlop {
+            state: TableState::Betting {
                 bundle,
+                round: H::FIRST_ROUND,
             },
         }
     }
 }
 
-pub struct State<'state> {
+pub struct State<'state, H: Variant = Hand> {
     pub rng: &'state mut Xs,
     pub ctx: &'state mut ui::Context,
-    pub table: &'state mut Table
+    pub table: &'state mut Table<H>
 }
 
-pub fn update_and_render(
+pub fn update_and_render<H: Variant>(
     commands: &mut Commands,
-    state: State<'_>,
+    state: State<'_, H>,
     input: Input,
     speaker: &mut Speaker,
 ) -> ModeCmd {
@@ -269,12 +606,9 @@ pub fn update_and_render(
         }
     }
 
-    const COMMUNITY_BASE_X: unscaled::X = unscaled::X(150);
-    const COMMUNITY_BASE_Y: unscaled::Y = unscaled::Y(150);
-
     macro_rules! stack_eval_text {
         ($text:ident = $eval: expr) => {
-            let mut eval_text = [0 as u8; 64];
+            let mut eval_text = [0u8; 64];
             let _cant_actually_fail = write!(
                 &mut eval_text[..],
                 "{}",
@@ -292,7 +626,7 @@ pub fn update_and_render(
     macro_rules! stack_money_text {
         ($text:ident = $money: expr) => {
             use std::io::Write;
-            let mut money_text = [0 as u8; 20];
+            let mut money_text = [0u8; 20];
             money_text[0] = b'$';
             let _cant_actually_fail = write!(
                 &mut money_text[1..],
@@ -324,7 +658,8 @@ pub fn update_and_render(
     }
 
     macro_rules! do_holdem_hands {
-        ($group: ident $(,)? $bundle: ident , $community_opt: expr) => ({
+        ($group: ident $(,)? $bundle: ident , $round: expr) => ({
+            let round: H::Round = $round;
             let group = $group;
             let hands = &$bundle.hands;
             let dealer = $bundle.dealer;
@@ -337,15 +672,19 @@ pub fn update_and_render(
                 xy!(0 0) ; models::holdem::MAX_PLAYERS as usize
             ];
 
-            let hand_width = gfx::Commands::HOLDEM_HAND_WIDTH.get() + 5;
+            let has_chart = H::HAS_CHART;
+
+            let hand_width = H::HAND_WIDTH.get() + 5;
+            // As many hands as fit across the screen go in each row.
+            let columns = command::WIDTH / hand_width;
 
             {
                 let mut i = 0u8;
-                'outer: for y in 0..4 {
-                    for x in 0..7 {
+                'outer: for y in 0.. {
+                    for x in 0..columns {
                         coords[usize::from(i)] = xy!(
                             x * hand_width,
-                            y * ((gfx::card::HEIGHT.get() / 2) + 1)
+                            y * H::ROW_HEIGHT.get()
                             + SPACING_H.get()
                         );
 
@@ -365,7 +704,8 @@ pub fn update_and_render(
                     let at = coords[i];
 
                     if current_i == i {
-                        group.commands.draw_holdem_hand_underlight(
+                        H::draw_underlight(
+                            group.commands,
                             at.x,
                             at.y
                         );
@@ -375,6 +715,12 @@ pub fn update_and_render(
                 }
             }
 
+            // Only the human who is acting, and so has the device, gets to see
+            // their own cards.
+            let acting_human = state.table.seats.personalities[current_i]
+                .is_none()
+                .then_some(current);
+
             {
                 let mut i: HandIndex = 0;
                 for hand in hands.iter() {
@@ -385,39 +731,30 @@ pub fn update_and_render(
                         HoldemMenu(_)
                         | HoldemChartButton => true,
                         _ => false,
-                    } && current == i;
+                    } && acting_human == Some(i);
 
                     if pot.has_folded(i) {
-                        let facing = if let Some(_personality) = &state.table.seats.personalities[current_i] {
+                        let show = if let Some(_personality) = &state.table.seats.personalities[current_i] {
                             // TODO make decision based on personality
-                            if cfg!(debug_assertions) {
-                                HoldemFacing::Up(hand)
-                            } else {
-                                HoldemFacing::Down
-                            }
+                            cfg!(debug_assertions)
                         } else {
-                            if show_if_player_owned {
-                                HoldemFacing::Up(hand)
-                            } else {
-                                HoldemFacing::Down
-                            }
+                            show_if_player_owned
                         };
 
-                        group.commands.draw_folded_holdem_hand(
-                            facing,
+                        H::draw_folded(
+                            group.commands,
+                            hand,
+                            round,
+                            show,
                             at.x,
                             at.y,
                         );
                     } else {
-                        let facing = if show_if_player_owned
-                        && state.table.seats.personalities[current_i].is_none() {
-                            HoldemFacing::Up(hand)
-                        } else {
-                            HoldemFacing::Down
-                        };
-
-                        group.commands.draw_holdem_hand(
-                            facing,
+                        H::draw(
+                            group.commands,
+                            hand,
+                            round,
+                            show_if_player_owned,
                             at.x,
                             at.y,
                         );
@@ -429,7 +766,6 @@ pub fn update_and_render(
 
             // The total bet needed to call
             let call_amount = pot.call_amount();
-            let minimum_raise_total = call_amount + MIN_MONEY_UNIT.get();
             // The amount extra needed to call
             let call_remainder = call_amount.saturating_sub(
                 pot.amount_for(current)
@@ -439,8 +775,17 @@ pub fn update_and_render(
                 .as_inner()
                 .checked_sub(call_remainder);
 
+            let raise_range: Option<RaiseRange> = pot.raise_range(
+                current,
+                state.table.seats.moneys[current_i].as_inner(),
+                state.table.seats.betting_structure,
+                H::bet_size(&state.table.seats, round),
+            );
+
             let allowed_kind_mode =
-                if call_remainder > 0 {
+                if raise_range.is_none() && call_remainder > 0 {
+                    AllowedKindMode::NoRaising
+                } else if call_remainder > 0 {
                     AllowedKindMode::All
                 } else if call_leftover.unwrap_or(0) > 0 {
                     AllowedKindMode::NoFolding
@@ -497,7 +842,11 @@ pub fn update_and_render(
 
                         if group.input.pressed_this_frame(Button::LEFT) {
                             if index == 0 {
-                                group.ctx.set_next_hot(HoldemChartButton);
+                                group.ctx.set_next_hot(if has_chart {
+                                    HoldemChartButton
+                                } else {
+                                    HoldemHand(hands_len - 1)
+                                });
                             } else {
                                 index -= 1;
                                 group.ctx.set_next_hot(HoldemHand(index));
@@ -505,15 +854,21 @@ pub fn update_and_render(
                         } else if group.input.pressed_this_frame(Button::RIGHT) {
                             index += 1;
                             if index >= hands_len {
-                                group.ctx.set_next_hot(HoldemChartButton);
+                                group.ctx.set_next_hot(if has_chart {
+                                    HoldemChartButton
+                                } else {
+                                    HoldemHand(0)
+                                });
                             } else {
                                 group.ctx.set_next_hot(HoldemHand(index));
                             }
                         } else if group.input.pressed_this_frame(Button::A) {
                             $bundle.selection.action_kind = match (allowed_kind_mode, $bundle.selection.action_kind) {
-                                (AllowedKindMode::NoFolding, ActionKind::Fold) => ActionKind::Call,
+                                (AllowedKindMode::NoFolding, ActionKind::Fold)
+                                | (AllowedKindMode::NoRaising, ActionKind::Raise) => ActionKind::Call,
                                 (AllowedKindMode::All, action_kind)
-                                | (AllowedKindMode::NoFolding, action_kind) => action_kind,
+                                | (AllowedKindMode::NoFolding, action_kind)
+                                | (AllowedKindMode::NoRaising, action_kind) => action_kind,
                                 (AllowedKindMode::AllIn, _) => ActionKind::Call,
                             };
                             group.ctx.set_next_hot(HoldemMenu(ACTION_KIND));
@@ -536,7 +891,8 @@ pub fn update_and_render(
 
                     match group.ctx.hot {
                         HoldemHand(index) if usize::from(index) == i => {
-                            group.commands.draw_holdem_hand_selected(
+                            H::draw_selected(
+                                group.commands,
                                 at.x,
                                 at.y
                             );
@@ -584,7 +940,7 @@ pub fn update_and_render(
                 h: HAND_DESC_H,
             };
 
-            {
+            if has_chart {
                 let w = unscaled::W(50);
                 let h = unscaled::H(50);
 
@@ -605,6 +961,13 @@ pub fn update_and_render(
                 }
             }
 
+            ui::draw_levels(
+                group.commands,
+                &state.table.seats.levels,
+                unscaled::X(0) + SPACING_W,
+                HAND_DESC_RECT.y - gfx::CHAR_LINE_ADVANCE - gfx::CHAR_LINE_ADVANCE,
+            );
+
             match group.ctx.hot {
                 HoldemChartButton => {
                     group.commands.draw_nine_slice(
@@ -628,13 +991,13 @@ pub fn update_and_render(
             }
 
             {
-                let mut y = COMMUNITY_BASE_Y;
+                let mut y = H::POTS_Y;
                 for amount in pot.individual_pots(&state.table.seats.moneys) {
                     stack_money_text!(main_pot_text = amount);
 
                     group.commands.print_chars(
                         &main_pot_text,
-                        COMMUNITY_BASE_X - pre_nul_len(&main_pot_text) * gfx::CHAR_ADVANCE,
+                        H::POTS_X - pre_nul_len(&main_pot_text) * gfx::CHAR_ADVANCE,
                         y,
                         TEXT
                     );
@@ -646,12 +1009,8 @@ pub fn update_and_render(
                 // which would be some function of MAX_PLAYERS. Exactly MAX_PLAYERS?
             }
 
-            if $bundle.selection.bet < minimum_raise_total {
-                $bundle.selection.bet = minimum_raise_total;
-            }
-            if $bundle.selection.bet > state.table.seats.moneys[current_i] {
-                $bundle.selection.bet = state.table.seats.moneys[current_i]
-                    .as_inner();
+            if let Some(range) = raise_range {
+                $bundle.selection.bet = range.clamp($bundle.selection.bet);
             }
 
             // TODO extract out a version of this that doesn't depend on hold'em
@@ -664,112 +1023,32 @@ pub fn update_and_render(
                 &state.table.seats.personalities[current_i]
             ) {
                 (true, _) => Some(Action::Fold),
-                (false, Some(_personality)) => {
-                    // TODO Base choice of action off of personality
-
-                    let hand = hands.get(current)
-                                .map(|&h| h)
-                                .unwrap_or_default();
-
-                    let mut action = match $community_opt {
-                        None => {
-                            let probability = hand_win_probability(hand);
-                            if probability >= SEVENTY_FIVE_PERCENT {
-                                let multiple = MoneyInner::from(xs::range(rng, 3..6));
-                                Action::Raise(minimum_raise_total + state.table.seats.large_blind_amount.get().saturating_mul(multiple))
-                            } else if probability >= FIFTY_PERCENT {
-                                if xs::range(rng, 0..5) == 0 {
-                                    // Don't be perfectly predictable!
-                                    gen_action(
-                                        rng,
-                                        ActionSpec {
-                                            one_past_max_money: NonZeroMoneyInner::MIN
-                                                .saturating_add(
-                                                    state.table.seats.moneys[current_i]
-                                                    .as_inner()
-                                                ),
-                                            min_money_unit: MIN_MONEY_UNIT,
-                                            minimum_raise_total,
-                                        }
-                                    )
-                                } else {
-                                    Action::Call
-                                }
-                            } else {
-                                Action::Fold
-                            }
-                        },
-                        Some(community_cards) => {
-                            let own_eval = evaluate::holdem_hand(
-                                community_cards,
-                                hand,
-                            );
-
-                            let other_hands = ALL_SORTED_HANDS.iter()
-                                .filter(|h| {
-                                    let is_already_used =
-                                    h[0] == hand[0]
-                                    || h[0] == hand[1]
-                                    || h[1] == hand[0]
-                                    || h[1] == hand[1]
-                                    || community_cards.contains(h[0])
-                                    || community_cards.contains(h[1]);
-
-                                    !is_already_used
-                                });
-
-                            let mut below_count = 0;
-                            let mut equal_count = 0;
-                            let mut above_count = 0;
-
-                            for other_hand in other_hands {
-                                use core::cmp::Ordering::*;
-                                let other_eval = evaluate::holdem_hand(
-                                    community_cards,
-                                    *other_hand,
-                                );
-
-                                match own_eval.cmp(&other_eval) {
-                                    Less => {
-                                        below_count += 1;
-                                    },
-                                    Equal => {
-                                        equal_count += 1;
-                                    },
-                                    Greater => {
-                                        above_count += 1;
-                                    },
-                                }
-                            }
-
-                            if below_count > (equal_count + above_count) {
-                                // TODO raise sometimes
-                                Action::Call
-                            } else {
-                                Action::Fold
-                            }
-                        }
+                (false, Some(strategy)) => {
+                    let stacks: [MoneyInner; MAX_PLAYERS as usize] = core::array::from_fn(
+                        |i| state.table.seats.moneys[i].as_inner()
+                    );
+                    let player_count = usize::from(hands.len().u8());
+
+                    let view = BettingView {
+                        cards: H::cards(
+                            hands,
+                            current,
+                            pot,
+                            round,
+                            state.table.seats.deck_composition,
+                        ),
+                        position: current,
+                        dealer,
+                        stacks: &stacks[..player_count],
+                        stats: &state.table.seats.stats[..player_count],
+                        pot,
+                        call_remainder,
+                        raise_range,
+                        raise_unit: H::raise_unit(&state.table.seats, round),
+                        allowed_kind_mode,
                     };
 
-                    match action {
-                        Action::Fold => {
-                            if call_remainder == 0 {
-                                action = Action::Call;
-                            }
-                        },
-                        Action::Call => {},
-                        Action::Raise(raise_amount) => {
-                            let inner = state.table.seats.moneys[current_i]
-                                .as_inner();
-                            if inner
-                                .checked_sub(raise_amount)
-                                .is_none() {
-                                action = Action::Raise(inner);
-                            }
-                        },
-                    }
-
-                    Some(action)
+                    Some(view.legal_action(strategy.betting_action(rng, &view)))
                 },
                 (false, None) => {
                     match group.ctx.hot {
@@ -847,7 +1126,8 @@ pub fn update_and_render(
                                     ActionKind::Call => {
                                         match allowed_kind_mode {
                                             AllowedKindMode::All
-                                            | AllowedKindMode::NoFolding => {
+                                            | AllowedKindMode::NoFolding
+                                            | AllowedKindMode::NoRaising => {
                                                 draw_money_in_rect!(group, call_remainder, money_rect);
                                             },
                                             AllowedKindMode::AllIn => {
@@ -938,6 +1218,10 @@ pub fn update_and_render(
                                         } else if group.input.pressed_this_frame(Button::DOWN) {
                                             $bundle.selection.bet = $bundle.selection.bet.saturating_sub(MIN_MONEY_UNIT.get());
                                         }
+                                        // Keep the chevrons within the amounts that can be raised.
+                                        if let Some(range) = raise_range {
+                                            $bundle.selection.bet = range.clamp($bundle.selection.bet);
+                                        }
                                     }
                                     _ => {}
                                 }
@@ -1178,6 +1462,24 @@ pub fn update_and_render(
             }
 
             if let Some(action) = action_opt {
+                // A raise can be left selected in the menu from an earlier turn,
+                // when raising is no longer allowed.
+                let action = match (action, raise_range) {
+                    (Action::Raise(_), None) => Action::Call,
+                    (Action::Raise(raise_amount), Some(range)) => {
+                        Action::Raise(range.clamp(raise_amount))
+                    },
+                    (action, _) => action,
+                };
+
+                if !pot.has_folded(current) {
+                    state.table.seats.stats[current_i].record_action(
+                        &action,
+                        call_remainder,
+                        round == H::FIRST_ROUND,
+                    );
+                }
+
                 let bet = match action {
                     Action::Fold => PotAction::Fold,
                     Action::Call => {
@@ -1262,6 +1564,15 @@ pub fn update_and_render(
 
     macro_rules! finish_round {
         () => {
+            state.table.seats.hands_played = state.table.seats.hands_played.saturating_add(1);
+            state.table.seats.levels.hand_played();
+            state.table.seats.standings.record_stacks(
+                state.table.seats.hands_played,
+                state.table.seats.starting_seats.iter()
+                    .zip(state.table.seats.moneys.iter())
+                    .map(|(&seat, money)| (seat, money.as_inner())),
+            );
+
             #[cfg(debug_assertions)]
             let expected_user_count = {
                 state.table.seats.moneys
@@ -1276,7 +1587,7 @@ pub fn update_and_render(
 
             // Condense players down
             {
-                let mut pairs: [(Money, Option<CpuPersonality>); MAX_PLAYERS as usize]
+                let mut pairs: [(Money, Personality, PlayerStats, StartingSeat); MAX_PLAYERS as usize]
                     = <_>::default();
 
                 let mut pair_index = 0;
@@ -1287,6 +1598,8 @@ pub fn update_and_render(
                     pairs[pair_index] = (
                         state.table.seats.moneys[i].take_all(),
                         state.table.seats.personalities[i].take(),
+                        core::mem::take(&mut state.table.seats.stats[i]),
+                        state.table.seats.starting_seats[i],
                     );
                     pair_index += 1;
                 }
@@ -1294,6 +1607,7 @@ pub fn update_and_render(
                 for i in 0..state.table.seats.moneys.len() {
                     assert_eq!(state.table.seats.moneys[i], 0);
                     state.table.seats.personalities[i] = None;
+                    state.table.seats.stats[i] = PlayerStats::default();
                 }
 
                 for i in 0..state.table.seats.moneys.len() {
@@ -1307,6 +1621,8 @@ pub fn update_and_render(
                         amount: NonZeroMoneyInner::MAX,
                     }.perform();
                     state.table.seats.personalities[i] = pairs[i].1.take();
+                    state.table.seats.stats[i] = pairs[i].2;
+                    state.table.seats.starting_seats[i] = pairs[i].3;
                 }
             }
 
@@ -1352,15 +1668,23 @@ pub fn update_and_render(
                 }
             }
 
-            if only_cpus_left && state.table.seats.skip == SkipState::Skip {
-                // TODO? Actually simulate the remaining turns, maybe with a timeout?
+            let hand_limit_reached = state.table.seats.hand_limit
+                .is_some_and(|limit| state.table.seats.hands_played >= limit);
+
+            state.table.seats.skip.simulate_if_only_cpus_left(only_cpus_left);
+
+            if hand_limit_reached {
                 speaker.request_sfx(SFX::CardPlace);
                 state.table.state = <_>::default();
                 cmd = ModeCmd::FinishedRound;
             } else {
                 match HandLen::try_from(remaining_player_count){
                     Ok(player_count) => {
-                        let (hands, deck) = models::holdem::deal(rng, player_count);
+                        let (hands, deck) = H::deal(
+                            rng,
+                            player_count,
+                            state.table.seats.deck_composition,
+                        );
 
                         let dealer = gen_hand_index(rng, player_count);
 
@@ -1368,25 +1692,25 @@ pub fn update_and_render(
 
                         let seats_ref = &mut state.table.seats;
 
-                        collect_blinds!(hands player_count dealer pot, seats_ref);
+                        seats_ref.update_forced_bets();
+
+                        H::collect_forced_bets(&hands, dealer, &mut pot, seats_ref);
+
+                        for stats in &mut seats_ref.stats[..player_count.usize()] {
+                            stats.start_hand();
+                        }
 
-                        next_bundle!(bundle = hands, deck, dealer, pot);
+                        next_bundle!(bundle = hands, deck, dealer, pot, H::FIRST_ROUND);
 
                         speaker.request_sfx(SFX::CardPlace);
-                        state.table.state = PreFlop {
+                        state.table.state = Betting {
                             bundle,
+                            round: H::FIRST_ROUND,
                         };
                     },
                     Err(_) => {
-                        // TODO show a winner screen with more winner info.
-                        if state.table.seats.personalities[0].is_none() {
-                            println!("User wins!");
-                        } else {
-                            println!("Cpu player wins!");
-                        }
-
                         speaker.request_sfx(SFX::CardPlace);
-                        state.table.state = <_>::default();
+                        state.table.state = Finished;
                         cmd = ModeCmd::FinishedRound;
                     },
                 };
@@ -1404,16 +1728,67 @@ pub fn update_and_render(
     macro_rules! award_now {
         ($hand_index: ident, $pot: expr) => {
             let i = usize::from($hand_index);
+            state.table.seats.standings.record_pot_won(
+                state.table.seats.starting_seats[i],
+                $pot.total(),
+            );
             $pot.award(&mut state.table.seats.moneys[i]);
 
             finish_round!();
         }
     }
 
+    if !matches!(state.table.state, Undealt { .. }) {
+        state.table.seats.levels.frame_passed();
+    }
+
     match &mut state.table.state {
+        Finished => {
+            match ui::do_standings(new_group!(), &state.table.seats.standings) {
+                StandingsCmd::NoOp => {},
+                StandingsCmd::PlayAgain => {
+                    let standings = &state.table.seats.standings;
+
+                    let mut moneys = [0; MAX_PLAYERS as usize];
+                    for (money, standing) in moneys.iter_mut().zip(standings.seats.iter()) {
+                        *money = standing.starting_money;
+                    }
+
+                    if let Ok(player_count) = HandLen::try_from(standings.player_count) {
+                        speaker.request_sfx(SFX::CardPlace);
+
+                        *state.table = Table::selected(
+                            rng,
+                            player_count,
+                            Money::array_from_inner_array(moneys),
+                            &standings.setups,
+                            state.table.seats.betting_structure,
+                            state.table.seats.deck_composition,
+                            state.table.seats.levels.schedule,
+                        );
+                    }
+                },
+                StandingsCmd::BackToTitleScreen => {
+                    cmd = ModeCmd::BackToTitleScreen;
+                },
+            }
+        },
+        Undealt {
+            ref player_count,
+            ref mut seats,
+            ref mut seat_editor,
+            ..
+        } if seat_editor.is_some() => {
+            ui::do_seat_editor(new_group!(), seats, player_count.u8(), seat_editor);
+        },
         Undealt {
             ref mut player_count,
             ref mut starting_money,
+            ref mut seats,
+            ref mut seat_editor,
+            ref mut betting_structure,
+            ref mut deck_composition,
+            ref mut level_schedule,
         } => {
             let group = new_group!();
 
@@ -1476,12 +1851,28 @@ pub fn update_and_render(
                 PlayerCountSelect,
             );
 
-            let starting_money_rect = unscaled::Rect {
-                x: unscaled::X(150),
-                y: unscaled::Y(100),
-                w: unscaled::W(50),
-                h: unscaled::H(100),
-            };
+            if do_button(
+                group,
+                ButtonSpec {
+                    id: EditSeats,
+                    rect: unscaled::Rect {
+                        x: player_count_rect.x,
+                        y: player_count_rect.y + player_count_rect.h,
+                        w: unscaled::W(50),
+                        h: unscaled::H(50),
+                    },
+                    text: b"seats",
+                }
+            ) {
+                *seat_editor = Some(SeatEditor::default());
+            }
+
+            let starting_money_rect = unscaled::Rect {
+                x: unscaled::X(150),
+                y: unscaled::Y(100),
+                w: unscaled::W(50),
+                h: unscaled::H(100),
+            };
 
             draw_money_in_rect!(group, starting_money, starting_money_rect);
 
@@ -1491,12 +1882,123 @@ pub fn update_and_render(
                 StartingMoneySelect,
             );
 
+            let mut options_right = starting_money_rect.x + starting_money_rect.w;
+
+            if H::HAS_OPTIONS {
+                let betting_structure_rect = unscaled::Rect {
+                    x: starting_money_rect.x + starting_money_rect.w,
+                    y: unscaled::Y(100),
+                    w: unscaled::W(50),
+                    h: unscaled::H(100),
+                };
+
+                {
+                    let betting_structure_text = betting_structure.text();
+
+                    let xy = gfx::center_line_in_rect(
+                        betting_structure_text.len() as _,
+                        betting_structure_rect,
+                    );
+                    group.commands.print_chars(
+                        betting_structure_text,
+                        xy.x,
+                        xy.y,
+                        TEXT
+                    );
+                }
+                {
+                    let limit_label = b"limit";
+
+                    let xy = gfx::center_line_in_rect(
+                        limit_label.len() as _,
+                        betting_structure_rect,
+                    );
+
+                    group.commands.print_chars(
+                        limit_label,
+                        xy.x,
+                        xy.y + gfx::CHAR_H,
+                        TEXT
+                    );
+                }
+
+                ui::draw_quick_select(
+                    group,
+                    betting_structure_rect,
+                    BettingStructureSelect,
+                );
+
+                let deck_composition_rect = unscaled::Rect {
+                    x: betting_structure_rect.x + betting_structure_rect.w,
+                    y: unscaled::Y(100),
+                    w: unscaled::W(50),
+                    h: unscaled::H(100),
+                };
+
+                {
+                    let deck_composition_text = deck_composition.text();
+
+                    let xy = gfx::center_line_in_rect(
+                        deck_composition_text.len() as _,
+                        deck_composition_rect,
+                    );
+                    group.commands.print_chars(
+                        deck_composition_text,
+                        xy.x,
+                        xy.y,
+                        TEXT
+                    );
+                }
+                {
+                    let deck_label = b"deck";
+
+                    let xy = gfx::center_line_in_rect(
+                        deck_label.len() as _,
+                        deck_composition_rect,
+                    );
+
+                    group.commands.print_chars(
+                        deck_label,
+                        xy.x,
+                        xy.y + gfx::CHAR_H,
+                        TEXT
+                    );
+                }
+
+                ui::draw_quick_select(
+                    group,
+                    deck_composition_rect,
+                    DeckCompositionSelect,
+                );
+
+                options_right = deck_composition_rect.x + deck_composition_rect.w;
+            }
+
+            let level_schedule_rect = unscaled::Rect {
+                x: options_right,
+                y: unscaled::Y(100),
+                w: unscaled::W(50),
+                h: unscaled::H(100),
+            };
+
+            ui::draw_level_schedule_in_rect(
+                group.commands,
+                *level_schedule,
+                level_schedule_rect,
+            );
+
+            ui::draw_quick_select(
+                group,
+                level_schedule_rect,
+                LevelScheduleSelect,
+            );
+
             if do_button(
                 group,
                 ButtonSpec {
                     id: Submit,
                     rect: unscaled::Rect {
-                        x: starting_money_rect.x + starting_money_rect.w,
+                        x: level_schedule_rect.x + level_schedule_rect.w,
                         y: unscaled::Y(100),
                         w: unscaled::W(50),
                         h: unscaled::H(100),
@@ -1510,7 +2012,7 @@ pub fn update_and_render(
 
                 let mut moneys = [0; MAX_PLAYERS as usize];
                 for i in 0..player_count.usize() {
-                    moneys[i] = *starting_money;
+                    moneys[i] = seats[i].starting_money;
                 }
                 let moneys = Money::array_from_inner_array(moneys);
 
@@ -1518,13 +2020,23 @@ pub fn update_and_render(
                     rng,
                     player_count,
                     moneys,
+                    &seats[..],
+                    *betting_structure,
+                    *deck_composition,
+                    *level_schedule,
                 );
             } else {
-                let menu = [BackToTitleScreen, PlayerCountSelect, StartingMoneySelect, Submit];
+                let menu: &[ui::Id] = if H::HAS_OPTIONS {
+                    &[BackToTitleScreen, PlayerCountSelect, EditSeats, StartingMoneySelect, BettingStructureSelect, DeckCompositionSelect, LevelScheduleSelect, Submit]
+                } else {
+                    &[BackToTitleScreen, PlayerCountSelect, EditSeats, StartingMoneySelect, LevelScheduleSelect, Submit]
+                };
+                let menu_i = menu.iter()
+                    .position(|&id| id == group.ctx.hot)
+                    .unwrap_or_default();
 
                 match group.ctx.hot {
                     BackToTitleScreen => {
-                        let menu_i = 0;
 
                         match input.dir_pressed_this_frame() {
                             Some(Dir::Up | Dir::Left) => {},
@@ -1535,16 +2047,72 @@ pub fn update_and_render(
                         }
                     }
                     StartingMoneySelect => {
-                        let menu_i = 2;
                         match input.dir_pressed_this_frame() {
                             Some(Dir::Up) => {
+                                let previous = *starting_money;
                                 *starting_money = starting_money.saturating_add(MIN_MONEY_UNIT.get());
+                                set_unedited_starting_money(seats, previous, *starting_money);
                             },
                             Some(Dir::Down) => {
+                                let previous = *starting_money;
                                 *starting_money = starting_money.saturating_sub(MIN_MONEY_UNIT.get());
                                 if *starting_money == 0 {
                                     *starting_money = MIN_MONEY_UNIT.get();
                                 }
+                                set_unedited_starting_money(seats, previous, *starting_money);
+                            },
+                            Some(Dir::Left) => {
+                                group.ctx.set_next_hot(menu[menu_i - 1]);
+                            }
+                            Some(Dir::Right) => {
+                                group.ctx.set_next_hot(menu[menu_i + 1]);
+                            }
+                            None => {}
+                        }
+                    }
+                    BettingStructureSelect => {
+                        match input.dir_pressed_this_frame() {
+                            Some(Dir::Up) => {
+                                *betting_structure = betting_structure.next_up();
+                            },
+                            Some(Dir::Down) => {
+                                *betting_structure = betting_structure.next_down();
+                            },
+                            Some(Dir::Left) => {
+                                group.ctx.set_next_hot(menu[menu_i - 1]);
+                            }
+                            Some(Dir::Right) => {
+                                group.ctx.set_next_hot(menu[menu_i + 1]);
+                            }
+                            None => {}
+                        }
+                    }
+                    DeckCompositionSelect => {
+                        match input.dir_pressed_this_frame() {
+                            Some(Dir::Up) => {
+                                *deck_composition = deck_composition.next_up();
+                                *player_count = clamp_player_count::<H>(*player_count, *deck_composition);
+                            },
+                            Some(Dir::Down) => {
+                                *deck_composition = deck_composition.next_down();
+                                *player_count = clamp_player_count::<H>(*player_count, *deck_composition);
+                            },
+                            Some(Dir::Left) => {
+                                group.ctx.set_next_hot(menu[menu_i - 1]);
+                            }
+                            Some(Dir::Right) => {
+                                group.ctx.set_next_hot(menu[menu_i + 1]);
+                            }
+                            None => {}
+                        }
+                    }
+                    LevelScheduleSelect => {
+                        match input.dir_pressed_this_frame() {
+                            Some(Dir::Up) => {
+                                *level_schedule = level_schedule.next_up();
+                            },
+                            Some(Dir::Down) => {
+                                *level_schedule = level_schedule.next_down();
                             },
                             Some(Dir::Left) => {
                                 group.ctx.set_next_hot(menu[menu_i - 1]);
@@ -1556,10 +2124,12 @@ pub fn update_and_render(
                         }
                     }
                     PlayerCountSelect => {
-                        let menu_i = 1;
                         match input.dir_pressed_this_frame() {
                             Some(Dir::Up) => {
-                                *player_count = player_count.saturating_add_1();
+                                *player_count = clamp_player_count::<H>(
+                                    player_count.saturating_add_1(),
+                                    *deck_composition,
+                                );
                             },
                             Some(Dir::Down) => {
                                 *player_count = player_count.saturating_sub_1();
@@ -1573,8 +2143,18 @@ pub fn update_and_render(
                             None => {}
                         }
                     }
+                    EditSeats => {
+                        match input.dir_pressed_this_frame() {
+                            Some(Dir::Left) => {
+                                group.ctx.set_next_hot(menu[menu_i - 1]);
+                            }
+                            Some(Dir::Right) => {
+                                group.ctx.set_next_hot(menu[menu_i + 1]);
+                            }
+                            _ => {}
+                        }
+                    }
                     Submit => {
-                        let menu_i = menu.len() - 1;
                         match input.dir_pressed_this_frame() {
                             Some(Dir::Left) => {
                                 group.ctx.set_next_hot(menu[menu_i - 1]);
@@ -1590,105 +2170,52 @@ pub fn update_and_render(
                 }
             }
         },
-        PreFlop { bundle } => {
+        Betting { bundle, round } => {
             let group = new_group!();
-            let outcome = do_holdem_hands!(group, bundle, None);
 
-            match outcome {
-                RoundOutcome::Undetermined => {},
-                RoundOutcome::AdvanceToNext => {
-                    let community_cards =
-                        models::holdem::deal_community_cards(
-                            &mut bundle.deck,
-                        ).expect("Deck ran out!?");
-                    next_bundle!(
-                        new_bundle =
-                            bundle.hands.clone(),
-                            bundle.deck.clone(),
-                            bundle.dealer,
-                            bundle.pot.clone()
-                    );
-                    speaker.request_sfx(SFX::CardPlace);
-                    state.table.state = PostFlop {
-                        bundle: new_bundle,
-                        community_cards
-                    };
-                },
-                RoundOutcome::AwardNow(hand_index) => {
-                    award_now!(hand_index, bundle.pot);
-                },
-            }
-        },
-        PostFlop { bundle, community_cards } => {
-            let group = new_group!();
-
-            group.commands.draw_holdem_community_cards(
-                *community_cards,
-                COMMUNITY_BASE_X,
-                COMMUNITY_BASE_Y,
-            );
+            H::draw_round(group.commands, *round);
 
-            let outcome = do_holdem_hands!(group, bundle, Some(*community_cards));
+            let outcome = if !bundle.pot.has_folded(bundle.current)
+            && ui::do_pass_device_if_needed(
+                group,
+                bundle.current,
+                state.table.seats.starting_seats[usize::from(bundle.current)],
+                &state.table.seats.personalities[..usize::from(bundle.hands.len().u8())],
+                &mut state.table.seats.hot_seat,
+                &state.table.seats.standings.setups,
+            ) {
+                RoundOutcome::Undetermined
+            } else {
+                do_holdem_hands!(group, bundle, *round)
+            };
 
             match outcome {
                 RoundOutcome::Undetermined => {},
                 RoundOutcome::AdvanceToNext => {
-                    match *community_cards {
-                        CommunityCards::Flop(flop) => {
-                            bundle.deck.burn();
-                            if let Some(turn) = bundle.deck.draw() {
-                                *community_cards = CommunityCards::Turn(flop, turn);
-                            } else {
-                                debug_assert!(false, "Ran out of cards for turn!");
-                            }
-
+                    match H::next_round(*round, &mut bundle.deck) {
+                        Next::Round(round) => {
                             next_bundle!(
                                 new_bundle =
                                     bundle.hands.clone(),
                                     bundle.deck.clone(),
                                     bundle.dealer,
-                                    bundle.pot.clone()
+                                    bundle.pot.clone(),
+                                    round
                             );
                             speaker.request_sfx(SFX::CardPlace);
-                            state.table.state = PostFlop {
+                            state.table.state = Betting {
                                 bundle: new_bundle,
-                                community_cards: *community_cards,
+                                round,
                             };
                         },
-                        CommunityCards::Turn(flop, turn) => {
-                            bundle.deck.burn();
-                            if let Some(river) = bundle.deck.draw() {
-                                *community_cards = CommunityCards::River(flop, turn, river);
-                            } else {
-                                debug_assert!(false, "Ran out of cards for river!");
-                            }
-
-                            next_bundle!(
-                                new_bundle =
-                                    bundle.hands.clone(),
-                                    bundle.deck.clone(),
-                                    bundle.dealer,
-                                    bundle.pot.clone()
-                            );
-                            speaker.request_sfx(SFX::CardPlace);
-                            state.table.state = PostFlop {
-                                bundle: new_bundle,
-                                community_cards: *community_cards,
-                            };
-                        }
-                        CommunityCards::River(flop, turn, river) => {
+                        Next::Showdown(board) => {
                             speaker.request_sfx(SFX::CardPlace);
                             state.table.state = Showdown {
                                 bundle: bundle.clone(),
-                                full_board: [
-                                    flop[0],
-                                    flop[1],
-                                    flop[2],
-                                    turn,
-                                    river
-                                ],
+                                board,
+                                first_pot: 0,
                             };
-                        }
+                        },
                     }
                 },
                 RoundOutcome::AwardNow(hand_index) => {
@@ -1696,7 +2223,7 @@ pub fn update_and_render(
                 },
             }
         },
-        Showdown { bundle, full_board } => {
+        Showdown { bundle, board, first_pot } => {
             debug_assert!(bundle.pot.total() > 0);
 
             let group = new_group!();
@@ -1704,9 +2231,6 @@ pub fn update_and_render(
             // If we'd be able to see something under the modal, sure.
             //let _outcome = do_holdem_hands!(group, bundle);
 
-            // TODO draw a modal that shows who won how much, and have
-            // a button to go on to the next game.
-
             group.commands.draw_nine_slice(
                 gfx::NineSlice::Window,
                 FULLSCREEN_MODAL_RECT
@@ -1715,11 +2239,13 @@ pub fn update_and_render(
             #[derive(Debug, Default)]
             struct Award {
                 amount: MoneyInner,
-                eval: evaluate::Eval,
             }
             type Awards = PerPlayer<[Award; MAX_POTS as usize]>;
 
-            let awards: Awards = {
+            /// Each main or side pot, as shown on the results screen.
+            type PotResults = [PotSplit; MAX_POTS as usize];
+
+            let (awards, pot_results): (Awards, PotResults) = {
                 debug_assert_eq!(
                     bundle.pot.eligibilities(&state.table.seats.moneys)
                             .map(|(_, n)| n)
@@ -1729,71 +2255,43 @@ pub fn update_and_render(
                 );
 
                 let mut awards = Awards::default();
-
-                for (eligibile_players, amount) in bundle.pot.eligibilities(&state.table.seats.moneys) {
-                    let mut winner_count = 0;
-                    let mut winners = [
-                        (0, evaluate::Eval::WORST);
-                        MAX_POTS as usize
-                    ];
-
-                    for player in eligibile_players.iter() {
-                        let best_eval = {
-                            let Some(hand) = bundle.hands.get(player) else {
-                                debug_assert!(false, "Hand not found for {player}");
-                                continue
-                            };
-                            evaluate::holdem_hand(
-                                CommunityCards::from(*full_board),
-                                *hand,
-                            )
-                        };
-
-                        use core::cmp::Ordering::*;
-                        match best_eval.cmp(&winners[0].1) {
-                            Greater => {
-                                winner_count = 1;
-                                winners[winner_count - 1] = (player, best_eval);
-                            },
-                            Equal => {
-                                winner_count += 1;
-                                winners[winner_count - 1] = (player, best_eval);
-                            },
-                            Less => {
-                                // next iteration
-                            }
+                let mut pot_results = PotResults::default();
+
+                let splits = bundle.pot.best_hand_splits(
+                    &state.table.seats.moneys,
+                    |player| match bundle.hands.get(player) {
+                        Some(hand) => hand.eval(
+                            state.table.seats.deck_composition,
+                            *board,
+                        ),
+                        None => {
+                            debug_assert!(false, "Hand not found for {player}");
+                            evaluate::Eval::WORST
                         }
-                    }
-
-                    debug_assert!(winner_count > 0);
-
-                    let award_amounts: PerPlayer<MoneyInner> = {
-                        let mut award_amounts = PerPlayer::<MoneyInner>::default();
-
-                        models::split_among(
-                            amount,
-                            &mut award_amounts[0..winner_count],
-                            usize::from(bundle.current)
-                        );
-                        
-                        award_amounts
-                    };
-                    for i in 0..winner_count {
-                        let (winner_index, winner_eval) = winners[i];
+                    },
+                    bundle.current,
+                );
 
-                        let amount = award_amounts[i];
+                for (pot_i, split) in splits.enumerate() {
+                    for (winner_index, &amount) in split.shares.iter().enumerate() {
+                        if amount == 0 {
+                            continue
+                        }
 
                         // Push an award on
-                        for award in &mut awards[usize::from(winner_index)] {
+                        for award in &mut awards[winner_index] {
                             if award.amount == 0 {
                                 *award = Award {
                                     amount,
-                                    eval: winner_eval,
                                 };
                                 break
                             }
                         }
                     }
+
+                    if let Some(result) = pot_results.get_mut(pot_i) {
+                        *result = split;
+                    }
                 }
 
                 debug_assert_eq!(
@@ -1812,86 +2310,195 @@ pub fn update_and_render(
                     "Awarded total did not match pot total!"
                 );
 
-                awards
+                (awards, pot_results)
             };
 
-            {
-                let mut y = unscaled::Y(gfx::CHAR_LINE_ADVANCE.get());
-                for (i, award_array) in awards.iter().enumerate() {
-                    let mut any_non_zero = false;
-                    for Award { amount, .. } in award_array {
-                        if *amount != 0 {
-                            any_non_zero = true;
-                            break
+            let w = unscaled::W(50);
+            let h = unscaled::H(20);
+
+            let submit_rect = unscaled::Rect {
+                x: unscaled::X(0) + ((command::WIDTH_W/2) - (w/2)),
+                y: unscaled::Y(0) + (command::HEIGHT_H - (h + SPACING_H)),
+                w,
+                h,
+            };
+
+            // Leave room for the skip select, which is taller than the button.
+            let max_text_y = submit_rect.y - (submit_rect.h + gfx::CHAR_LINE_ADVANCE);
+
+            const POTS_X: unscaled::X = unscaled::X(SPACING_W.get() * 2);
+            const HANDS_X: unscaled::X = unscaled::X(190);
+
+            // The name of the player at the given hand index.
+            macro_rules! seat_name {
+                ($i: expr) => {
+                    state.table.seats.standings.name(
+                        state.table.seats.starting_seats[usize::from($i)]
+                    )
+                }
+            }
+
+            let pot_count = pot_results.iter()
+                .take_while(|result| result.amount != 0)
+                .count();
+            *first_pot = core::cmp::min(*first_pot, pot_count.saturating_sub(1) as u8);
+
+            // Who put in what, and who took it. When not every pot fits, the
+            // ones that do are listed starting from `first_pot`.
+            let pots_shown = {
+                let mut y = unscaled::Y(0) + SPACING_H * 2;
+                let mut pots_shown = 0;
+
+                'pots: for (pot_i, result) in pot_results[..pot_count]
+                    .iter()
+                    .enumerate()
+                    .skip(usize::from(*first_pot))
+                {
+
+                    let mut pot_text: [u8; 32] = [0; 32];
+                    let _cant_actually_fail = if pot_i == 0 {
+                        write!(&mut pot_text[..], "main pot ${}", result.amount)
+                    } else {
+                        write!(&mut pot_text[..], "side pot {pot_i} ${}", result.amount)
+                    };
+
+                    if y > max_text_y { break 'pots }
+                    group.commands.print_chars(&pot_text, POTS_X, y, TEXT);
+                    y += gfx::CHAR_LINE_ADVANCE;
+
+                    let mut x = POTS_X;
+                    if y > max_text_y { break 'pots }
+                    group.commands.print_chars(b"in:", x, y, TEXT);
+                    x += gfx::CHAR_ADVANCE * 3;
+                    for player in result.eligible.iter() {
+                        let mut player_text: [u8; 16] = [0; 16];
+                        let _cant_actually_fail = write!(
+                            &mut player_text[..],
+                            " {}",
+                            seat_name!(player),
+                        );
+                        let player_w = pre_nul_len(&player_text) * gfx::CHAR_ADVANCE;
+
+                        if x + player_w > HANDS_X - SPACING_W {
+                            x = POTS_X + gfx::CHAR_ADVANCE * 3;
+                            y += gfx::CHAR_LINE_ADVANCE;
+                            if y > max_text_y { break 'pots }
+                        }
+
+                        group.commands.print_chars(&player_text, x, y, TEXT);
+                        x += player_w;
+                    }
+                    y += gfx::CHAR_LINE_ADVANCE;
+
+                    for (player, share) in result.shares.iter().enumerate() {
+                        if *share == 0 {
+                            continue
                         }
+
+                        let mut won_text: [u8; 32] = [0; 32];
+                        let _cant_actually_fail = write!(
+                            &mut won_text[..],
+                            "{} won ${share}",
+                            seat_name!(player),
+                        );
+
+                        if y > max_text_y { break 'pots }
+                        group.commands.print_chars(&won_text, POTS_X, y, YELLOW_INDEX);
+                        y += gfx::CHAR_LINE_ADVANCE;
                     }
-                    if !any_non_zero {
+
+                    pots_shown += 1;
+                    y += gfx::CHAR_LINE_ADVANCE;
+                }
+
+                pots_shown
+            };
+            let has_more_pots = usize::from(*first_pot) + pots_shown < pot_count;
+
+            if *first_pot > 0 || has_more_pots {
+                let mut scroll_text: [u8; 32] = [0; 32];
+                let _cant_actually_fail = write!(
+                    &mut scroll_text[..],
+                    "pots {}-{} of {pot_count}, up/down",
+                    *first_pot + 1,
+                    usize::from(*first_pot) + pots_shown,
+                );
+                group.commands.print_chars(
+                    &scroll_text,
+                    POTS_X,
+                    max_text_y + gfx::CHAR_LINE_ADVANCE,
+                    TEXT
+                );
+            }
+
+            // Every hand that made it to the showdown, with the cards that
+            // won highlighted.
+            {
+                let mut y = unscaled::Y(0) + SPACING_H * 2;
+
+                for (i, hand) in bundle.hands.iter().enumerate() {
+                    let player = i as PlayerIndex;
+                    if bundle.pot.has_folded(player) {
                         continue
                     }
+                    if y > max_text_y {
+                        break
+                    }
 
-                    let mut player_text = [0 as u8; 20];
-                    player_text[0] = b'p';
-                    player_text[1] = b'l';
-                    player_text[2] = b'a';
-                    player_text[3] = b'y';
-                    player_text[4] = b'e';
-                    player_text[5] = b'r';
-                    player_text[6] = b' ';
+                    let won = awards[i].iter().any(|award| award.amount != 0);
 
-                    let _cant_actually_fail = write!(
-                        &mut player_text[7..],
-                        "{i}",
+                    let (best_five, category) = hand.best_five(
+                        state.table.seats.deck_composition,
+                        *board,
                     );
 
+                    let mut player_text: [u8; 16] = [0; 16];
+                    let _cant_actually_fail = write!(
+                        &mut player_text[..],
+                        "{}:",
+                        seat_name!(i),
+                    );
                     group.commands.print_chars(
                         &player_text,
-                        COMMUNITY_BASE_X - (pre_nul_len(&player_text) * gfx::CHAR_ADVANCE),
+                        HANDS_X,
                         y,
-                        TEXT
+                        if won { YELLOW_INDEX } else { TEXT }
                     );
 
-                    y += gfx::CHAR_LINE_ADVANCE;
-
-                    for Award { amount, eval } in award_array {
-                        if *amount == 0 {
-                            break
+                    let mut x = HANDS_X + gfx::CHAR_ADVANCE * (SeatName::LEN as unscaled::Inner + 2);
+                    let hand = hand.as_ref();
+                    let (board_cards, board_len) = H::board_cards(*board);
+                    for (card_i, card) in hand.iter().chain(board_cards[..board_len].iter()).enumerate() {
+                        if card_i == hand.len() {
+                            // Set the hole cards apart from the board.
+                            x += gfx::CHAR_ADVANCE;
                         }
 
-                        stack_money_text!(amount_text = amount);
-
-                        group.commands.print_chars(
-                            &amount_text,
-                            COMMUNITY_BASE_X - (pre_nul_len(&amount_text) * gfx::CHAR_ADVANCE),
-                            y,
+                        let colour = if !won {
                             TEXT
-                        );
+                        } else if best_five.contains(card) {
+                            YELLOW_INDEX
+                        } else {
+                            GREY_INDEX
+                        };
 
-                        stack_eval_text!(eval_text = eval);
+                        group.commands.print_card(*card, x, y, colour);
+                        x += gfx::CHAR_ADVANCE * 3;
+                    }
 
-                        group.commands.print_chars(
-                            &eval_text ,
-                            COMMUNITY_BASE_X + gfx::CHAR_ADVANCE,
-                            y,
-                            TEXT
-                        );
+                    stack_eval_text!(category_text = category.text());
 
-                        y += gfx::CHAR_LINE_ADVANCE;
-                    }
+                    group.commands.print_chars(
+                        &category_text,
+                        x + gfx::CHAR_ADVANCE,
+                        y,
+                        TEXT
+                    );
 
                     y += gfx::CHAR_LINE_ADVANCE;
                 }
             }
 
-            let w = unscaled::W(50);
-            let h = unscaled::H(20);
-
-            let submit_rect = unscaled::Rect {
-                x: unscaled::X(0) + ((command::WIDTH_W/2) - (w/2)),
-                y: unscaled::Y(0) + (command::HEIGHT_H - (h + SPACING_H)),
-                w,
-                h,
-            };
-
             {
                 let skip_rect = unscaled::Rect {
                     x: submit_rect.x - (w * 2),
@@ -1901,7 +2508,7 @@ pub fn update_and_render(
                 };
 
                 let skip_text: &[u8] = match state.table.seats.skip {
-                    SkipState::Skip => b"skip cpu only",
+                    SkipState::Skip | SkipState::Simulating => b"skip cpu only",
                     SkipState::Watch => b"watch cpu only",
                 };
 
@@ -1930,9 +2537,22 @@ pub fn update_and_render(
                 ButtonSpec {
                     id: ShowdownSubmit,
                     rect: submit_rect,
-                    text: b"submit",
+                    text: b"continue",
                 }
             ) {
+                for i in 0..bundle.hands.len().u8() {
+                    if !bundle.pot.has_folded(i) {
+                        state.table.seats.stats[usize::from(i)].record_showdown();
+                    }
+                }
+
+                for (i, pots) in awards.iter().enumerate() {
+                    state.table.seats.standings.record_pot_won(
+                        state.table.seats.starting_seats[i],
+                        pots.iter().map(|award| award.amount).sum(),
+                    );
+                }
+
                 bundle.pot.award_multiple(
                     &mut state.table.seats.moneys,
                     awards.iter()
@@ -1951,14 +2571,14 @@ pub fn update_and_render(
                             Some(Dir::Up) => {
                                 state.table.seats.skip =
                                     match state.table.seats.skip {
-                                        SkipState::Skip => SkipState::Watch,
+                                        SkipState::Skip | SkipState::Simulating => SkipState::Watch,
                                         SkipState::Watch => SkipState::Skip,
                                     };
                             },
                             Some(Dir::Down) => {
                                 state.table.seats.skip =
                                     match state.table.seats.skip {
-                                        SkipState::Skip => SkipState::Watch,
+                                        SkipState::Skip | SkipState::Simulating => SkipState::Watch,
                                         SkipState::Watch => SkipState::Skip,
                                     };
                             },
@@ -1973,7 +2593,13 @@ pub fn update_and_render(
                             Some(Dir::Left | Dir::Right) => {
                                 group.ctx.set_next_hot(SkipRemainderOfGameSelect);
                             }
-                            Some(Dir::Up | Dir::Down) | None => {}
+                            Some(Dir::Up) => {
+                                *first_pot = first_pot.saturating_sub(1);
+                            }
+                            Some(Dir::Down) if has_more_pots => {
+                                *first_pot += 1;
+                            }
+                            Some(Dir::Down) | None => {}
                         }
                     }
                     _ => {
@@ -1986,3 +2612,169 @@ pub fn update_and_render(
 
     cmd
 }
+
+#[test]
+fn only_the_human_who_was_passed_the_device_sees_their_hole_cards() {
+    use platform_types::sprite;
+
+    fn card_sprite(card: Card) -> sprite::XY {
+        let mut commands = Commands::default();
+        commands.draw_card(card, unscaled::X(0), unscaled::Y(0));
+        commands.slice()[0].sprite_xy
+    }
+
+    let mut rng = xs::from_seed([3; 16]);
+
+    let mut setups = default_seat_setups(1000);
+    setups[1].is_human = true;
+
+    let mut moneys = [0; MAX_PLAYERS as usize];
+    moneys[0] = 1000;
+    moneys[1] = 1000;
+
+    let mut table = Table::<Hand>::selected(
+        &mut rng,
+        PlayerCount::try_from(2).unwrap(),
+        Money::array_from_inner_array(moneys),
+        &setups[..],
+        <_>::default(),
+        <_>::default(),
+        <_>::default(),
+    );
+
+    let mut ctx = ui::Context::default();
+    let mut commands = Commands::default();
+    let mut speaker = Speaker::default();
+
+    macro_rules! frame {
+        ($gamepad: expr, $previous_gamepad: expr) => {
+            ctx.frame_init();
+            commands.clear();
+            speaker.clear();
+
+            update_and_render(
+                &mut commands,
+                State {
+                    rng: &mut rng,
+                    ctx: &mut ctx,
+                    table: &mut table,
+                },
+                Input { gamepad: $gamepad, previous_gamepad: $previous_gamepad },
+                &mut speaker,
+            );
+        }
+    }
+
+    let TableState::Betting { ref bundle, .. } = table.state else {
+        panic!("The table should start betting");
+    };
+    let acting = bundle.current;
+    let hands = bundle.hands.clone();
+
+    // Both humans have hands to hide, so nobody sees a table yet.
+    frame!(<_>::default(), <_>::default());
+    let shown = |commands: &Commands, index: HandIndex| {
+        hands.get(index).unwrap().as_ref().iter().all(|&card| {
+            commands.slice().iter().any(|command| command.sprite_xy == card_sprite(card))
+        })
+    };
+    assert!(!shown(&commands, 0));
+    assert!(!shown(&commands, 1));
+
+    frame!(Button::A, <_>::default());
+    frame!(<_>::default(), Button::A);
+    // The table comes back a frame after the pass, and the acting hand is
+    // selected the frame after that.
+    frame!(<_>::default(), <_>::default());
+    frame!(<_>::default(), <_>::default());
+
+    assert!(shown(&commands, acting));
+    assert!(!shown(&commands, 1 - acting));
+}
+
+#[test]
+fn side_pots_that_do_not_fit_can_be_scrolled_to() {
+    let mut rng = xs::from_seed([4; 16]);
+
+    let mut setups = default_seat_setups(1000);
+    for setup in &mut setups {
+        setup.is_human = false;
+    }
+
+    // Everyone has a different stack, so going all in makes a side pot for
+    // each of them.
+    let moneys: [MoneyInner; MAX_PLAYERS as usize] = core::array::from_fn(
+        |i| 1000 + 100 * i as MoneyInner
+    );
+
+    let mut table = Table::<Hand>::selected(
+        &mut rng,
+        PlayerCount::try_from(MAX_PLAYERS).unwrap(),
+        Money::array_from_inner_array(moneys),
+        &setups[..],
+        <_>::default(),
+        <_>::default(),
+        <_>::default(),
+    );
+
+    let TableState::Betting { ref bundle, .. } = table.state else {
+        panic!("The table should start betting");
+    };
+    let mut bundle = bundle.clone();
+    for i in 0..MAX_PLAYERS {
+        let all_in = table.seats.moneys[usize::from(i)].take_all();
+        bundle.pot.push_bet(i, PotAction::Bet(all_in));
+    }
+    table.state = TableState::Showdown {
+        bundle,
+        board: [40, 41, 42, 27, 1],
+        first_pot: 0,
+    };
+
+    let mut ctx = ui::Context::default();
+    let mut commands = Commands::default();
+    let mut speaker = Speaker::default();
+
+    let mut press = |table: &mut Table, button: Button| -> u8 {
+        for input in [
+            Input { gamepad: button, previous_gamepad: <_>::default() },
+            Input { gamepad: <_>::default(), previous_gamepad: button },
+        ] {
+            ctx.frame_init();
+            commands.clear();
+            speaker.clear();
+
+            update_and_render(
+                &mut commands,
+                State {
+                    rng: &mut rng,
+                    ctx: &mut ctx,
+                    table,
+                },
+                input,
+                &mut speaker,
+            );
+        }
+
+        let TableState::Showdown { first_pot, .. } = table.state else {
+            panic!("The showdown should still be shown");
+        };
+        first_pot
+    };
+
+    // The first press makes the continue button hot.
+    assert_eq!(press(&mut table, Button::DOWN), 0);
+
+    let mut first_pots = vec![];
+    for _ in 0..MAX_POTS {
+        first_pots.push(press(&mut table, Button::DOWN));
+    }
+    let last_first_pot = *first_pots.last().unwrap();
+
+    // It scrolls one pot at a time, and stops once the last pot is shown.
+    assert!(last_first_pot > 0);
+    assert!(last_first_pot < MAX_POTS - 1);
+    assert!(first_pots.windows(2).all(|pair| pair[1] <= pair[0] + 1));
+
+    assert_eq!(press(&mut table, Button::UP), last_first_pot - 1);
+}
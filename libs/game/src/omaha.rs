@@ -0,0 +1,51 @@
+//! Omaha is played like hold'em, apart from the hole cards: each player gets
+//! four, and must use exactly two of them. So the table and the betting are
+//! hold'em's, and this module only supplies the hand.
+use gfx::{Commands, OmahaFacing};
+pub use models::{omaha::{Hand, MIN_PLAYERS, MAX_PLAYERS}};
+use models::{BettingStructure, DeckComposition, holdem::CommunityCards};
+use platform_types::unscaled;
+
+pub use crate::holdem::{HandIndex, PlayerCount, PlayerIndex, Seats, update_and_render};
+use crate::holdem::{self, HoleCards};
+use crate::strategy::Cards;
+
+pub type Table = holdem::Table<Hand>;
+
+fn omaha_facing(facing: Option<Hand>) -> OmahaFacing {
+    match facing {
+        Some(hand) => OmahaFacing::Up(hand),
+        None => OmahaFacing::Down,
+    }
+}
+
+impl HoleCards for Hand {
+    /// This is Pot-Limit Omaha unless another structure is selected.
+    const DEFAULT_BETTING_STRUCTURE: BettingStructure = BettingStructure::PotLimit;
+
+    const HAND_WIDTH: unscaled::W = Commands::OMAHA_HAND_WIDTH;
+
+    fn draw(commands: &mut Commands, facing: Option<Self>, x: unscaled::X, y: unscaled::Y) {
+        commands.draw_omaha_hand(omaha_facing(facing), x, y);
+    }
+
+    fn draw_folded(commands: &mut Commands, facing: Option<Self>, x: unscaled::X, y: unscaled::Y) {
+        commands.draw_folded_omaha_hand(omaha_facing(facing), x, y);
+    }
+
+    fn draw_underlight(commands: &mut Commands, x: unscaled::X, y: unscaled::Y) {
+        commands.draw_omaha_hand_underlight(x, y);
+    }
+
+    fn draw_selected(commands: &mut Commands, x: unscaled::X, y: unscaled::Y) {
+        commands.draw_omaha_hand_selected(x, y);
+    }
+
+    fn cards(self, community_opt: Option<CommunityCards>, deck: DeckComposition) -> Cards {
+        Cards::Omaha {
+            hand: self,
+            community_opt,
+            deck,
+        }
+    }
+}
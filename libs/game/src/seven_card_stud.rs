@@ -0,0 +1,232 @@
+//! Seven-card stud has no community cards. Each player gets their own cards,
+//! some face up, over five streets with a betting round on each. The table,
+//! betting and showdown are hold'em's, and this module supplies the rest.
+use gfx::{SPACING_H, SPACING_W, Commands, MixedFacing};
+pub use models::stud::{Hand, MIN_PLAYERS, MAX_PLAYERS};
+use models::{BettingStructure, BetKind, Card, Deck, DeckComposition, NonZeroMoneyInner, Pot, PotAction, holdem::{Hands, PlayerAmount}, stud::{self, Street, UpCards}};
+use platform_types::{command, unscaled};
+
+use xs::Xs;
+
+pub use crate::holdem::{HandIndex, PlayerCount, PlayerIndex, Seats, update_and_render};
+use crate::holdem::{self, Next, Variant};
+use crate::strategy::Cards;
+
+pub type Table = holdem::Table<Hand>;
+
+/// The player with the lowest face up card on third street brings in.
+fn bring_in_index(hands: &Hands<Hand>) -> HandIndex {
+    let mut output = 0;
+    let mut lowest = None;
+    for (i, hand) in hands.iter().enumerate() {
+        let key = stud::bring_in_key(hand[2]);
+        if lowest.is_none_or(|lowest| key < lowest) {
+            output = i as HandIndex;
+            lowest = Some(key);
+        }
+    }
+    output
+}
+
+/// The cards of `hand` dealt by `street`, with the face up ones facing up, and
+/// the rest peeked at if `show` is true.
+fn facings(hand: Hand, street: Street, show: bool) -> [MixedFacing; stud::HAND_LEN as usize] {
+    let mut facings = [MixedFacing::Down; stud::HAND_LEN as usize];
+    for (card_index, &card) in hand[..usize::from(street.card_count())].iter().enumerate() {
+        facings[card_index] = if stud::is_up(card_index as u8) {
+            MixedFacing::Up(card)
+        } else if show {
+            MixedFacing::Peeked(card)
+        } else {
+            MixedFacing::Down
+        };
+    }
+    facings
+}
+
+impl Variant for Hand {
+    type Round = Street;
+    /// There are no community cards.
+    type Board = ();
+
+    const FIRST_ROUND: Street = Street::Third;
+
+    /// Seven-card stud is traditionally played fixed-limit.
+    const DEFAULT_BETTING_STRUCTURE: BettingStructure = BettingStructure::FixedLimit;
+
+    const HAS_OPTIONS: bool = false;
+
+    const HAS_CHART: bool = false;
+
+    const HAND_WIDTH: unscaled::W = Commands::STUD_HAND_WIDTH;
+
+    // The rows overlap, but the top of each card, with the rank and suit,
+    // stays visible.
+    const ROW_HEIGHT: unscaled::H = unscaled::H(
+        (Commands::STUD_HAND_HEIGHT.get() / 2) + SPACING_H.get()
+    );
+
+    // The pots are listed to the right of the hands.
+    const POTS_X: unscaled::X = unscaled::x_const_add_w(
+        unscaled::X(0),
+        unscaled::w_const_sub(command::WIDTH_W, SPACING_W),
+    );
+    const POTS_Y: unscaled::Y = unscaled::y_const_add_h(unscaled::Y(0), SPACING_H);
+
+    fn max_players(_deck_composition: DeckComposition) -> PlayerAmount {
+        MAX_PLAYERS
+    }
+
+    fn deal(rng: &mut Xs, player_count: PlayerCount, _deck_composition: DeckComposition) -> (Hands<Self>, Deck) {
+        stud::deal(rng, player_count)
+    }
+
+    fn collect_forced_bets(hands: &Hands<Self>, _dealer: HandIndex, pot: &mut Pot, seats: &mut Seats) {
+        for index in 0..hands.len().u8() {
+            pot.push_bet_of_kind(
+                index,
+                PotAction::Bet(
+                    seats.moneys[usize::from(index)]
+                        .take(seats.small_forced_bet.get())
+                ),
+                BetKind::Ante,
+            );
+        }
+
+        // The bring-in is posted like a blind, so the bring-in player still
+        // gets a chance to act, and the first raise is a full bet over it.
+        let index = bring_in_index(hands);
+
+        pot.push_bet_of_kind(
+            index,
+            PotAction::Bet(
+                seats.moneys[usize::from(index)]
+                    .take(seats.large_forced_bet.get())
+            ),
+            BetKind::BringIn,
+        );
+    }
+
+    /// The player after the bring-in acts first on third street. On the later
+    /// streets, the live player with the best face up cards does, with ties
+    /// going to whoever is first after the dealer.
+    fn first_to_act(hands: &Hands<Self>, street: Street, pot: &Pot, dealer: HandIndex) -> HandIndex {
+        let hands_len = hands.len().u8();
+
+        if street == Street::Third {
+            let mut index = bring_in_index(hands) + 1;
+            if index >= hands_len {
+                index = 0;
+            }
+            return index
+        }
+
+        let mut output = dealer;
+        let mut best = None;
+
+        let mut index = dealer;
+        for _ in 0..hands_len {
+            index += 1;
+            if index >= hands_len {
+                index = 0;
+            }
+
+            if pot.has_folded(index) {
+                continue
+            }
+
+            let Some(hand) = hands.get(index) else { continue };
+            let (up_cards, up_len) = stud::up_cards(hand, street.card_count());
+            let showing = evaluate::showing(&up_cards[..usize::from(up_len)]);
+            if best.is_none_or(|best| showing > best) {
+                output = index;
+                best = Some(showing);
+            }
+        }
+
+        output
+    }
+
+    /// Every card was dealt in advance, so there is nothing to draw here.
+    fn next_round(street: Street, _deck: &mut Deck) -> Next<Street, ()> {
+        match street.next() {
+            Some(street) => Next::Round(street),
+            None => Next::Showdown(()),
+        }
+    }
+
+    /// Third and fourth street use a small bet, which is twice the bring-in,
+    /// and the later streets use a big bet, which is twice the size of the
+    /// small bet.
+    fn bet_size(seats: &Seats, street: Street) -> NonZeroMoneyInner {
+        let two = NonZeroMoneyInner::MIN.saturating_add(1);
+        let small_bet = seats.large_forced_bet.saturating_mul(two);
+        match street {
+            Street::Third | Street::Fourth => small_bet,
+            Street::Fifth | Street::Sixth | Street::Seventh => small_bet.saturating_mul(two),
+        }
+    }
+
+    fn raise_unit(seats: &Seats, street: Street) -> NonZeroMoneyInner {
+        Self::bet_size(seats, street)
+    }
+
+    fn draw_round(_commands: &mut Commands, _street: Street) {}
+
+    fn draw(commands: &mut Commands, hand: Self, street: Street, show: bool, x: unscaled::X, y: unscaled::Y) {
+        let facings = facings(hand, street, show);
+        commands.draw_stud_hand(&facings[..usize::from(street.card_count())], x, y);
+    }
+
+    fn draw_folded(commands: &mut Commands, hand: Self, street: Street, show: bool, x: unscaled::X, y: unscaled::Y) {
+        let facings = facings(hand, street, show);
+        commands.draw_folded_stud_hand(&facings[..usize::from(street.card_count())], x, y);
+    }
+
+    fn draw_underlight(commands: &mut Commands, x: unscaled::X, y: unscaled::Y) {
+        commands.draw_stud_hand_underlight(x, y);
+    }
+
+    fn draw_selected(commands: &mut Commands, x: unscaled::X, y: unscaled::Y) {
+        commands.draw_stud_hand_selected(x, y);
+    }
+
+    fn cards(hands: &Hands<Self>, current: HandIndex, pot: &Pot, street: Street, _deck_composition: DeckComposition) -> Cards {
+        let hand = hands.get(current)
+                    .copied()
+                    .unwrap_or_default();
+
+        let mut opponent_up_cards =
+            [UpCards::default(); MAX_PLAYERS as usize - 1];
+        let mut opponent_count = 0;
+        for (i, other_hand) in hands.iter().enumerate() {
+            let i = i as HandIndex;
+            if i == current || pot.has_folded(i) {
+                continue
+            }
+
+            let Some(up_cards) = opponent_up_cards.get_mut(opponent_count) else { break };
+            *up_cards = stud::up_cards(&other_hand, street.card_count()).0;
+            opponent_count += 1;
+        }
+
+        Cards::Stud {
+            hand,
+            street,
+            opponent_up_cards,
+            opponent_count: opponent_count as _,
+        }
+    }
+
+    fn eval(self, _deck_composition: DeckComposition, _board: ()) -> evaluate::Eval {
+        evaluate::stud_hand(self)
+    }
+
+    fn best_five(self, _deck_composition: DeckComposition, _board: ()) -> ([Card; 5], evaluate::Category) {
+        evaluate::stud_best_five(self)
+    }
+
+    fn board_cards(_board: ()) -> ([Card; 5], usize) {
+        (<_>::default(), 0)
+    }
+}
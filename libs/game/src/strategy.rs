@@ -0,0 +1,463 @@
+//! How CPU players decide what to do. The game modules describe each decision
+//! with a read-only view, and ask a `Strategy` for the answer, so new CPU
+//! players can be written, and swapped in, without changing the game modules.
+
+use equity::{Iterations, OpponentCount, five_card::DiscardMask};
+use models::{Action, AllowedKindMode, DeckComposition, MoneyInner, NonZeroMoneyInner, PlayerIndex, Pot, RaiseRange, WildCards, holdem::{self, CommunityCards}, omaha, stud};
+use probability::Probability;
+
+use xs::Xs;
+
+use crate::SubGame;
+pub use crate::shared_game_types::{CpuPersonality, OpponentTendencies, PlayerStats};
+use crate::shared_game_types::{PokerDecision, PostflopDecision};
+
+/// The amount of hand evaluations a CPU player does to estimate the strength of
+/// their hand, split across the sampled runouts.
+const CPU_EQUITY_EVALUATIONS: Iterations = 8192;
+
+/// Like `CPU_EQUITY_EVALUATIONS`, but for Omaha, where each evaluation checks
+/// every legal combination of cards, so is several times slower.
+const CPU_OMAHA_EQUITY_EVALUATIONS: Iterations = 1024;
+
+/// The cards the deciding player can see.
+#[derive(Clone, Copy)]
+pub enum Cards {
+    Holdem {
+        hand: holdem::Hand,
+        /// `None` before the flop.
+        community_opt: Option<CommunityCards>,
+        deck: DeckComposition,
+    },
+    Omaha {
+        hand: omaha::Hand,
+        /// `None` before the flop.
+        community_opt: Option<CommunityCards>,
+        deck: DeckComposition,
+    },
+    Stud {
+        /// Only the cards dealt so far on `street` are known.
+        hand: stud::Hand,
+        street: stud::Street,
+        /// The face up cards of each live opponent, in the first
+        /// `opponent_count` elements.
+        opponent_up_cards: [stud::UpCards; stud::MAX_PLAYERS as usize - 1],
+        opponent_count: OpponentCount,
+    },
+    FiveCardDraw {
+        hand: equity::five_card::Hand,
+        wilds: WildCards,
+    },
+}
+
+/// A rough estimate of how likely a five card hand is to win, with any wild
+/// cards standing in for whatever makes the hand best.
+fn five_card_win_probability(hand: equity::five_card::Hand, wilds: WildCards) -> Probability {
+    match evaluate::substitute_wilds(hand, wilds) {
+        Some(hand) => look_up::five_card::hand_win_probability(hand),
+        // Five of a kind can only lose to a higher five of a kind.
+        None => probability::ONE,
+    }
+}
+
+impl Cards {
+    /// Estimate the probability that these cards win against
+    /// `opponent_count` live opponents.
+    pub fn win_probability(
+        self,
+        rng: &mut Xs,
+        opponent_count: OpponentCount,
+    ) -> Probability {
+        match self {
+            Self::Holdem { hand, community_opt, deck } => equity::holdem::equity(
+                rng,
+                hand,
+                community_opt,
+                opponent_count,
+                deck,
+                CPU_EQUITY_EVALUATIONS
+                    / (Iterations::from(opponent_count) + 1),
+            ),
+            Self::Omaha { hand, community_opt, deck } => equity::holdem::equity(
+                rng,
+                hand,
+                community_opt,
+                opponent_count,
+                deck,
+                CPU_OMAHA_EQUITY_EVALUATIONS
+                    / (Iterations::from(opponent_count) + 1),
+            ),
+            Self::Stud { hand, street, opponent_up_cards, opponent_count: up_cards_count } => {
+                let count = opponent_count.min(up_cards_count);
+                equity::stud::equity(
+                    rng,
+                    hand,
+                    street.card_count(),
+                    &opponent_up_cards[..usize::from(count).min(opponent_up_cards.len())],
+                    street.up_card_count(),
+                    CPU_EQUITY_EVALUATIONS
+                        / (Iterations::from(count) + 1),
+                )
+            },
+            Self::FiveCardDraw { hand, wilds } => {
+                five_card_win_probability(hand, wilds)
+            },
+        }
+    }
+}
+
+/// Everything a player is allowed to know when deciding what to do in a
+/// betting round.
+#[derive(Clone, Copy)]
+pub struct BettingView<'view> {
+    pub cards: Cards,
+    /// The index of the deciding player.
+    pub position: PlayerIndex,
+    pub dealer: PlayerIndex,
+    /// The stacks of every player at the table, including folded players,
+    /// indexed the same way as `position`.
+    pub stacks: &'view [MoneyInner],
+    pub stats: &'view [PlayerStats],
+    pub pot: &'view Pot,
+    /// The amount extra needed to call.
+    pub call_remainder: MoneyInner,
+    /// The amounts that can be put in with a raise, including the amount needed
+    /// to call, or `None` if raising is not allowed.
+    pub raise_range: Option<RaiseRange>,
+    /// The typical amount to raise by, for example the big blind.
+    pub raise_unit: NonZeroMoneyInner,
+    pub allowed_kind_mode: AllowedKindMode,
+}
+
+impl BettingView<'_> {
+    pub fn stack(&self) -> MoneyInner {
+        self.stacks.get(usize::from(self.position))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// The indexes of the other players who have not folded yet.
+    pub fn live_opponents(&self) -> impl Iterator<Item = PlayerIndex> + '_ {
+        (0..self.stacks.len() as PlayerIndex)
+            .filter(|&i| i != self.position && !self.pot.has_folded(i))
+    }
+
+    pub fn opponent_tendencies(&self) -> OpponentTendencies {
+        OpponentTendencies::from_stats(
+            self.live_opponents()
+                .filter_map(|i| self.stats.get(usize::from(i)))
+        )
+    }
+
+    /// Turn whatever a `Strategy` returned into an action that is legal here.
+    /// Folding when there is nothing to call becomes checking, raises are moved
+    /// into the `raise_range`, and raises that are not allowed become calls.
+    pub fn legal_action(&self, action: Action) -> Action {
+        match (action, self.raise_range) {
+            (Action::Fold, _) if self.call_remainder == 0 => Action::Call,
+            (Action::Raise(_), None) => Action::Call,
+            (Action::Raise(raise_amount), Some(range)) => {
+                Action::Raise(range.clamp(raise_amount))
+            },
+            (action, _) => action,
+        }
+    }
+}
+
+/// Everything a player is allowed to know when deciding how much to bet
+/// against the pot, in games like acey-deucey.
+#[derive(Clone, Copy, Debug)]
+pub struct PotBetView {
+    /// The probability that the next card lands between the posts, counting
+    /// the cards seen since the last shuffle.
+    pub win_probability: Probability,
+    pub stack: MoneyInner,
+    /// The most that can be bet right now.
+    pub max_bet: MoneyInner,
+}
+
+/// Everything a player is allowed to know when deciding what to discard in a
+/// draw game.
+#[derive(Clone, Copy, Debug)]
+pub struct DrawView {
+    pub hand: equity::five_card::Hand,
+    /// Discarding more cards than this is treated as standing pat.
+    pub max_discards: u8,
+    pub wilds: WildCards,
+}
+
+/// Everything a dealer is allowed to know when choosing the next game in
+/// dealer's choice.
+#[derive(Clone, Copy, Debug)]
+pub struct SubGameView<'view> {
+    /// The games that can be dealt to the players with money left. Never empty.
+    pub choices: &'view [SubGame],
+}
+
+impl SubGameView<'_> {
+    /// Turn whatever a `Strategy` returned into one of the `choices`. Games
+    /// that are not among them become the first one.
+    pub fn legal_choice(&self, sub_game: SubGame) -> SubGame {
+        if self.choices.contains(&sub_game) {
+            sub_game
+        } else {
+            self.choices.first().copied().unwrap_or(sub_game)
+        }
+    }
+}
+
+/// A way of playing. Only betting rounds need to be handled; the other
+/// decisions default to what a middle of the road `CpuPersonality` would do.
+pub trait Strategy: CloneStrategy {
+    /// Decide what to do in a betting round. Actions that are not legal are
+    /// made legal with `BettingView::legal_action`.
+    fn betting_action(&self, rng: &mut Xs, view: &BettingView<'_>) -> Action;
+
+    /// Decide how much to bet against the pot. `None` means passing.
+    fn pot_bet(&self, rng: &mut Xs, view: &PotBetView) -> Option<NonZeroMoneyInner> {
+        CpuPersonality::default().pot_bet(rng, view)
+    }
+
+    /// Decide which cards to discard. An empty mask means standing pat.
+    fn discards(&self, rng: &mut Xs, view: &DrawView) -> DiscardMask {
+        CpuPersonality::default().discards(rng, view)
+    }
+
+    /// Decide which game to deal next, as the dealer in dealer's choice. Games
+    /// that are not legal are made legal with `SubGameView::legal_choice`.
+    fn sub_game(&self, rng: &mut Xs, view: &SubGameView<'_>) -> SubGame {
+        CpuPersonality::default().sub_game(rng, view)
+    }
+}
+
+/// Allows cloning boxed strategies, so tables can still be cloned. This is
+/// implemented for every `Strategy` that is `Clone`.
+pub trait CloneStrategy {
+    fn clone_box(&self) -> Box<dyn Strategy>;
+}
+
+impl <S: Strategy + Clone + 'static> CloneStrategy for S {
+    fn clone_box(&self) -> Box<dyn Strategy> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn Strategy> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+/// The amount of sampled draws CPU players use to compare their options.
+const CPU_DRAW_ITERATIONS: Iterations = 256;
+
+/// How well a game suits loose, aggressive players, with 0 suiting the
+/// tightest players best. Games with bigger bets and bigger swings score
+/// higher.
+fn sub_game_action(sub_game: SubGame) -> Probability {
+    match sub_game {
+        SubGame::SevenCardStud => 0b0010_0000,
+        SubGame::FiveCardDraw => 0b0100_0000,
+        SubGame::Holdem => 0b1000_0000,
+        SubGame::Omaha => 0b1100_0000,
+        SubGame::AceyDeucey => 0b1110_0000,
+    }
+}
+
+impl Strategy for CpuPersonality {
+    fn betting_action(&self, rng: &mut Xs, view: &BettingView<'_>) -> Action {
+        let live_opponent_count = view.live_opponents().count() as OpponentCount;
+
+        let decision = PokerDecision {
+            win_probability: view.cards.win_probability(rng, live_opponent_count),
+            call_remainder: view.call_remainder,
+            pot_odds: equity::pot_odds(view.call_remainder, view.pot.total()),
+            stack: view.stack(),
+            minimum_raise_total: match view.raise_range {
+                Some(range) => range.min,
+                // Any raise will become a call, so the amount doesn't matter.
+                None => view.call_remainder,
+            },
+            raise_unit: view.raise_unit,
+            opponents: view.opponent_tendencies(),
+        };
+
+        let has_draw = match view.cards {
+            Cards::Holdem { hand, community_opt: Some(community_cards), .. } => {
+                holdem::has_draw(community_cards, hand)
+            },
+            Cards::Omaha { hand, community_opt: Some(community_cards), .. } => {
+                omaha::has_draw(community_cards, hand)
+            },
+            Cards::Stud { hand, street, .. } if street != stud::Street::Third => {
+                stud::has_draw(&hand, street)
+            },
+            Cards::Holdem { community_opt: None, .. }
+            | Cards::Omaha { community_opt: None, .. }
+            | Cards::Stud { .. }
+            | Cards::FiveCardDraw { .. } => return self.decide_poker_action(rng, decision),
+        };
+
+        self.decide_postflop_action(
+            rng,
+            decision,
+            PostflopDecision {
+                pot_total: view.pot.total(),
+                has_draw,
+                has_gone_this_round: view.pot.has_gone_this_round(view.position),
+            },
+        )
+    }
+
+    fn pot_bet(&self, rng: &mut Xs, view: &PotBetView) -> Option<NonZeroMoneyInner> {
+        self.decide_pot_bet(rng, view.win_probability, view.stack, view.max_bet)
+    }
+
+    fn discards(&self, rng: &mut Xs, &DrawView { hand, max_discards, wilds }: &DrawView) -> DiscardMask {
+        // Standing pat on a weak hand can make it look stronger than it is.
+        if Self::roll(rng, self.bluff_frequency / 4)
+        && five_card_win_probability(hand, wilds) < self.play_threshold() {
+            return 0
+        }
+
+        let expected_values = equity::five_card::draw_expected_values(
+            rng,
+            hand,
+            wilds,
+            CPU_DRAW_ITERATIONS,
+        );
+
+        let mut best: DiscardMask = 0;
+        for (mask, expected_value) in expected_values.iter().enumerate() {
+            let mask = mask as DiscardMask;
+            if mask.count_ones() > u32::from(max_discards) {
+                continue
+            }
+
+            if *expected_value > expected_values[usize::from(best)] {
+                best = mask;
+            }
+        }
+
+        best
+    }
+
+    fn sub_game(&self, rng: &mut Xs, view: &SubGameView<'_>) -> SubGame {
+        // Calling an unexpected game now and then keeps the table guessing.
+        if !view.choices.is_empty() && Self::roll(rng, self.bluff_frequency) {
+            return view.choices[xs::range(rng, 0..view.choices.len() as u32) as usize]
+        }
+
+        let taste = (
+            u16::from(self.aggression)
+            + u16::from(self.risk_tolerance)
+            + u16::from(probability::ONE - self.tightness)
+        ) / 3;
+
+        view.choices
+            .iter()
+            .copied()
+            .min_by_key(|&sub_game| u16::from(sub_game_action(sub_game)).abs_diff(taste))
+            .unwrap_or_default()
+    }
+}
+
+#[test]
+fn strategies_written_elsewhere_can_be_boxed_cloned_and_made_legal() {
+    #[derive(Clone)]
+    struct AlwaysShove;
+
+    impl Strategy for AlwaysShove {
+        fn betting_action(&self, _: &mut Xs, _: &BettingView<'_>) -> Action {
+            Action::Raise(MoneyInner::MAX)
+        }
+    }
+
+    #[derive(Clone)]
+    struct AlwaysFold;
+
+    impl Strategy for AlwaysFold {
+        fn betting_action(&self, _: &mut Xs, _: &BettingView<'_>) -> Action {
+            Action::Fold
+        }
+    }
+
+    let mut rng = xs::from_seed([42; 16]);
+
+    let pot = Pot::with_capacity(2, 16);
+    let stacks = [300, 200];
+    let stats = [PlayerStats::default(); 2];
+    let view = BettingView {
+        cards: Cards::FiveCardDraw { hand: [0, 1, 2, 3, 4], wilds: <_>::default() },
+        position: 1,
+        dealer: 0,
+        stacks: &stacks,
+        stats: &stats,
+        pot: &pot,
+        call_remainder: 0,
+        raise_range: Some(RaiseRange { min: 10, max: 200 }),
+        raise_unit: models::MIN_MONEY_UNIT,
+        allowed_kind_mode: AllowedKindMode::NoFolding,
+    };
+
+    let shove: Box<dyn Strategy> = Box::new(AlwaysShove);
+    let fold: Box<dyn Strategy> = Box::new(AlwaysFold).clone();
+
+    assert!(matches!(
+        view.legal_action(shove.betting_action(&mut rng, &view)),
+        Action::Raise(200)
+    ));
+    assert!(matches!(
+        view.legal_action(fold.betting_action(&mut rng, &view)),
+        Action::Call
+    ));
+
+    // The other decisions fall back to a default personality.
+    // Ranks are 0 for ace up to 12 for king, and suits advance by 13.
+    // So this is a pair of aces, with nothing else going on.
+    let discards = fold.discards(
+        &mut rng,
+        &DrawView { hand: [0, 13, 20, 35, 50], max_discards: 4, wilds: <_>::default() }
+    );
+    assert_ne!(discards, 0);
+    assert_eq!(discards & 0b11, 0, "Discarded an ace: {discards:05b}");
+}
+
+#[test]
+fn cpu_dealers_choose_games_that_suit_their_personality() {
+    let mut rng = xs::from_seed([17; 16]);
+
+    let view = SubGameView { choices: &SubGame::ALL };
+
+    // Rocks never bluff, so they never mix it up either.
+    for _ in 0..16 {
+        assert_eq!(
+            CpuPersonality::ROCK.sub_game(&mut rng, &view),
+            SubGame::SevenCardStud
+        );
+    }
+
+    let mut maniac_counts = [0; SubGame::ALL.len()];
+    for _ in 0..256 {
+        let sub_game = CpuPersonality::MANIAC.sub_game(&mut rng, &view);
+        maniac_counts[sub_game.index_of()] += 1;
+    }
+    let favourite = SubGame::ALL[
+        (0..maniac_counts.len())
+            .max_by_key(|&i| maniac_counts[i])
+            .unwrap()
+    ];
+    assert_eq!(favourite, SubGame::AceyDeucey, "{maniac_counts:?}");
+
+    let choices = [SubGame::Holdem, SubGame::FiveCardDraw];
+    let view = SubGameView { choices: &choices };
+    for personality in CpuPersonality::PRESETS {
+        for _ in 0..16 {
+            let sub_game = personality.sub_game(&mut rng, &view);
+            assert!(choices.contains(&sub_game), "{sub_game:?}");
+        }
+    }
+
+    assert_eq!(view.legal_choice(SubGame::Omaha), SubGame::Holdem);
+    assert_eq!(view.legal_choice(SubGame::FiveCardDraw), SubGame::FiveCardDraw);
+}
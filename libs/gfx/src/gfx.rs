@@ -1,4 +1,4 @@
-use models::{Card, Rank, Suit, holdem, get_rank, get_suit, suits};
+use models::{Card, JOKER, Rank, Suit, holdem, omaha, stud, get_rank, get_suit, suits};
 
 use platform_types::{Command, PALETTE, sprite, unscaled, command::{self, Rect}, PaletteIndex, FONT_BASE_Y, FONT_WIDTH};
 
@@ -16,6 +16,26 @@ pub enum HoldemFacing {
     Up(holdem::Hand),
 }
 
+#[derive(Copy, Clone, Default)]
+pub enum OmahaFacing {
+    #[default]
+    Down,
+    Up(omaha::Hand),
+}
+
+/// How a single card is shown, in a hand where some of the cards are face up
+/// and the rest are face down.
+#[derive(Copy, Clone, Default)]
+pub enum MixedFacing {
+    /// A face down card that the viewer cannot see.
+    #[default]
+    Down,
+    /// A face down card that the viewer can see, because it is theirs.
+    Peeked(Card),
+    /// A face up card, that everyone can see.
+    Up(Card),
+}
+
 // TODO? Generic `Facing` type?
 #[derive(Copy, Clone, Default)]
 pub enum FiveCardFacing {
@@ -105,6 +125,31 @@ impl Commands {
         }
     }
 
+    /// Prints `card` as its rank followed by its suit, two characters wide,
+    /// for places where a whole card would not fit. Red suits stay red, so
+    /// they can be told apart.
+    pub fn print_card(
+        &mut self,
+        card: Card,
+        x: unscaled::X,
+        y: unscaled::Y,
+        colour: PaletteIndex
+    ) {
+        let suit = get_suit(card);
+        let (suit_colour, suit_char) = get_suit_colour_and_char(suit);
+
+        self.print_char(get_rank_char(card), x, y, colour);
+        self.print_char(
+            suit_char,
+            x + CHAR_ADVANCE,
+            y,
+            match suit {
+                suits::DIAMONDS | suits::HEARTS => suit_colour,
+                _ => colour,
+            }
+        );
+    }
+
     pub fn draw_title(
         &mut self,
         x: unscaled::X,
@@ -165,6 +210,89 @@ impl Commands {
         );
     }
 
+    // Omaha hands have more cards, so they are packed tighter, to fit more of
+    // them on the screen.
+    const OMAHA_HAND_X_OFFSET: unscaled::W = unscaled::w_const_div(card::WIDTH, 3);
+
+    pub const OMAHA_HAND_WIDTH: unscaled::W = unscaled::w_const_add(
+        unscaled::w_const_mul(
+            Self::OMAHA_HAND_X_OFFSET,
+            omaha::HAND_LEN as _,
+        ),
+        unscaled::w_const_sub(card::WIDTH, Self::OMAHA_HAND_X_OFFSET),
+    );
+    pub const OMAHA_HAND_HEIGHT: unscaled::H = unscaled::h_const_add(
+        Self::HAND_Y_OFFSET,
+        card::HEIGHT,
+    );
+
+    pub fn draw_omaha_hand_underlight(
+        &mut self,
+        x: unscaled::X,
+        y: unscaled::Y
+    ) {
+        let (new_x, clipped_w) = match x.checked_sub(SPACING_W) {
+            Some(n_x) => (n_x, unscaled::W(0)),
+            None => (unscaled::X(0), unscaled::W(SPACING_W.get() - x.get())),
+        };
+        let (new_y, clipped_h) = match y.checked_sub(SPACING_H) {
+            Some(n_y) => (n_y, unscaled::H(0)),
+            None => (unscaled::Y(0), unscaled::H(SPACING_H.get() - y.get())),
+        };
+
+        self.draw_nine_slice(
+            NineSlice::Highlight,
+            unscaled::Rect {
+                x: new_x,
+                y: new_y,
+                w: (SPACING_W + Self::OMAHA_HAND_WIDTH + SPACING_W) - clipped_w,
+                h: (SPACING_H + Self::OMAHA_HAND_HEIGHT + SPACING_H) - clipped_h,
+            },
+        );
+    }
+
+    const STUD_HAND_X_OFFSET: unscaled::W = Self::OMAHA_HAND_X_OFFSET;
+    // Face down cards are drawn lower than face up cards, so which ones the
+    // other players can see is still clear when the viewer can see them all.
+    const STUD_DOWN_Y_OFFSET: unscaled::H = unscaled::H(6);
+
+    pub const STUD_HAND_WIDTH: unscaled::W = unscaled::w_const_add(
+        unscaled::w_const_mul(
+            Self::STUD_HAND_X_OFFSET,
+            stud::HAND_LEN as _,
+        ),
+        unscaled::w_const_sub(card::WIDTH, Self::STUD_HAND_X_OFFSET),
+    );
+    pub const STUD_HAND_HEIGHT: unscaled::H = unscaled::h_const_add(
+        Self::STUD_DOWN_Y_OFFSET,
+        card::HEIGHT,
+    );
+
+    pub fn draw_stud_hand_underlight(
+        &mut self,
+        x: unscaled::X,
+        y: unscaled::Y
+    ) {
+        let (new_x, clipped_w) = match x.checked_sub(SPACING_W) {
+            Some(n_x) => (n_x, unscaled::W(0)),
+            None => (unscaled::X(0), unscaled::W(SPACING_W.get() - x.get())),
+        };
+        let (new_y, clipped_h) = match y.checked_sub(SPACING_H) {
+            Some(n_y) => (n_y, unscaled::H(0)),
+            None => (unscaled::Y(0), unscaled::H(SPACING_H.get() - y.get())),
+        };
+
+        self.draw_nine_slice(
+            NineSlice::Highlight,
+            unscaled::Rect {
+                x: new_x,
+                y: new_y,
+                w: (SPACING_W + Self::STUD_HAND_WIDTH + SPACING_W) - clipped_w,
+                h: (SPACING_H + Self::STUD_HAND_HEIGHT + SPACING_H) - clipped_h,
+            },
+        );
+    }
+
     pub const FIVE_CARD_HAND_WIDTH: unscaled::W = unscaled::w_const_mul(
         Self::HAND_X_OFFSET,
         5 + 1
@@ -240,6 +368,28 @@ impl Commands {
         )
     }
 
+    pub fn draw_omaha_hand_selected(
+        &mut self,
+        x: unscaled::X,
+        y: unscaled::Y
+    ) {
+        self.draw_selected(
+            x + (Self::OMAHA_HAND_WIDTH / 2),
+            y,
+        )
+    }
+
+    pub fn draw_stud_hand_selected(
+        &mut self,
+        x: unscaled::X,
+        y: unscaled::Y
+    ) {
+        self.draw_selected(
+            x + (Self::STUD_HAND_WIDTH / 2),
+            y,
+        )
+    }
+
     pub fn draw_five_card_hand_selected(
         &mut self,
         x: unscaled::X,
@@ -276,13 +426,92 @@ impl Commands {
         y: unscaled::Y
     ) {
         self.draw_holdem_hand(facing, x, y);
-        let hand_rect = unscaled::Rect {
+        self.draw_folded_label(unscaled::Rect {
             x,
             y,
-            w: Self::HAND_X_OFFSET + card::WIDTH,
-            h: Self::HAND_Y_OFFSET + card::HEIGHT,
-        };
+            w: Self::HOLDEM_HAND_WIDTH,
+            h: Self::HOLDEM_HAND_HEIGHT,
+        });
+    }
+
+    pub fn draw_omaha_hand(
+        &mut self,
+        facing: OmahaFacing,
+        mut x: unscaled::X,
+        mut y: unscaled::Y
+    ) {
+        match facing {
+            OmahaFacing::Down => {
+                for _ in 0..omaha::HAND_LEN {
+                    self.draw_card_back(x, y);
+                    x += Self::OMAHA_HAND_X_OFFSET;
+                    y += Self::HAND_Y_OFFSET;
+                }
+            }
+            OmahaFacing::Up(hand) => {
+                for card in hand {
+                    self.draw_card(card, x, y);
+                    x += Self::OMAHA_HAND_X_OFFSET;
+                    y += Self::HAND_Y_OFFSET;
+                }
+            },
+        }
+    }
 
+    pub fn draw_folded_omaha_hand(
+        &mut self,
+        facing: OmahaFacing,
+        x: unscaled::X,
+        y: unscaled::Y
+    ) {
+        self.draw_omaha_hand(facing, x, y);
+        self.draw_folded_label(unscaled::Rect {
+            x,
+            y,
+            w: Self::OMAHA_HAND_WIDTH,
+            h: Self::OMAHA_HAND_HEIGHT,
+        });
+    }
+
+    /// Draws the cards dealt so far in a stud hand, each facing its own way.
+    pub fn draw_stud_hand(
+        &mut self,
+        cards: &[MixedFacing],
+        mut x: unscaled::X,
+        y: unscaled::Y
+    ) {
+        for &facing in cards {
+            match facing {
+                MixedFacing::Down => {
+                    self.draw_card_back(x, y + Self::STUD_DOWN_Y_OFFSET);
+                },
+                MixedFacing::Peeked(card) => {
+                    self.draw_card(card, x, y + Self::STUD_DOWN_Y_OFFSET);
+                },
+                MixedFacing::Up(card) => {
+                    self.draw_card(card, x, y);
+                },
+            }
+            x += Self::STUD_HAND_X_OFFSET;
+        }
+    }
+
+    pub fn draw_folded_stud_hand(
+        &mut self,
+        cards: &[MixedFacing],
+        x: unscaled::X,
+        y: unscaled::Y
+    ) {
+        self.draw_stud_hand(cards, x, y);
+        self.draw_folded_label(unscaled::Rect {
+            x,
+            y,
+            w: Self::STUD_HAND_WIDTH,
+            h: Self::STUD_HAND_HEIGHT,
+        });
+    }
+
+    fn draw_folded_label(&mut self, hand_rect: unscaled::Rect) {
         let rect = unscaled::Rect {
             y: hand_rect.y + (hand_rect.h / 2) - unscaled::H(CHAR_H.get() / 2) - CHAR_SPACING_H,
             h: CHAR_H + 3 * CHAR_SPACING_H,
@@ -376,6 +605,18 @@ impl Commands {
         y: unscaled::Y
     ) {
         type Inner = sprite::Inner;
+
+        if card == JOKER {
+            // The joker face is in the column past the kings, under the back.
+            self.draw_card_sprite(
+                13,
+                3,
+                x,
+                y,
+            );
+            return
+        }
+
         let suit = get_suit(card);
         let rank = get_rank(card);
 
@@ -15,6 +15,14 @@ pub const DECK_SIZE: u8 = RANK_COUNT * SUIT_COUNT;
 
 pub type Card = u8;
 
+/// A card outside of the usual 52, that some games add to the deck. It has no
+/// rank or suit of its own, so it needs to be checked for before calling
+/// `get_rank` or `get_suit`.
+pub const JOKER: Card = DECK_SIZE;
+
+/// The size of a deck with a joker added to it.
+pub const MAX_DECK_SIZE: u8 = DECK_SIZE + 1;
+
 pub const ALL_CARDS: [Card; DECK_SIZE as usize] = {
     let mut all_cards = [0; DECK_SIZE as usize];
 
@@ -28,6 +36,10 @@ pub const ALL_CARDS: [Card; DECK_SIZE as usize] = {
 };
 
 pub fn card_text_byte(card: Card) -> u8 {
+    if card == JOKER {
+        return b'*'
+    }
+
     match get_rank(card) {
         0 => b'a',
         1 => b'2',
@@ -72,6 +84,7 @@ pub mod ranks {
     use super::*;
 
     pub const ACE: Rank = 0;
+    pub const JACK: Rank = 10;
     pub const HIGH_ACE: Rank = 13;
 }
 
@@ -84,22 +97,24 @@ type CardIndex = u8;
 
 #[derive(Clone, Debug)]
 pub struct Deck {
-    cards: [Card; DECK_SIZE as usize],
+    cards: [Card; MAX_DECK_SIZE as usize],
     index: CardIndex,
+    len: CardAmount,
 }
 
 impl Default for Deck {
     fn default() -> Self {
         Self {
-            cards: [0; DECK_SIZE as usize],
+            cards: [0; MAX_DECK_SIZE as usize],
             index: 0,
+            len: DECK_SIZE,
         }
     }
 }
 
 impl Deck {
     pub fn draw(&mut self) -> Option<Card> {
-        if self.index >= DECK_SIZE {
+        if self.index >= self.len {
             None
         } else {
             let output = Some(self.cards[self.index as usize]);
@@ -120,17 +135,278 @@ pub fn gen_deck(rng: &mut Xs) -> Deck {
     for i in 1..DECK_SIZE {
         output.cards[i as usize] = i;
     }
+    xs::shuffle(rng, &mut output.cards[..DECK_SIZE as usize]);
+
+    output
+}
+
+/// Generates a deck with all the usual cards, plus a `JOKER`.
+pub fn gen_deck_with_joker(rng: &mut Xs) -> Deck {
+    let mut output = Deck::default();
+    for i in 1..MAX_DECK_SIZE {
+        output.cards[i as usize] = i;
+    }
     xs::shuffle(rng, &mut output.cards);
 
+    output.len = MAX_DECK_SIZE;
+
     output
 }
 
+/// Generates a deck that contains exactly the cards in `cards`, which may
+/// include a `JOKER`.
+pub fn gen_deck_from(rng: &mut Xs, cards: CardBitset) -> Deck {
+    let mut output = Deck::default();
+
+    let mut len = 0;
+    for card in cards.iter() {
+        output.cards[len] = card;
+        len += 1;
+    }
+    xs::shuffle(rng, &mut output.cards[..len]);
+
+    output.len = len as CardAmount;
+
+    output
+}
+
+/// Generates a deck that will never draw any of the cards in `excluded`.
+pub fn gen_deck_without(rng: &mut Xs, excluded: CardBitset) -> Deck {
+    let mut output = Deck::default();
+
+    let mut i = 0;
+    for card in excluded.iter() {
+        output.cards[i] = card;
+        i += 1;
+    }
+
+    let drawable_start = i;
+    for card in 0..DECK_SIZE {
+        if !excluded.contains(card) {
+            output.cards[i] = card;
+            i += 1;
+        }
+    }
+    xs::shuffle(rng, &mut output.cards[drawable_start..i]);
+
+    output.index = drawable_start as CardIndex;
+    output.len = i as CardAmount;
+
+    output
+}
+
+#[test]
+fn gen_deck_without_never_draws_excluded_cards() {
+    let mut rng = xs::from_seed([0xD; 16]);
+
+    let mut excluded = CardBitset::default();
+    excluded.set(0);
+    excluded.set(13);
+    excluded.set(DECK_SIZE - 1);
+
+    let mut deck = gen_deck_without(&mut rng, excluded);
+
+    let mut count = 0;
+    while let Some(card) = deck.draw() {
+        assert!(!excluded.contains(card), "{card}");
+        count += 1;
+    }
+
+    assert_eq!(count, DECK_SIZE - excluded.len());
+}
+
+/// Which cards a deck is made up of.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum DeckComposition {
+    /// All 52 cards.
+    #[default]
+    Full,
+    /// The 36 cards left after removing the twos through fives, sometimes
+    /// called "six plus".
+    Short,
+}
+
+impl DeckComposition {
+    pub const ALL: [Self; 2] = [
+        Self::Full,
+        Self::Short,
+    ];
+
+    /// The cards that a deck of this composition never contains.
+    pub fn excluded(self) -> CardBitset {
+        let mut output = CardBitset::default();
+
+        match self {
+            Self::Full => {},
+            Self::Short => {
+                for card in 0..DECK_SIZE {
+                    if (1..=4).contains(&get_rank(card)) {
+                        output.set(card);
+                    }
+                }
+            },
+        }
+
+        output
+    }
+
+    pub fn card_count(self) -> CardAmount {
+        DECK_SIZE - self.excluded().len()
+    }
+
+    /// A short name for the composition, meant to be shown above the word "deck".
+    pub fn text(self) -> &'static [u8] {
+        match self {
+            Self::Full => b"full",
+            Self::Short => b"short",
+        }
+    }
+
+    pub fn next_up(self) -> Self {
+        match self {
+            Self::Full => Self::Short,
+            Self::Short => Self::Full,
+        }
+    }
+
+    pub fn next_down(self) -> Self {
+        self.next_up()
+    }
+}
+
+/// Generates a shuffled deck containing exactly the cards of `composition`.
+pub fn gen_deck_of(rng: &mut Xs, composition: DeckComposition) -> Deck {
+    match composition {
+        DeckComposition::Full => gen_deck(rng),
+        DeckComposition::Short => gen_deck_without(rng, composition.excluded()),
+    }
+}
+
+#[test]
+fn short_decks_only_contain_six_and_up() {
+    let mut rng = xs::from_seed([0x6; 16]);
+
+    let mut deck = gen_deck_of(&mut rng, DeckComposition::Short);
+
+    let mut count = 0;
+    while let Some(card) = deck.draw() {
+        let rank = get_rank(card);
+        assert!(rank == ranks::ACE || rank >= 5, "{card}");
+        count += 1;
+    }
+
+    assert_eq!(count, 36);
+    assert_eq!(DeckComposition::Short.card_count(), 36);
+    assert_eq!(DeckComposition::Full.card_count(), DECK_SIZE);
+}
+
+/// Which cards, if any, can stand in for any other card.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum WildCards {
+    #[default]
+    None,
+    /// A `JOKER` is added to the deck, and it is wild.
+    Jokers,
+    /// The deck is the usual one, and all four twos are wild.
+    Deuces,
+}
+
+impl WildCards {
+    pub const ALL: [Self; 3] = [
+        Self::None,
+        Self::Jokers,
+        Self::Deuces,
+    ];
+
+    /// All the cards in a deck played with these rules.
+    pub fn cards(self) -> CardBitset {
+        let mut output = CardBitset::full();
+        if self == Self::Jokers {
+            output.set(JOKER);
+        }
+        output
+    }
+
+    pub fn is_wild(self, card: Card) -> bool {
+        match self {
+            Self::None => false,
+            Self::Jokers => card == JOKER,
+            Self::Deuces => card < DECK_SIZE && get_rank(card) == 1,
+        }
+    }
+
+    /// Generates a shuffled deck with the cards these rules are played with.
+    pub fn gen_deck(self, rng: &mut Xs) -> Deck {
+        match self {
+            Self::None | Self::Deuces => gen_deck(rng),
+            Self::Jokers => gen_deck_with_joker(rng),
+        }
+    }
+
+    /// A short name for the rules, meant to be shown above the word "wild".
+    pub fn text(self) -> &'static [u8] {
+        match self {
+            Self::None => b"no",
+            Self::Jokers => b"jokers",
+            Self::Deuces => b"deuces",
+        }
+    }
+
+    pub fn next_up(self) -> Self {
+        match self {
+            Self::None => Self::Deuces,
+            Self::Jokers => Self::None,
+            Self::Deuces => Self::Jokers,
+        }
+    }
+
+    pub fn next_down(self) -> Self {
+        match self {
+            Self::None => Self::Jokers,
+            Self::Jokers => Self::Deuces,
+            Self::Deuces => Self::None,
+        }
+    }
+}
+
+#[test]
+fn jokers_wild_decks_have_exactly_one_joker() {
+    let mut rng = xs::from_seed([0x7; 16]);
+
+    let mut deck = WildCards::Jokers.gen_deck(&mut rng);
+
+    let mut seen = CardBitset::default();
+    while let Some(card) = deck.draw() {
+        assert!(!seen.contains(card), "{card}");
+        seen.set(card);
+    }
+
+    assert_eq!(seen.len(), MAX_DECK_SIZE);
+    assert!(seen.contains(JOKER));
+}
+
+#[test]
+fn gen_deck_from_can_draw_the_joker() {
+    let mut rng = xs::from_seed([0x8; 16]);
+
+    let mut cards = CardBitset::default();
+    cards.set(JOKER);
+    cards.set(0);
+
+    let mut deck = gen_deck_from(&mut rng, cards);
+
+    let mut drawn = [deck.draw(), deck.draw()];
+    drawn.sort();
+    assert_eq!(drawn, [Some(0), Some(JOKER)]);
+    assert_eq!(deck.draw(), None);
+}
+
 type CardBits = u64;
 #[derive(Clone, Copy, Debug, Default)]
 pub struct CardBitset(CardBits);
 
 compile_time_assert!{
-    CardBits::BITS >= DECK_SIZE as u32
+    CardBits::BITS >= MAX_DECK_SIZE as u32
 }
 
 impl CardBitset {
@@ -143,15 +419,20 @@ impl CardBitset {
     }
 
     pub fn set(&mut self, card: Card){
-        if card > DECK_SIZE { return }
+        if card > JOKER { return }
         self.0 |= 1 << CardBits::from(card);
     }
 
     pub fn remove(&mut self, card: Card) {
-        if card > DECK_SIZE { return }
+        if card > JOKER { return }
         self.0 &= !(1 << CardBits::from(card));
     }
 
+    pub fn contains(&self, card: Card) -> bool {
+        if card > JOKER { return false }
+        self.0 & (1 << CardBits::from(card)) != 0
+    }
+
     pub fn iter(self) -> CardBitsetIter {
         CardBitsetIter {
             set: self,
@@ -178,7 +459,7 @@ impl Iterator for CardBitsetIter {
     type Item = Card;
 
     fn next(&mut self) -> Option<Self::Item> {
-        while self.index < DECK_SIZE {
+        while self.index <= JOKER {
             if (self.set.0 & 1 << self.index) != 0 {
                 let output = self.index;
 
@@ -590,6 +871,9 @@ pub enum AllowedKindMode {
     #[default]
     All,
     NoFolding,
+    /// Raising is not allowed, for example because the raise cap was reached,
+    /// or because the player's hand is not good enough to open.
+    NoRaising,
     AllIn,
 }
 
@@ -608,6 +892,11 @@ impl ActionKind {
                 Call => Raise,
                 Raise => Call,
             },
+            NoRaising => match self {
+                Fold => Call,
+                Call => Fold,
+                Raise => Call,
+            },
             AllIn => Call,
         }
     }
@@ -626,11 +915,87 @@ impl ActionKind {
                 Call => Raise,
                 Raise => Call,
             },
+            NoRaising => match self {
+                Fold => Call,
+                Call => Fold,
+                Raise => Call,
+            },
             AllIn => Call,
         }
     }
 }
 
+/// The rules for how much can be bet or raised.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum BettingStructure {
+    /// Any amount up to the whole stack, as long as raises are at least as
+    /// large as the previous raise in the round.
+    #[default]
+    NoLimit,
+    /// Any amount up to the size of the pot after calling, as long as raises
+    /// are at least as large as the previous raise in the round.
+    PotLimit,
+    /// Every bet and raise is exactly one bet size, and only
+    /// `FIXED_LIMIT_RAISE_CAP` of them are allowed per round.
+    FixedLimit,
+}
+
+impl BettingStructure {
+    pub const ALL: [Self; 3] = [
+        Self::NoLimit,
+        Self::PotLimit,
+        Self::FixedLimit,
+    ];
+
+    /// A short name for the structure, meant to be shown above the word "limit".
+    pub fn text(self) -> &'static [u8] {
+        use BettingStructure::*;
+        match self {
+            NoLimit => b"no",
+            PotLimit => b"pot",
+            FixedLimit => b"fixed",
+        }
+    }
+
+    pub fn next_up(self) -> Self {
+        use BettingStructure::*;
+        match self {
+            NoLimit => FixedLimit,
+            PotLimit => NoLimit,
+            FixedLimit => PotLimit,
+        }
+    }
+
+    pub fn next_down(self) -> Self {
+        use BettingStructure::*;
+        match self {
+            NoLimit => PotLimit,
+            PotLimit => FixedLimit,
+            FixedLimit => NoLimit,
+        }
+    }
+}
+
+pub type RaiseCount = u8;
+
+/// The amount of bets and raises allowed in a single betting round, under
+/// `BettingStructure::FixedLimit`. That is, a bet and three raises.
+pub const FIXED_LIMIT_RAISE_CAP: RaiseCount = 4;
+
+/// The inclusive range of amounts that a player can put in with a raise,
+/// counting the amount needed to call.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct RaiseRange {
+    pub min: MoneyInner,
+    pub max: MoneyInner,
+}
+
+impl RaiseRange {
+    pub fn clamp(self, amount: MoneyInner) -> MoneyInner {
+        amount.clamp(self.min, self.max)
+    }
+}
+
 mod pot {
     use super::*;
 
@@ -652,6 +1017,11 @@ mod pot {
             self.0 |= 1 << PerPlayerBits::from(index);
         }
 
+        pub fn contains(&self, index: PlayerIndex) -> bool {
+            if index > OVERALL_MAX_PLAYER_COUNT { return false }
+            self.0 & (1 << PerPlayerBits::from(index)) != 0
+        }
+
         pub fn iter(self) -> PerPlayerBitsetIter {
             PerPlayerBitsetIter {
                 set: self,
@@ -705,6 +1075,11 @@ mod pot {
         actions: PerPlayer<Vec<PotAction>>,
         player_count: PlayerCount,
         has_gone_this_round: PerPlayerBitset,
+        /// The amount the most recent full bet or raise in the current round
+        /// increased the call amount by.
+        last_raise_size: MoneyInner,
+        /// The amount of full bets and raises in the current round.
+        raise_count: RaiseCount,
     }
 
     #[cfg(test)]
@@ -716,6 +1091,8 @@ mod pot {
                 // Maybe a type with that as a minimum value
                 player_count: 2,
                 has_gone_this_round: <_>::default(),
+                last_raise_size: 0,
+                raise_count: 0,
             }
         }
     }
@@ -726,6 +1103,8 @@ mod pot {
                 actions: <_>::default(),
                 player_count,
                 has_gone_this_round: <_>::default(),
+                last_raise_size: 0,
+                raise_count: 0,
             };
 
             for vec in &mut output.actions {
@@ -736,11 +1115,115 @@ mod pot {
         }
     }
 
+    /// The players with the best hands for one pot, in games where each pot is
+    /// split between the best high hand and the best low hand.
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct HiLoWinners {
+        pub high: PerPlayerBitset,
+        /// Empty if no hand qualified for low, in which case the best high
+        /// hands win the whole pot.
+        pub low: PerPlayerBitset,
+    }
+
+    /// The amounts a player won for having the best high hand and for having
+    /// the best low hand.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct HiLoShare {
+        pub high: MoneyInner,
+        pub low: MoneyInner,
+    }
+
+    impl HiLoShare {
+        pub fn total(self) -> MoneyInner {
+            self.high.saturating_add(self.low)
+        }
+    }
+
+    /// One pot, main or side, and how it was split between the best hands.
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct PotSplit {
+        pub amount: MoneyInner,
+        /// The players who had a chance at this pot.
+        pub eligible: PerPlayerBitset,
+        /// How much of this pot each player won.
+        pub shares: PerPlayer<MoneyInner>,
+    }
+
+    /// Splits `amount` evenly among `winners`. Any odd chips go out one at a
+    /// time to the winners in seat order, starting from `first_seat`.
+    fn split_among_seats(
+        amount: MoneyInner,
+        winners: PerPlayerBitset,
+        first_seat: PlayerIndex,
+    ) -> PerPlayer<MoneyInner> {
+        let mut output = per_player![0];
+
+        let mut seats: PerPlayer<PlayerIndex> = per_player![0];
+        let mut seat_count = 0;
+        for offset in 0..OVERALL_MAX_PLAYER_COUNT {
+            let seat = (first_seat.saturating_add(offset)) % OVERALL_MAX_PLAYER_COUNT;
+            if winners.contains(seat) {
+                seats[seat_count] = seat;
+                seat_count += 1;
+            }
+        }
+
+        if seat_count == 0 {
+            debug_assert!(amount == 0, "split_among_seats called with no winners!");
+            return output
+        }
+
+        let mut amounts: PerPlayer<MoneyInner> = per_player![0];
+        crate::split_among(amount, &mut amounts[..seat_count], 0);
+
+        for i in 0..seat_count {
+            output[usize::from(seats[i])] = amounts[i];
+        }
+
+        output
+    }
+
+    /// Splits `amount` in half between the high and low winners. If the pot
+    /// cannot be split evenly in half, the odd chip goes to the high half.
+    /// Each half is split among the players who tied for it, so a player who
+    /// ties for only one half gets a quarter of the pot, or less. Within each
+    /// half, odd chips go to the winners in seat order, starting from
+    /// `first_seat`.
+    pub fn split_hi_lo(
+        amount: MoneyInner,
+        winners: HiLoWinners,
+        first_seat: PlayerIndex,
+    ) -> PerPlayer<HiLoShare> {
+        let mut output = per_player![HiLoShare::default()];
+
+        let (high_amount, low_amount) = if winners.low.len() == 0 {
+            (amount, 0)
+        } else {
+            let low_amount = (amount / MIN_MONEY_UNIT.get() / 2) * MIN_MONEY_UNIT.get();
+            (amount.saturating_sub(low_amount), low_amount)
+        };
+
+        let highs = split_among_seats(high_amount, winners.high, first_seat);
+        let lows = split_among_seats(low_amount, winners.low, first_seat);
+
+        for i in 0..output.len() {
+            output[i] = HiLoShare {
+                high: highs[i],
+                low: lows[i],
+            };
+        }
+
+        output
+    }
+
     #[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
     pub enum BetKind {
         #[default]
         Bet,
         Ante,
+        /// The forced bet made by the player showing the lowest card, at the
+        /// start of a stud hand.
+        BringIn,
     }
 
     impl Pot {
@@ -762,18 +1245,50 @@ mod pot {
             bet: PotAction,
             kind: BetKind
         ) {
+            let previous_call_amount = self.call_amount();
+
             match kind {
                 BetKind::Bet => {
                     self.has_gone_this_round.set(index);
                 },
-                // Adding an ante doesn't count as going for a round.
-                BetKind::Ante => {},
+                // Adding a forced bet doesn't count as going for a round.
+                BetKind::Ante
+                | BetKind::BringIn => {},
             }
             self.actions[usize::from(index)].push(bet);
+
+            if kind == BetKind::Bet {
+                let raise_size = self.call_amount()
+                    .saturating_sub(previous_call_amount);
+
+                // Going all in for less than a full raise does not change the
+                // size that the next raise needs to be.
+                if raise_size > 0 && raise_size >= self.last_raise_size {
+                    self.last_raise_size = raise_size;
+                    self.raise_count = self.raise_count.saturating_add(1);
+                }
+            }
         }
 
         pub fn reset_for_new_round(&mut self) {
             self.has_gone_this_round = <_>::default();
+            self.last_raise_size = 0;
+            self.raise_count = 0;
+        }
+
+        /// Starts the hand over, keeping everything already put in the pot.
+        /// Players who folded are dealt back in. Used when nobody opens in
+        /// games with an opening requirement.
+        pub fn restart_hand(&mut self) {
+            for actions in self.actions.iter_mut() {
+                actions.retain(|a| *a != PotAction::Fold);
+            }
+            self.reset_for_new_round();
+        }
+
+        /// Returns true if the player has already acted in the current round.
+        pub fn has_gone_this_round(&self, index: PlayerIndex) -> bool {
+            self.has_gone_this_round.contains(index)
         }
 
         pub fn has_folded(&self, index: PlayerIndex) -> bool {
@@ -966,6 +1481,59 @@ mod pot {
             self.amounts()[usize::from(index)]
         }
 
+        /// The amounts that the player at `index`, who has `stack` left, can put
+        /// in with a raise, or `None` if they cannot raise. `bet_size` is the
+        /// smallest bet allowed, for example the big blind, or for fixed-limit,
+        /// the size of every bet in the current round. Raises that would be
+        /// larger than the stack are capped to going all in.
+        pub fn raise_range(
+            &self,
+            index: PlayerIndex,
+            stack: MoneyInner,
+            structure: BettingStructure,
+            bet_size: NonZeroMoneyInner,
+        ) -> Option<RaiseRange> {
+            let call_amount = self.call_amount();
+            let call_remainder = call_amount.saturating_sub(self.amount_for(index));
+
+            if stack <= call_remainder {
+                return None
+            }
+
+            let min_raise_size = match structure {
+                BettingStructure::FixedLimit => {
+                    if self.raise_count >= FIXED_LIMIT_RAISE_CAP {
+                        return None
+                    }
+
+                    bet_size.get()
+                },
+                BettingStructure::NoLimit
+                | BettingStructure::PotLimit => {
+                    core::cmp::max(self.last_raise_size, bet_size.get())
+                },
+            };
+
+            let min = call_remainder.saturating_add(min_raise_size);
+
+            let max = match structure {
+                BettingStructure::NoLimit => stack,
+                // Calling first, then raising by the size of the pot after
+                // that call.
+                BettingStructure::PotLimit => call_remainder
+                    .saturating_add(self.total())
+                    .saturating_add(call_remainder),
+                BettingStructure::FixedLimit => min,
+            };
+
+            let max = core::cmp::min(max, stack);
+
+            Some(RaiseRange {
+                min: core::cmp::min(min, max),
+                max,
+            })
+        }
+
         fn amounts(&self) -> PerPlayer<MoneyInner> {
             let mut outputs: PerPlayer<MoneyInner> = per_player![0];
             for i in 0..OVERALL_MAX_PLAYER_COUNT as usize {
@@ -1034,6 +1602,113 @@ mod pot {
 
             assert_eq!(pile.as_inner(), 0);
         }
+
+        /// How much each player wins, when each pot, including each side pot,
+        /// is split with `split_hi_lo`. `winners_for` is passed the players
+        /// who can win a given pot, not including the ones who have folded,
+        /// and should return which of them have the best high and low hands.
+        pub fn hi_lo_shares(
+            &self,
+            current_money: &[Money],
+            mut winners_for: impl FnMut(PerPlayerBitset) -> HiLoWinners,
+            first_seat: PlayerIndex,
+        ) -> PerPlayer<HiLoShare> {
+            let mut output = per_player![HiLoShare::default()];
+
+            for (contributors, amount) in self.eligibilities(current_money) {
+                let mut live = PerPlayerBitset::default();
+                for index in contributors.iter() {
+                    if !self.has_folded(index) {
+                        live.set(index);
+                    }
+                }
+
+                if live.len() == 0 {
+                    debug_assert!(false, "Everyone who put in {amount} folded!");
+                    live = contributors;
+                }
+
+                let shares = split_hi_lo(amount, winners_for(live), first_seat);
+
+                for (total, share) in output.iter_mut().zip(shares) {
+                    total.high = total.high.saturating_add(share.high);
+                    total.low = total.low.saturating_add(share.low);
+                }
+            }
+
+            output
+        }
+
+        /// Each pot, including each side pot, split evenly among the best
+        /// hands of the players who put into it and did not fold. `eval_for`
+        /// is passed each of those players, and the highest results win. Odd
+        /// chips go out as with `split_among_seats`.
+        pub fn best_hand_splits<'pot, E: Ord>(
+            &'pot self,
+            current_money: &[Money],
+            mut eval_for: impl FnMut(PlayerIndex) -> E + 'pot,
+            first_seat: PlayerIndex,
+        ) -> impl Iterator<Item = PotSplit> + 'pot {
+            self.eligibilities(current_money).map(move |(contributors, amount)| {
+                // Folded players put money in, but cannot win it back.
+                let mut eligible = PerPlayerBitset::default();
+                for index in contributors.iter() {
+                    if !self.has_folded(index) {
+                        eligible.set(index);
+                    }
+                }
+                // Only expected if everyone who matched this level folded
+                // afterwards, but the money has to go somewhere.
+                if eligible.len() == 0 {
+                    eligible = contributors;
+                }
+
+                let mut winners = PerPlayerBitset::default();
+                let mut best = None;
+                for index in eligible.iter() {
+                    let eval = eval_for(index);
+
+                    use core::cmp::Ordering::*;
+                    match best.as_ref().map(|best| eval.cmp(best)) {
+                        None | Some(Greater) => {
+                            winners = PerPlayerBitset::default();
+                            winners.set(index);
+                            best = Some(eval);
+                        },
+                        Some(Equal) => {
+                            winners.set(index);
+                        },
+                        Some(Less) => {},
+                    }
+                }
+
+                PotSplit {
+                    amount,
+                    eligible,
+                    shares: split_among_seats(amount, winners, first_seat),
+                }
+            })
+        }
+
+        /// Awards every pot, split as by `hi_lo_shares`, and returns how much
+        /// each player won.
+        pub fn award_hi_lo(
+            &mut self,
+            moneys: &mut [Money],
+            winners_for: impl FnMut(PerPlayerBitset) -> HiLoWinners,
+            first_seat: PlayerIndex,
+        ) -> PerPlayer<HiLoShare> {
+            let shares = self.hi_lo_shares(moneys, winners_for, first_seat);
+
+            self.award_multiple(
+                moneys,
+                shares.iter()
+                    .enumerate()
+                    .map(|(i, share)| (i as PlayerIndex, share.total()))
+            );
+
+            shares
+        }
     }
 
     // We delibrately don't want to make this operation convenient outside of tests,
@@ -1190,6 +1865,160 @@ mod pot {
         }
     }
 
+    #[cfg(test)]
+    mod hi_lo_shares_works {
+        use super::*;
+
+        fn bitset(indexes: &[PlayerIndex]) -> PerPlayerBitset {
+            let mut output = PerPlayerBitset::default();
+            for &index in indexes {
+                output.set(index);
+            }
+            output
+        }
+
+        fn share(high: MoneyInner, low: MoneyInner) -> HiLoShare {
+            HiLoShare { high, low }
+        }
+
+        #[test]
+        fn split_hi_lo_on_these_examples() {
+            // No qualifying low, so the high hand scoops.
+            let shares = split_hi_lo(100, HiLoWinners { high: bitset(&[1]), low: bitset(&[]) }, 0);
+            assert_eq!(shares[..3], [share(0, 0), share(100, 0), share(0, 0)]);
+
+            // The same player can win both halves.
+            let shares = split_hi_lo(100, HiLoWinners { high: bitset(&[0]), low: bitset(&[0]) }, 0);
+            assert_eq!(shares[..2], [share(50, 50), share(0, 0)]);
+
+            // Two players tying for low get quartered.
+            let shares = split_hi_lo(100, HiLoWinners { high: bitset(&[0]), low: bitset(&[1, 2]) }, 0);
+            assert_eq!(shares[..3], [share(50, 0), share(0, 25), share(0, 25)]);
+
+            // The odd chip goes to the high half.
+            let shares = split_hi_lo(15, HiLoWinners { high: bitset(&[0]), low: bitset(&[1]) }, 0);
+            assert_eq!(shares[..2], [share(10, 0), share(0, 5)]);
+
+            // Odd chips within a half go to the first winner from `first_seat`.
+            let shares = split_hi_lo(30, HiLoWinners { high: bitset(&[0, 2]), low: bitset(&[1]) }, 1);
+            assert_eq!(shares[..3], [share(5, 0), share(0, 15), share(10, 0)]);
+        }
+
+        #[test]
+        fn on_these_examples() {
+            let mut pot = Pot::default();
+
+            pot.push_bet(0, PotAction::Bet(test_money_inner_to_money(100)));
+            pot.push_bet(1, PotAction::Bet(test_money_inner_to_money(300)));
+            pot.push_bet(2, PotAction::Bet(test_money_inner_to_money(300)));
+            pot.push_bet(3, PotAction::Bet(test_money_inner_to_money(50)));
+            pot.push_bet(3, PotAction::Fold);
+
+            // Player 0 is all in, so there is a side pot.
+            let moneys = Money::array_from_inner_array([0, 1, 1, 1]);
+
+            let mut asked = Vec::new();
+
+            let shares = pot.hi_lo_shares(
+                &moneys,
+                |live| {
+                    asked.push(live.iter().collect::<Vec<_>>());
+
+                    if live.contains(0) {
+                        // Player 0 has the best high, and player 1 the best
+                        // low, for the main pot.
+                        HiLoWinners { high: bitset(&[0]), low: bitset(&[1]) }
+                    } else {
+                        // Nobody qualifies for low in the side pot.
+                        HiLoWinners { high: bitset(&[2]), low: bitset(&[]) }
+                    }
+                },
+                0,
+            );
+
+            // The folded player is never passed in.
+            assert_eq!(asked, [vec![0, 1, 2], vec![1, 2]]);
+
+            // The main pot is 100 * 3 + 50.
+            assert_eq!(
+                shares[..4],
+                [share(175, 0), share(0, 175), share(400, 0), share(0, 0)]
+            );
+        }
+    }
+
+    #[cfg(test)]
+    mod best_hand_splits_works {
+        use super::*;
+
+        #[test]
+        fn on_these_examples() {
+            let mut pot = Pot::default();
+
+            pot.push_bet(0, PotAction::Bet(test_money_inner_to_money(100)));
+            pot.push_bet(1, PotAction::Bet(test_money_inner_to_money(300)));
+            pot.push_bet(2, PotAction::Bet(test_money_inner_to_money(300)));
+            pot.push_bet(3, PotAction::Bet(test_money_inner_to_money(50)));
+            pot.push_bet(3, PotAction::Fold);
+
+            // Player 0 is all in, so there is a side pot.
+            let moneys = Money::array_from_inner_array([0, 1, 1, 1]);
+
+            // Player 3 folded the best hand, and player 0 ties player 2.
+            let evals = [5, 1, 5, 9];
+
+            let mut asked = Vec::new();
+
+            let splits: Vec<PotSplit> = pot.best_hand_splits(
+                &moneys,
+                |player| {
+                    asked.push(player);
+                    evals[usize::from(player)]
+                },
+                0,
+            ).collect();
+
+            // The folded player is never passed in.
+            assert_eq!(asked, [0, 1, 2, 1, 2]);
+
+            assert_eq!(splits.len(), 2);
+
+            // The main pot is 100 * 3 + 50, split between the tied hands.
+            assert_eq!(splits[0].amount, 350);
+            assert_eq!(splits[0].eligible.iter().collect::<Vec<_>>(), [0, 1, 2]);
+            assert_eq!(splits[0].shares[..4], [175, 0, 175, 0]);
+
+            // Player 0 cannot win the side pot.
+            assert_eq!(splits[1].amount, 400);
+            assert_eq!(splits[1].eligible.iter().collect::<Vec<_>>(), [1, 2]);
+            assert_eq!(splits[1].shares[..4], [0, 0, 400, 0]);
+        }
+    }
+
+    #[cfg(test)]
+    mod restart_hand_works {
+        use super::*;
+
+        #[test]
+        fn keeps_the_money_and_deals_folded_players_back_in() {
+            let mut pot = Pot::default();
+
+            pot.push_bet(0, PotAction::Bet(test_money_inner_to_money(10)));
+            pot.push_bet(1, PotAction::Bet(test_money_inner_to_money(10)));
+            pot.push_bet(1, PotAction::Fold);
+
+            assert!(pot.has_folded(1));
+            assert!(pot.has_gone_this_round(0));
+
+            pot.restart_hand();
+
+            assert!(!pot.has_folded(1));
+            assert!(!pot.has_gone_this_round(0));
+            assert_eq!(pot.amount_for(0), 10);
+            assert_eq!(pot.amount_for(1), 10);
+        }
+    }
+
     #[cfg(test)]
     mod round_outcome_works {
         use super::{*, RoundOutcome::*};
@@ -1217,6 +2046,14 @@ mod pot {
             }
         }
 
+        fn bring_in(bet: MoneyInner) -> Spec {
+            Spec {
+                action: PotAction::Bet(test_money_inner_to_money(bet)),
+                is_all_in: false,
+                bet_kind: BetKind::BringIn,
+            }
+        }
+
         fn all_in(bet: MoneyInner) -> Spec {
             Spec {
                 action: PotAction::Bet(test_money_inner_to_money(bet)),
@@ -1287,16 +2124,67 @@ mod pot {
             a!([bet(5), bet(5)], AdvanceToNext);
             a!([ante(5), ante(5)], Undetermined);
             a!([ante(5), bet(5)], Undetermined);
+            a!([ante(5), bring_in(10)], Undetermined);
+            a!([bring_in(10), bet(10)], Undetermined);
+        }
+    }
+
+    #[cfg(test)]
+    mod raise_range_works {
+        use super::*;
+
+        const BET_SIZE: NonZeroMoneyInner = MIN_MONEY_UNIT.saturating_add(MIN_MONEY_UNIT.get());
+
+        // Short for assert
+        macro_rules! a {
+            ($bets: expr, $stack: expr, $structure: expr, $expected: expr) => {
+                let bets: &[(PlayerIndex, MoneyInner)] = &$bets;
+
+                let mut pot = Pot::default();
+
+                for &(i, bet) in bets {
+                    pot.push_bet(i, PotAction::Bet(test_money_inner_to_money(bet)));
+                }
+
+                let actual = pot.raise_range(2, $stack, $structure, BET_SIZE);
+
+                assert_eq!(actual, $expected, "{bets:?}");
+            }
+        }
+
+        fn range(min: MoneyInner, max: MoneyInner) -> Option<RaiseRange> {
+            Some(RaiseRange { min, max })
+        }
+
+        #[test]
+        fn on_these_examples() {
+            use BettingStructure::*;
+
+            // Opening the betting needs at least the bet size.
+            a!([], 500, NoLimit, range(10, 500));
+            // Re-raises need to be at least as large as the previous raise.
+            a!([(0, 10), (1, 40)], 500, NoLimit, range(70, 500));
+            // Short stacks can still go all in.
+            a!([(0, 10), (1, 40)], 50, NoLimit, range(50, 50));
+            a!([(0, 10), (1, 40)], 40, NoLimit, None);
+            // Calling 40, then raising by the 90 that would be in the pot.
+            a!([(0, 10), (1, 40)], 500, PotLimit, range(70, 130));
+            a!([(0, 10), (1, 40)], 100, PotLimit, range(70, 100));
+            a!([(0, 10)], 500, FixedLimit, range(20, 20));
+            a!([(0, 10), (1, 20), (0, 20), (1, 20)], 500, FixedLimit, None);
+            a!([(0, 10), (1, 20), (0, 20)], 500, FixedLimit, range(40, 40));
         }
     }
 }
 
-pub use pot::{BetKind, Pot, PotAction, RoundOutcome};
+pub use pot::{BetKind, HiLoShare, HiLoWinners, PerPlayerBitset, Pot, PotAction, PotSplit, RoundOutcome, split_hi_lo};
 
 pub mod holdem {
     use super::*;
 
-    pub type Hand = [Card; 2];
+    pub const HAND_LEN: u8 = 2;
+
+    pub type Hand = [Card; HAND_LEN as usize];
 
     pub fn short_hand_text(mut hand: Hand) -> [u8; 2] {
         let rank_0 = get_rank(hand[0]);
@@ -1509,14 +2397,16 @@ pub mod holdem {
         }
     }
 
+    /// The hands of each player, which are hold'em hands unless another game
+    /// that is played the same way, like Omaha, deals more cards to each.
     #[derive(Clone, Debug, Default)]
-    pub struct Hands {
-        hands: PerPlayer<Hand>,
+    pub struct Hands<H = Hand> {
+        hands: PerPlayer<H>,
         len: HandLen,
     }
 
-    impl Hands {
-        pub fn iter(&self) -> impl Iterator<Item = Hand> {
+    impl <H: Copy> Hands<H> {
+        pub fn iter(&self) -> impl Iterator<Item = H> {
             self.hands.into_iter().take(self.len.usize())
         }
 
@@ -1524,25 +2414,38 @@ pub mod holdem {
             self.len
         }
 
-        pub fn get(&self, index: HandIndex) -> Option<&Hand> {
+        pub fn get(&self, index: HandIndex) -> Option<&H> {
             self.hands.get(usize::from(index))
         }
     }
 
-    pub fn deal(
+    /// With a short deck there are only 36 cards, so after the community and
+    /// burn cards there are 28 left over, enough for 14 hands.
+    pub fn max_players(composition: DeckComposition) -> PlayerAmount {
+        max_players_with(HAND_LEN, composition)
+    }
+
+    /// Like `max_players`, but for hands with `hand_len` cards each.
+    pub fn max_players_with(hand_len: u8, composition: DeckComposition) -> PlayerAmount {
+        (composition.card_count() - 5 - 3) / hand_len
+    }
+
+    pub fn deal<H: Copy + Default + AsMut<[Card]>>(
         rng: &mut Xs,
         player_count: HandLen,
-    ) -> (Hands, Deck) {
-        let mut deck = gen_deck(rng);
+        composition: DeckComposition,
+    ) -> (Hands<H>, Deck) {
+        let mut deck = gen_deck_of(rng, composition);
 
-        let mut hands = Hands::default();
+        let mut hands = Hands::<H>::default();
 
         let count = player_count.usize();
 
-        for hand in (&mut hands.hands[0..count]).iter_mut() {
-            let (Some(card1), Some(card2)) = (deck.draw(), deck.draw())
-                else { continue };
-            *hand = [card1, card2];
+        for hand in &mut hands.hands[0..count] {
+            for card in hand.as_mut() {
+                let Some(drawn) = deck.draw() else { continue };
+                *card = drawn;
+            }
         }
 
         hands.len = player_count;
@@ -1552,7 +2455,7 @@ pub mod holdem {
 
     pub type Flop = [Card; 3];
 
-    #[derive(Clone, Copy)]
+    #[derive(Clone, Copy, PartialEq, Eq)]
     pub enum CommunityCards {
         Flop(Flop),
         Turn(Flop, Card),
@@ -1624,6 +2527,107 @@ pub mod holdem {
         }
     }
 
+    /// Returns true if there are more cards to come, and one more card of the
+    /// right kind would complete a flush or a straight that uses at least one
+    /// of the cards in `hand`.
+    pub fn has_draw(community_cards: CommunityCards, hand: Hand) -> bool {
+        let mut board = [0; 4];
+        let board_len = match community_cards {
+            CommunityCards::Flop(flop) => {
+                board[..3].copy_from_slice(&flop);
+                3
+            },
+            CommunityCards::Turn(flop, turn) => {
+                board[..3].copy_from_slice(&flop);
+                board[3] = turn;
+                4
+            },
+            CommunityCards::River(..) => return false,
+        };
+        let board = &board[..board_len];
+
+        let mut suit_counts = [0u8; SUIT_COUNT as usize];
+        for &card in board.iter().chain(hand.iter()) {
+            suit_counts[usize::from(get_suit(card))] += 1;
+        }
+        for card in hand {
+            if suit_counts[usize::from(get_suit(card))] == 4 {
+                return true
+            }
+        }
+
+        type RankBits = u16;
+        fn rank_bits(card: Card) -> RankBits {
+            let rank = get_rank(card);
+            let bits = 1 << rank;
+            if rank == ranks::ACE {
+                bits | 1 << ranks::HIGH_ACE
+            } else {
+                bits
+            }
+        }
+
+        let hand_ranks = rank_bits(hand[0]) | rank_bits(hand[1]);
+        let all_ranks = board.iter()
+            .fold(hand_ranks, |acc, &card| acc | rank_bits(card));
+
+        let mut made_straight = false;
+        let mut straight_draw = false;
+        for start in 0..=(ranks::HIGH_ACE - 4) {
+            let window: RankBits = 0b11111 << start;
+            match (all_ranks & window).count_ones() {
+                5 => { made_straight = true; },
+                4 if hand_ranks & window != 0 => { straight_draw = true; },
+                _ => {},
+            }
+        }
+
+        straight_draw && !made_straight
+    }
+
+    #[test]
+    fn has_draw_works_on_these_examples() {
+        // Ranks are 0 for ace up to 12 for king, and suits advance by 13.
+        const CLUBS: Card = 0;
+        const HEARTS: Card = 26;
+
+        // Four clubs, two from the hand.
+        assert!(has_draw(
+            CommunityCards::Flop([CLUBS + 2, CLUBS + 7, HEARTS + 11]),
+            [CLUBS + 4, CLUBS + 9],
+        ));
+        // Four clubs, but all of them on the board.
+        assert!(!has_draw(
+            CommunityCards::Turn([CLUBS + 2, CLUBS + 7, CLUBS + 11], CLUBS + 4),
+            [HEARTS + 5, HEARTS + 9],
+        ));
+        // Open ended: 5, 6, 7, 8.
+        assert!(has_draw(
+            CommunityCards::Flop([CLUBS + 4, HEARTS + 5, HEARTS + 12]),
+            [CLUBS + 6, HEARTS + 7],
+        ));
+        // Wheel draw, with the ace low: A, 2, 3, 4.
+        assert!(has_draw(
+            CommunityCards::Flop([CLUBS + 1, HEARTS + 2, HEARTS + 9]),
+            [CLUBS, CLUBS + 3 + 13],
+        ));
+        // Ace high: J, Q, K, A.
+        assert!(has_draw(
+            CommunityCards::Flop([CLUBS + 10, HEARTS + 11, HEARTS + 4]),
+            [CLUBS + 12, HEARTS],
+        ));
+        // Nothing.
+        assert!(!has_draw(
+            CommunityCards::Flop([CLUBS + 1, HEARTS + 5, HEARTS + 9]),
+            [CLUBS + 12, HEARTS + 7],
+        ));
+        // No more cards to come.
+        assert!(!has_draw(
+            CommunityCards::River([CLUBS + 2, CLUBS + 7, HEARTS + 11], HEARTS + 1, HEARTS + 3),
+            [CLUBS + 4, CLUBS + 9],
+        ));
+    }
+
     pub type FullBoard = [Card; 5];
 
     impl From<FullBoard> for CommunityCards {
@@ -1641,3 +2645,235 @@ pub mod holdem {
     }
 }
 
+
+pub mod omaha {
+    use super::{Card, holdem};
+
+    pub use holdem::{CommunityCards, FullBoard, MIN_PLAYERS, PlayerAmount, deal_community_cards, deal_to_community_cards};
+
+    pub const HAND_LEN: u8 = 4;
+
+    pub type Hand = [Card; HAND_LEN as usize];
+
+    /// Exactly this many of the cards in a `Hand` must be used.
+    pub const HOLE_CARDS_USED: u8 = 2;
+
+    /// Each of the ways to pick the `HOLE_CARDS_USED` cards out of a `Hand`,
+    /// as indexes into the hand.
+    pub const HOLE_CARD_PAIRS: [[u8; HOLE_CARDS_USED as usize]; 6] = [
+        [0, 1],
+        [0, 2],
+        [0, 3],
+        [1, 2],
+        [1, 3],
+        [2, 3],
+    ];
+
+    pub fn hole_card_pairs(hand: Hand) -> impl Iterator<Item = holdem::Hand> {
+        HOLE_CARD_PAIRS.into_iter()
+            .map(move |[i, j]| [hand[usize::from(i)], hand[usize::from(j)]])
+    }
+
+    /// With 52 cards, and 5 community cards, and 3 burn cards,
+    /// that leaves 44 cards left over so the maximum amount of
+    /// possible four card hands is 11.
+    pub const MAX_PLAYERS: PlayerAmount = 11;
+
+    /// Returns true if any two of the cards in `hand` would have a draw in
+    /// hold'em. This is only an estimate, since it counts some draws that use
+    /// fewer than two of the cards in `hand`, which do not count in Omaha.
+    pub fn has_draw(community_cards: CommunityCards, hand: Hand) -> bool {
+        hole_card_pairs(hand)
+            .any(|pair| holdem::has_draw(community_cards, pair))
+    }
+
+    #[test]
+    fn hole_card_pairs_covers_each_pair_once() {
+        let pairs: Vec<_> = hole_card_pairs([10, 20, 30, 40]).collect();
+
+        assert_eq!(
+            pairs,
+            [[10, 20], [10, 30], [10, 40], [20, 30], [20, 40], [30, 40]]
+        );
+    }
+}
+
+pub mod stud {
+    use super::{Card, Deck, DeckComposition, SUIT_COUNT, get_rank, get_suit, holdem::{self, HandLen, Hands}, ranks};
+    use xs::Xs;
+
+    pub const HAND_LEN: u8 = 7;
+
+    pub type Hand = [Card; HAND_LEN as usize];
+
+    /// The most cards that are face up in a hand at once.
+    pub const MAX_UP_CARDS: u8 = 4;
+
+    pub type UpCards = [Card; MAX_UP_CARDS as usize];
+
+    /// The third through sixth cards are dealt face up. The rest are face down.
+    pub const fn is_up(card_index: u8) -> bool {
+        card_index >= 2 && card_index < 2 + MAX_UP_CARDS
+    }
+
+    /// The face up cards in the first `dealt` cards of `hand`, and how many of
+    /// them there are.
+    pub fn up_cards(hand: &Hand, dealt: u8) -> (UpCards, u8) {
+        let mut output = UpCards::default();
+        let mut len = 0;
+        for i in 0..dealt.min(HAND_LEN) {
+            if is_up(i) {
+                output[usize::from(len)] = hand[usize::from(i)];
+                len += 1;
+            }
+        }
+
+        (output, len)
+    }
+
+    /// Each street has its own betting round.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub enum Street {
+        #[default]
+        Third,
+        Fourth,
+        Fifth,
+        Sixth,
+        Seventh,
+    }
+
+    impl Street {
+        /// The amount of cards each player has been dealt, on this street.
+        pub fn card_count(self) -> u8 {
+            match self {
+                Self::Third => 3,
+                Self::Fourth => 4,
+                Self::Fifth => 5,
+                Self::Sixth => 6,
+                Self::Seventh => 7,
+            }
+        }
+
+        /// The amount of cards each player has face up, on this street.
+        pub fn up_card_count(self) -> u8 {
+            match self {
+                Self::Third => 1,
+                Self::Fourth => 2,
+                Self::Fifth => 3,
+                Self::Sixth
+                | Self::Seventh => MAX_UP_CARDS,
+            }
+        }
+
+        pub fn next(self) -> Option<Self> {
+            match self {
+                Self::Third => Some(Self::Fourth),
+                Self::Fourth => Some(Self::Fifth),
+                Self::Fifth => Some(Self::Sixth),
+                Self::Sixth => Some(Self::Seventh),
+                Self::Seventh => None,
+            }
+        }
+    }
+
+    pub const MIN_PLAYERS: u8 = 2;
+    /// With 52 cards, and 7 cards per player, and no community cards, 7 is the
+    /// most players that can be dealt in, even without any burn cards.
+    pub const MAX_PLAYERS: u8 = 7;
+
+    /// Deals out every card each player will get, in advance. Cards past the
+    /// ones dealt so far on the current street should not be shown or used yet.
+    pub fn deal(
+        rng: &mut Xs,
+        player_count: HandLen,
+    ) -> (Hands<Hand>, Deck) {
+        holdem::deal(rng, player_count, DeckComposition::Full)
+    }
+
+    /// Returns true if there are more cards to come, and one more card of the
+    /// right kind would complete a flush or a straight, with the cards dealt so
+    /// far on `street`.
+    pub fn has_draw(hand: &Hand, street: Street) -> bool {
+        if street == Street::Seventh {
+            return false
+        }
+
+        let dealt = &hand[..usize::from(street.card_count())];
+
+        let mut suit_counts = [0u8; SUIT_COUNT as usize];
+        for &card in dealt {
+            suit_counts[usize::from(get_suit(card))] += 1;
+        }
+        if suit_counts.contains(&4) {
+            return true
+        }
+
+        let mut rank_bits: u16 = 0;
+        for &card in dealt {
+            let rank = get_rank(card);
+            rank_bits |= 1 << rank;
+            if rank == ranks::ACE {
+                rank_bits |= 1 << ranks::HIGH_ACE;
+            }
+        }
+
+        let mut made_straight = false;
+        let mut straight_draw = false;
+        for start in 0..=(ranks::HIGH_ACE - 4) {
+            let window: u16 = 0b11111 << start;
+            match (rank_bits & window).count_ones() {
+                5 => { made_straight = true; },
+                4 => { straight_draw = true; },
+                _ => {},
+            }
+        }
+
+        straight_draw && !made_straight
+    }
+
+    /// Orders the face up cards for deciding who brings in. The lowest rank
+    /// brings in, with aces high, and ties are broken by suit, from clubs
+    /// being the lowest, up to spades being the highest.
+    pub fn bring_in_key(card: Card) -> (u8, u8) {
+        let rank = get_rank(card);
+        let rank = if rank == ranks::ACE { ranks::HIGH_ACE } else { rank };
+
+        (rank, get_suit(card))
+    }
+
+    #[test]
+    fn bring_in_key_puts_deuces_low_and_aces_high() {
+        // Ranks are 0 for ace up to 12 for king, and suits advance by 13.
+        const CLUBS: Card = 0;
+        const SPADES: Card = 39;
+
+        assert!(bring_in_key(CLUBS + 1) < bring_in_key(SPADES + 1));
+        assert!(bring_in_key(SPADES + 1) < bring_in_key(CLUBS + 2));
+        assert!(bring_in_key(CLUBS + 12) < bring_in_key(CLUBS));
+    }
+
+    #[test]
+    fn has_draw_works_on_these_examples() {
+        // Ranks are 0 for ace up to 12 for king, and suits advance by 13.
+        const CLUBS: Card = 0;
+        const DIAMONDS: Card = 13;
+
+        // Four clubs, but only from fifth street on.
+        let hand = [CLUBS + 1, CLUBS + 5, DIAMONDS + 9, CLUBS + 11, CLUBS + 7, 0, 0];
+        assert!(!has_draw(&hand, Street::Fourth));
+        assert!(has_draw(&hand, Street::Fifth));
+
+        // Two, three, four and five
+        let hand = [CLUBS + 1, DIAMONDS + 2, CLUBS + 3, DIAMONDS + 4, 0, 0, 0];
+        assert!(has_draw(&hand, Street::Fourth));
+        assert!(!has_draw(&hand, Street::Seventh));
+    }
+
+    #[test]
+    fn up_cards_are_the_middle_four() {
+        let hand = [10, 11, 12, 13, 14, 15, 16];
+
+        assert_eq!(up_cards(&hand, 3), ([12, 0, 0, 0], 1));
+        assert_eq!(up_cards(&hand, 7), ([12, 13, 14, 15], 4));
+    }
+}
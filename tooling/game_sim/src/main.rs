@@ -0,0 +1,259 @@
+use game::{FRAMES_PER_SECOND, LevelSchedule, OpeningRequirement, SecondRoundStart, SubGame, headless::{self, Spec}};
+use models::{BettingStructure, DeckComposition, WildCards};
+
+const USAGE: &str = "usage: game_sim --seed <n> --game <game> --players <n> --hands <n> [<option>...]
+
+--seed <n> is a number, used to seed the rng.
+--game <game> is one of: holdem, short-deck, acey-deucey, five-card-draw, omaha, seven-card-stud
+--players <n> is the amount of players at the table.
+--hands <n> is the total amount of hands to play, across as many games as that takes.
+
+options:
+--limit <limit> is one of: no-limit, pot-limit, fixed-limit. Defaults to pot-limit for omaha,
+and no-limit otherwise.
+--levels <levels> is one of: none, hands:<n>, minutes:<n>. Defaults to none.
+--wilds <wilds> is one of: none, jokers, deuces. Defaults to none. Only used by five-card-draw.
+--opening <opening> is one of: any, jacks-or-better. Defaults to any. Only used by five-card-draw.
+--second <second> is who bets first after the draw, one of: dealers-left, last-aggressor, opener.
+Defaults to dealers-left. Only used by five-card-draw.";
+
+fn parse_sub_game(name: &str) -> Option<(SubGame, DeckComposition)> {
+    match name {
+        "holdem" => Some((SubGame::Holdem, DeckComposition::Full)),
+        "short-deck" => Some((SubGame::Holdem, DeckComposition::Short)),
+        "acey-deucey" => Some((SubGame::AceyDeucey, DeckComposition::Full)),
+        "five-card-draw" => Some((SubGame::FiveCardDraw, DeckComposition::Full)),
+        "omaha" => Some((SubGame::Omaha, DeckComposition::Full)),
+        "seven-card-stud" => Some((SubGame::SevenCardStud, DeckComposition::Full)),
+        _ => None,
+    }
+}
+
+fn parse_betting_structure(name: &str) -> Option<BettingStructure> {
+    match name {
+        "no-limit" => Some(BettingStructure::NoLimit),
+        "pot-limit" => Some(BettingStructure::PotLimit),
+        "fixed-limit" => Some(BettingStructure::FixedLimit),
+        _ => None,
+    }
+}
+
+fn parse_wilds(name: &str) -> Option<WildCards> {
+    match name {
+        "none" => Some(WildCards::None),
+        "jokers" => Some(WildCards::Jokers),
+        "deuces" => Some(WildCards::Deuces),
+        _ => None,
+    }
+}
+
+fn parse_opening(name: &str) -> Option<OpeningRequirement> {
+    match name {
+        "any" => Some(OpeningRequirement::Any),
+        "jacks-or-better" => Some(OpeningRequirement::JacksOrBetter),
+        _ => None,
+    }
+}
+
+fn parse_second_round_start(name: &str) -> Option<SecondRoundStart> {
+    match name {
+        "dealers-left" => Some(SecondRoundStart::DealersLeft),
+        "last-aggressor" => Some(SecondRoundStart::LastAggressor),
+        "opener" => Some(SecondRoundStart::Opener),
+        _ => None,
+    }
+}
+
+fn parse_level_schedule(name: &str) -> Option<LevelSchedule> {
+    if name == "none" {
+        return Some(LevelSchedule::Flat)
+    }
+
+    let (unit, count) = name.split_once(':')?;
+    let count = count.parse().ok().filter(|&count| count > 0)?;
+
+    match unit {
+        "hands" => Some(LevelSchedule::Hands(count)),
+        "minutes" => Some(LevelSchedule::Frames(
+            count.checked_mul(60 * FRAMES_PER_SECOND)?
+        )),
+        _ => None,
+    }
+}
+
+/// The value of each flag, before parsing, if it was passed.
+#[derive(Default)]
+struct Flags {
+    seed: Option<String>,
+    game: Option<String>,
+    players: Option<String>,
+    hands: Option<String>,
+    limit: Option<String>,
+    levels: Option<String>,
+    wilds: Option<String>,
+    opening: Option<String>,
+    second: Option<String>,
+}
+
+impl Flags {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut flags = Self::default();
+
+        while let Some(flag) = args.next() {
+            let slot = match flag.as_str() {
+                "--seed" => &mut flags.seed,
+                "--game" => &mut flags.game,
+                "--players" => &mut flags.players,
+                "--hands" => &mut flags.hands,
+                "--limit" => &mut flags.limit,
+                "--levels" => &mut flags.levels,
+                "--wilds" => &mut flags.wilds,
+                "--opening" => &mut flags.opening,
+                "--second" => &mut flags.second,
+                _ => return Err(format!("unknown flag: {flag}")),
+            };
+
+            let value = args.next()
+                .ok_or_else(|| format!("missing value for {flag}"))?;
+
+            if slot.replace(value).is_some() {
+                return Err(format!("{flag} was passed more than once"));
+            }
+        }
+
+        Ok(flags)
+    }
+}
+
+fn parse_spec(args: impl Iterator<Item = String>) -> Result<Spec, String> {
+    let flags = Flags::parse(args)?;
+
+    let required = |value: Option<String>, flag: &str| {
+        value.ok_or_else(|| format!("missing {flag}"))
+    };
+
+    let seed: u128 = required(flags.seed, "--seed")?
+        .parse()
+        .map_err(|e| format!("bad seed: {e}"))?;
+
+    let game_name = required(flags.game, "--game")?;
+    let (sub_game, deck_composition) = parse_sub_game(&game_name)
+        .ok_or_else(|| format!("unknown game: {game_name}"))?;
+
+    let max_player_count = match sub_game {
+        SubGame::Holdem => models::holdem::max_players(deck_composition),
+        _ => sub_game.max_player_count(),
+    };
+
+    let player_count = required(flags.players, "--players")?
+        .parse()
+        .map_err(|e| format!("bad player count: {e}"))?;
+
+    if player_count < sub_game.min_player_count()
+    || player_count > max_player_count {
+        return Err(format!(
+            "{game_name} needs between {} and {max_player_count} players",
+            sub_game.min_player_count(),
+        ));
+    }
+
+    let hand_count = required(flags.hands, "--hands")?
+        .parse()
+        .map_err(|e| format!("bad hand count: {e}"))?;
+
+    let betting_structure = match flags.limit {
+        Some(limit_name) => parse_betting_structure(&limit_name)
+            .ok_or_else(|| format!("unknown limit: {limit_name}"))?,
+        None if sub_game == SubGame::Omaha => BettingStructure::PotLimit,
+        None => BettingStructure::default(),
+    };
+
+    let level_schedule = match flags.levels {
+        Some(levels_name) => parse_level_schedule(&levels_name)
+            .ok_or_else(|| format!("unknown levels: {levels_name}"))?,
+        None => LevelSchedule::default(),
+    };
+
+    let wilds = match flags.wilds {
+        Some(wilds_name) => parse_wilds(&wilds_name)
+            .ok_or_else(|| format!("unknown wilds: {wilds_name}"))?,
+        None => WildCards::default(),
+    };
+
+    let opening = match flags.opening {
+        Some(opening_name) => parse_opening(&opening_name)
+            .ok_or_else(|| format!("unknown opening: {opening_name}"))?,
+        None => OpeningRequirement::default(),
+    };
+
+    let second_round_start = match flags.second {
+        Some(second_name) => parse_second_round_start(&second_name)
+            .ok_or_else(|| format!("unknown second: {second_name}"))?,
+        None => SecondRoundStart::default(),
+    };
+
+    Ok(Spec {
+        seed: seed.to_le_bytes(),
+        sub_game,
+        player_count,
+        hand_count,
+        betting_structure,
+        deck_composition,
+        wilds,
+        opening,
+        second_round_start,
+        level_schedule,
+        ..Spec::default()
+    })
+}
+
+fn main() {
+    let spec = match parse_spec(std::env::args().skip(1)) {
+        Ok(spec) => spec,
+        Err(error) => {
+            eprintln!("{error}\n\n{USAGE}");
+            std::process::exit(2);
+        }
+    };
+
+    let report = headless::simulate(spec);
+
+    println!(
+        "{} hands, {} finished games, {} unfinished games",
+        report.hands,
+        report.games_finished,
+        report.games_unfinished,
+    );
+
+    println!("win rates per starting seat:");
+    for seat in 0..usize::from(spec.player_count) {
+        let wins = report.wins[seat];
+        let percent = if report.games_finished == 0 {
+            0.0
+        } else {
+            f64::from(wins) * 100.0 / f64::from(report.games_finished)
+        };
+        println!("    seat {seat:>2}: {wins:>6} wins ({percent:.1}%)");
+    }
+
+    match report.average_pot() {
+        Some(average) => println!("average pot: ${average}"),
+        None => println!("average pot: n/a"),
+    }
+
+    match report.average_hands_to_elimination() {
+        Some(average) => println!("average hands to elimination: {average}"),
+        None => println!("average hands to elimination: n/a"),
+    }
+
+    if report.violations.is_empty() {
+        println!("no invariant violations");
+    } else {
+        println!("{} invariant violations:", report.violations.len());
+        for violation in &report.violations {
+            println!("    {violation}");
+        }
+
+        std::process::exit(1);
+    }
+}